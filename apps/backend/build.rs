@@ -12,6 +12,8 @@ fn main() -> Result<()> {
             "src/proto/control/connect.proto",
             "src/proto/control/disconnect.proto",
             "src/proto/control/heartbeat.proto",
+            "src/proto/control/ping.proto",
+            "src/proto/control/reconnect.proto",
         ],
         &["src/proto/"],
     )?;
@@ -20,10 +22,13 @@ fn main() -> Result<()> {
             "src/proto/lobby/broadcast.proto",
             "src/proto/lobby/create.proto",
             "src/proto/lobby/join.proto",
+            "src/proto/lobby/kick.proto",
             "src/proto/lobby/list.proto",
             "src/proto/lobby/lobby.proto",
             "src/proto/lobby/quit.proto",
             "src/proto/lobby/ready.proto",
+            "src/proto/lobby/rematch.proto",
+            "src/proto/lobby/update.proto",
         ],
         &["src/proto/"],
     )?;
@@ -37,11 +42,48 @@ fn main() -> Result<()> {
             "src/proto/game/cards.proto",
             "src/proto/game/broadcast.proto",
             "src/proto/game/finish_turn.proto",
+            "src/proto/game/get_board.proto",
             "src/proto/game/get_new_card.proto",
             "src/proto/game/set_tile.proto",
             "src/proto/game/cancel.proto",
             "src/proto/game/exit.proto",
             "src/proto/game/words.proto",
+            "src/proto/game/spectate.proto",
+            "src/proto/game/stop_spectating.proto",
+            "src/proto/game/vote_kick.proto",
+        ],
+        &["src/proto/"],
+    )?;
+    prost_build::compile_protos(
+        &["src/proto/chat/message.proto", "src/proto/chat/send.proto"],
+        &["src/proto/"],
+    )?;
+    prost_build::compile_protos(
+        &[
+            "src/proto/matchmaking/cancel.proto",
+            "src/proto/matchmaking/quick_play.proto",
+        ],
+        &["src/proto/"],
+    )?;
+    prost_build::compile_protos(
+        &[
+            "src/proto/friend/accept.proto",
+            "src/proto/friend/add.proto",
+            "src/proto/friend/broadcast.proto",
+            "src/proto/friend/friend.proto",
+            "src/proto/friend/invite.proto",
+            "src/proto/friend/list.proto",
+            "src/proto/friend/remove.proto",
+        ],
+        &["src/proto/"],
+    )?;
+    prost_build::compile_protos(
+        &[
+            "src/proto/stats/history.proto",
+            "src/proto/stats/leaderboard.proto",
+            "src/proto/stats/match_record.proto",
+            "src/proto/stats/player_stats.proto",
+            "src/proto/stats/profile.proto",
         ],
         &["src/proto/"],
     )?;
@@ -0,0 +1,6 @@
+#[allow(clippy::module_inception)]
+pub mod account;
+pub mod account_repository;
+pub mod friend;
+pub mod friendship;
+pub mod password;
@@ -0,0 +1,11 @@
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Account {
+    pub id: u32,
+    pub username: String,
+}
+
+impl Account {
+    pub fn new(id: u32, username: String) -> Self {
+        Self { id, username }
+    }
+}
@@ -0,0 +1,230 @@
+use std::{error::Error, sync::Mutex};
+
+use rusqlite::{params, Connection, OptionalExtension};
+
+use super::{account::Account, friendship::Friendship};
+
+#[derive(Debug)]
+pub struct AccountRepository {
+    connection: Mutex<Connection>,
+}
+
+impl AccountRepository {
+    pub fn open(path: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        AccountRepository::init(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self, Box<dyn Error + Send + Sync>> {
+        AccountRepository::init(Connection::open_in_memory()?)
+    }
+
+    fn init(connection: Connection) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS account (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                username TEXT NOT NULL UNIQUE COLLATE NOCASE,
+                password_hash TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS friendship (
+                requester_id INTEGER NOT NULL REFERENCES account(id),
+                addressee_id INTEGER NOT NULL REFERENCES account(id),
+                accepted INTEGER NOT NULL,
+                PRIMARY KEY (requester_id, addressee_id)
+            );
+            CREATE INDEX IF NOT EXISTS friendship_addressee_id ON friendship(addressee_id);",
+        )?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    pub fn create_account(
+        &self,
+        username: &str,
+        password_hash: &str,
+    ) -> Result<Account, Box<dyn Error + Send + Sync>> {
+        let connection = self.connection.lock().unwrap();
+        connection.execute(
+            "INSERT INTO account (username, password_hash) VALUES (?1, ?2)",
+            params![username, password_hash],
+        )?;
+        Ok(Account::new(
+            connection.last_insert_rowid() as u32,
+            String::from(username),
+        ))
+    }
+
+    pub fn find_by_username(
+        &self,
+        username: &str,
+    ) -> Result<Option<(Account, String)>, Box<dyn Error + Send + Sync>> {
+        Ok(self
+            .connection
+            .lock()
+            .unwrap()
+            .query_row(
+                "SELECT id, username, password_hash FROM account WHERE username = ?1",
+                params![username],
+                |row| Ok((Account::new(row.get(0)?, row.get(1)?), row.get(2)?)),
+            )
+            .optional()?)
+    }
+
+    pub fn find_by_id(&self, id: u32) -> Result<Option<Account>, Box<dyn Error + Send + Sync>> {
+        Ok(self
+            .connection
+            .lock()
+            .unwrap()
+            .query_row(
+                "SELECT id, username FROM account WHERE id = ?1",
+                params![id],
+                |row| Ok(Account::new(row.get(0)?, row.get(1)?)),
+            )
+            .optional()?)
+    }
+
+    pub fn find_friendship(
+        &self,
+        account_id: u32,
+        other_id: u32,
+    ) -> Result<Option<Friendship>, Box<dyn Error + Send + Sync>> {
+        Ok(self
+            .connection
+            .lock()
+            .unwrap()
+            .query_row(
+                "SELECT requester_id, addressee_id, accepted FROM friendship
+                WHERE (requester_id = ?1 AND addressee_id = ?2)
+                    OR (requester_id = ?2 AND addressee_id = ?1)",
+                params![account_id, other_id],
+                |row| Ok(Friendship::new(row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()?)
+    }
+
+    pub fn create_friend_request(
+        &self,
+        requester_id: u32,
+        addressee_id: u32,
+    ) -> Result<Friendship, Box<dyn Error + Send + Sync>> {
+        self.connection.lock().unwrap().execute(
+            "INSERT INTO friendship (requester_id, addressee_id, accepted) VALUES (?1, ?2, 0)",
+            params![requester_id, addressee_id],
+        )?;
+        Ok(Friendship::new(requester_id, addressee_id, false))
+    }
+
+    pub fn accept_friend_request(
+        &self,
+        requester_id: u32,
+        addressee_id: u32,
+    ) -> Result<bool, Box<dyn Error + Send + Sync>> {
+        Ok(self.connection.lock().unwrap().execute(
+            "UPDATE friendship SET accepted = 1
+            WHERE requester_id = ?1 AND addressee_id = ?2 AND accepted = 0",
+            params![requester_id, addressee_id],
+        )? > 0)
+    }
+
+    pub fn delete_friendship(
+        &self,
+        account_id: u32,
+        other_id: u32,
+    ) -> Result<bool, Box<dyn Error + Send + Sync>> {
+        Ok(self.connection.lock().unwrap().execute(
+            "DELETE FROM friendship
+            WHERE (requester_id = ?1 AND addressee_id = ?2)
+                OR (requester_id = ?2 AND addressee_id = ?1)",
+            params![account_id, other_id],
+        )? > 0)
+    }
+
+    pub fn get_friendships(
+        &self,
+        account_id: u32,
+    ) -> Result<Vec<(Account, Friendship)>, Box<dyn Error + Send + Sync>> {
+        let connection = self.connection.lock().unwrap();
+        let mut statement = connection.prepare(
+            "SELECT account.id, account.username, requester_id, addressee_id, accepted
+            FROM friendship JOIN account ON account.id = CASE
+                WHEN requester_id = ?1 THEN addressee_id ELSE requester_id END
+            WHERE requester_id = ?1 OR addressee_id = ?1
+            ORDER BY account.username",
+        )?;
+        let friendships = statement
+            .query_map(params![account_id], |row| {
+                Ok((
+                    Account::new(row.get(0)?, row.get(1)?),
+                    Friendship::new(row.get(2)?, row.get(3)?, row.get(4)?),
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(friendships)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_account_with_new_username_should_be_found() -> Result<(), Box<dyn Error + Send + Sync>>
+    {
+        let repository = AccountRepository::open_in_memory()?;
+        let account = repository.create_account("test", "hash")?;
+        let (found, password_hash) = repository.find_by_username("TEST")?.unwrap();
+        assert_eq!(found, account);
+        assert_eq!(password_hash, "hash");
+        Ok(())
+    }
+
+    #[test]
+    fn create_account_with_taken_username_should_return_error(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let repository = AccountRepository::open_in_memory()?;
+        repository.create_account("test", "hash")?;
+        assert!(repository.create_account("Test", "hash").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn find_by_username_with_unknown_username_should_return_none(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let repository = AccountRepository::open_in_memory()?;
+        assert!(repository.find_by_username("test")?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn accept_friend_request_with_pending_request_should_be_accepted(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let repository = AccountRepository::open_in_memory()?;
+        let account1 = repository.create_account("test1", "hash")?;
+        let account2 = repository.create_account("test2", "hash")?;
+        repository.create_friend_request(account1.id, account2.id)?;
+        assert!(!repository.accept_friend_request(account2.id, account1.id)?);
+        assert!(repository.accept_friend_request(account1.id, account2.id)?);
+        assert_eq!(
+            repository.find_friendship(account2.id, account1.id)?,
+            Some(Friendship::new(account1.id, account2.id, true))
+        );
+        assert_eq!(
+            repository.get_friendships(account2.id)?,
+            vec![(account1, Friendship::new(1, 2, true))]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn delete_friendship_with_friendship_should_remove_both_sides(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let repository = AccountRepository::open_in_memory()?;
+        let account1 = repository.create_account("test1", "hash")?;
+        let account2 = repository.create_account("test2", "hash")?;
+        repository.create_friend_request(account1.id, account2.id)?;
+        assert!(repository.delete_friendship(account2.id, account1.id)?);
+        assert!(repository.get_friendships(account1.id)?.is_empty());
+        assert!(!repository.delete_friendship(account2.id, account1.id)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,10 @@
+use super::{account::Account, friendship::FriendStatus};
+use crate::player::Presence;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Friend {
+    pub account: Account,
+    pub status: FriendStatus,
+    pub presence: Presence,
+    pub lobby_id: Option<u32>,
+}
@@ -0,0 +1,55 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Friendship {
+    pub requester_id: u32,
+    pub addressee_id: u32,
+    pub accepted: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FriendStatus {
+    Accepted,
+    Incoming,
+    Outgoing,
+}
+
+impl Friendship {
+    pub fn new(requester_id: u32, addressee_id: u32, accepted: bool) -> Self {
+        Self {
+            requester_id,
+            addressee_id,
+            accepted,
+        }
+    }
+
+    pub fn get_status(&self, account_id: u32) -> FriendStatus {
+        match self.accepted {
+            true => FriendStatus::Accepted,
+            false if self.requester_id == account_id => FriendStatus::Outgoing,
+            false => FriendStatus::Incoming,
+        }
+    }
+
+    pub fn get_other_id(&self, account_id: u32) -> u32 {
+        match self.requester_id == account_id {
+            true => self.addressee_id,
+            false => self.requester_id,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_status_with_pending_request_should_depend_on_side() {
+        let friendship = Friendship::new(1, 2, false);
+        assert_eq!(friendship.get_status(1), FriendStatus::Outgoing);
+        assert_eq!(friendship.get_status(2), FriendStatus::Incoming);
+        assert_eq!(friendship.get_other_id(2), 1);
+        assert_eq!(
+            Friendship::new(1, 2, true).get_status(2),
+            FriendStatus::Accepted
+        );
+    }
+}
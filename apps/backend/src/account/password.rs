@@ -0,0 +1,45 @@
+use std::error::Error;
+
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+
+pub fn hash_password(password: &str) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let salt = SaltString::generate(&mut OsRng);
+    match Argon2::default().hash_password(password.as_bytes(), &salt) {
+        Ok(hash) => Ok(hash.to_string()),
+        Err(e) => Err(format!("Failed to hash password: {}", e).into()),
+    }
+}
+
+pub fn verify_password(password: &str, password_hash: &str) -> bool {
+    match PasswordHash::new(password_hash) {
+        Ok(hash) => Argon2::default()
+            .verify_password(password.as_bytes(), &hash)
+            .is_ok(),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_password_with_same_password_should_return_true(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let hash = hash_password("password")?;
+        assert_ne!(hash, "password");
+        assert!(verify_password("password", &hash));
+        Ok(())
+    }
+
+    #[test]
+    fn verify_password_with_wrong_password_should_return_false(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let hash = hash_password("password")?;
+        assert!(!verify_password("wrong password", &hash));
+        Ok(())
+    }
+}
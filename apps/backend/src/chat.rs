@@ -0,0 +1,3 @@
+pub mod chat_history;
+pub mod chat_message;
+pub mod rate_limiter;
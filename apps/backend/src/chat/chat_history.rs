@@ -0,0 +1,69 @@
+use std::collections::VecDeque;
+
+use super::chat_message::ChatMessage;
+
+pub const CHAT_HISTORY_SIZE: usize = 50;
+
+/// Keeps the most recent chat messages so they can be sent to late joiners.
+#[derive(Debug, Clone)]
+pub struct ChatHistory {
+    capacity: usize,
+    messages: VecDeque<ChatMessage>,
+}
+
+impl ChatHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            messages: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub fn push(&mut self, message: ChatMessage) {
+        if self.messages.len() == self.capacity {
+            self.messages.pop_front();
+        }
+        self.messages.push_back(message);
+    }
+
+    pub fn get_messages(&self) -> Vec<ChatMessage> {
+        self.messages.iter().cloned().collect()
+    }
+}
+
+impl Default for ChatHistory {
+    fn default() -> Self {
+        ChatHistory::new(CHAT_HISTORY_SIZE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::player::Player;
+
+    use super::*;
+
+    #[test]
+    fn push_with_message_should_be_added() {
+        let mut history = ChatHistory::new(2);
+        let player = Arc::new(Player::new(0, String::from("test")));
+        history.push(ChatMessage::new(player, String::from("hello")));
+        assert_eq!(history.get_messages().len(), 1);
+        assert_eq!(history.get_messages()[0].content, "hello");
+    }
+
+    #[test]
+    fn push_with_full_history_should_drop_oldest_message() {
+        let mut history = ChatHistory::new(2);
+        let player = Arc::new(Player::new(0, String::from("test")));
+        for content in ["a", "b", "c"] {
+            history.push(ChatMessage::new(player.clone(), String::from(content)));
+        }
+        let messages = history.get_messages();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].content, "b");
+        assert_eq!(messages[1].content, "c");
+    }
+}
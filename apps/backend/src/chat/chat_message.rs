@@ -0,0 +1,27 @@
+use std::{
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::player::Player;
+
+#[derive(Debug, Clone)]
+pub struct ChatMessage {
+    pub sender: Arc<Player>,
+    pub content: String,
+    /// Unix timestamp in milliseconds.
+    pub timestamp: u64,
+}
+
+impl ChatMessage {
+    pub fn new(sender: Arc<Player>, content: String) -> Self {
+        Self {
+            sender,
+            content,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_millis() as u64)
+                .unwrap_or_default(),
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// Sliding window limiter, allows at most `limit` actions in `window`.
+#[derive(Debug)]
+pub struct RateLimiter {
+    limit: usize,
+    window: Duration,
+    timestamps: VecDeque<Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(limit: usize, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            timestamps: VecDeque::with_capacity(limit),
+        }
+    }
+
+    pub fn try_acquire(&mut self, now: Instant) -> bool {
+        while let Some(timestamp) = self.timestamps.front() {
+            if now.duration_since(*timestamp) < self.window {
+                break;
+            }
+            self.timestamps.pop_front();
+        }
+        if self.timestamps.len() >= self.limit {
+            return false;
+        }
+        self.timestamps.push_back(now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn try_acquire_within_limit_should_return_true() {
+        let mut limiter = RateLimiter::new(2, Duration::from_secs(10));
+        let now = Instant::now();
+        assert!(limiter.try_acquire(now));
+        assert!(limiter.try_acquire(now));
+    }
+
+    #[test]
+    fn try_acquire_exceed_limit_should_return_false() {
+        let mut limiter = RateLimiter::new(2, Duration::from_secs(10));
+        let now = Instant::now();
+        assert!(limiter.try_acquire(now));
+        assert!(limiter.try_acquire(now));
+        assert!(!limiter.try_acquire(now));
+    }
+
+    #[test]
+    fn try_acquire_after_window_should_return_true() {
+        let mut limiter = RateLimiter::new(1, Duration::from_secs(10));
+        let now = Instant::now();
+        assert!(limiter.try_acquire(now));
+        assert!(limiter.try_acquire(now + Duration::from_secs(10)));
+    }
+}
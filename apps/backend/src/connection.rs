@@ -1,28 +1,69 @@
-use std::{io::Cursor, time::Duration};
+use std::io::Cursor;
+use std::sync::Arc;
 
 use crate::frame::Frame;
-use bytes::{Buf, BytesMut};
+use crate::transport::{self, BoxedStream, Stream, TransportReader, TransportWriter};
+use bytes::{Buf, BufMut, BytesMut};
 use prost::Message;
-use tokio::{
-    io::{AsyncReadExt, AsyncWriteExt},
-    net::{
-        tcp::{OwnedReadHalf, OwnedWriteHalf},
-        TcpStream,
-    },
-    sync::Mutex,
-    time::timeout,
-};
-
-#[derive(Debug)]
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::mpsc::Sender;
+use tokio::sync::{Mutex, Notify};
+
+pub const MAX_BUFFER_LEN: usize = 16 * 1024;
+
+/**
+ * The queue of frames waiting to be written to a client. Frames are queued without waiting, so a
+ * client that stops reading can't stall the game it is in. It is disconnected instead once its
+ * queue is full.
+ */
+#[derive(Debug, Clone)]
+pub struct ClientSender {
+    frames: Sender<Frame>,
+    overflow: Arc<Notify>,
+}
+
+impl ClientSender {
+    pub fn new(frames: Sender<Frame>) -> Self {
+        Self {
+            frames,
+            overflow: Arc::new(Notify::new()),
+        }
+    }
+
+    pub fn send(&self, frame: Frame) -> Result<(), TrySendError<Frame>> {
+        let result = self.frames.try_send(frame);
+        if let Err(TrySendError::Full(_)) = &result {
+            self.overflow.notify_one();
+        }
+        result
+    }
+
+    pub async fn overflowed(&self) {
+        self.overflow.notified().await
+    }
+}
+
 pub struct Connection {
-    reader: Mutex<OwnedReadHalf>,
-    writer: Mutex<OwnedWriteHalf>,
+    reader: Mutex<TransportReader>,
+    writer: Mutex<TransportWriter>,
     buffer: Mutex<BytesMut>,
 }
 
 impl Connection {
-    pub fn new(stream: TcpStream) -> Self {
-        let (reader, writer) = stream.into_split();
+    pub fn new<S: Stream + 'static>(stream: S) -> Self {
+        let (reader, writer) = transport::raw(Box::new(stream));
+        Connection::from_transport(reader, writer)
+    }
+
+    pub async fn accept_websocket<S: Stream + 'static>(
+        stream: S,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let stream: BoxedStream = Box::new(stream);
+        let (reader, writer) = transport::websocket(stream).await?;
+        Ok(Connection::from_transport(reader, writer))
+    }
+
+    fn from_transport(reader: TransportReader, writer: TransportWriter) -> Self {
         Self {
             reader: Mutex::new(reader),
             writer: Mutex::new(writer),
@@ -41,34 +82,33 @@ impl Connection {
                 return Ok(Some(frame));
             }
 
-            match timeout(
-                Duration::from_secs(30),
-                self.reader
-                    .lock()
-                    .await
-                    .read_buf(&mut *self.buffer.lock().await),
-            )
-            .await
+            // Frames are size checked before their content arrives, so a full
+            // buffer means the peer is misbehaving.
+            if self.buffer.lock().await.len() >= MAX_BUFFER_LEN {
+                return Err("read buffer limit exceeded".into());
+            }
+
+            // There is not enough buffered data to read a frame.
+            // Attempt to read more data from the socket.
+            //
+            // On success, the number of bytes is returned. `0`
+            // indicates "end of stream".
+            if 0 == self
+                .reader
+                .lock()
+                .await
+                .read_buf(&mut *self.buffer.lock().await)
+                .await?
             {
-                // There is not enough buffered data to read a frame.
-                // Attempt to read more data from the socket.
-                //
-                // On success, the number of bytes is returned. `0`
-                // indicates "end of stream".
-                Ok(result) => {
-                    if 0 == result? {
-                        // The remote closed the connection. For this to be
-                        // a clean shutdown, there should be no data in the
-                        // read buffer. If there is, this means that the
-                        // peer closed the socket while sending a frame.
-                        if self.buffer.lock().await.is_empty() {
-                            return Ok(None);
-                        } else {
-                            return Err("connection reset by peer".into());
-                        }
-                    }
+                // The remote closed the connection. For this to be
+                // a clean shutdown, there should be no data in the
+                // read buffer. If there is, this means that the
+                // peer closed the socket while sending a frame.
+                if self.buffer.lock().await.is_empty() {
+                    return Ok(None);
+                } else {
+                    return Err("connection reset by peer".into());
                 }
-                Err(_) => return Err("read timeout, maybe connection closed".into()),
             }
         }
     }
@@ -102,8 +142,19 @@ impl Connection {
             }
             // Not enough data has been buffered
             Err(crate::frame::Error::Incomplete) => Ok(None),
-            // client sent invalid data
-            Err(crate::frame::Error::ProtobufDecodeFailed(_)) => Ok(None),
+            // client sent invalid data, skip it so the next frame can be read
+            Err(crate::frame::Error::Malformed { state, len, reason }) => {
+                buf_mutex_guard.advance(len);
+                Ok(Some(Frame::Invalid {
+                    state,
+                    reason: reason.to_string(),
+                }))
+            }
+            // client declared more data than allowed, don't wait for it
+            Err(crate::frame::Error::TooLarge { state, len, max }) => Ok(Some(Frame::TooLarge {
+                state,
+                reason: format!("payload of {len} bytes exceeds the limit of {max} bytes"),
+            })),
             // An error was encountered
             Err(crate::frame::Error::Other(e)) => Err(e),
         }
@@ -113,7 +164,7 @@ impl Connection {
     pub async fn write_frame(&self, frame: &Frame) -> Result<(), Box<dyn std::error::Error>> {
         match frame {
             Frame::Response(res) => {
-                let mut buf = match res.get_data().as_ref() {
+                let buf = match res.get_data().as_ref() {
                     crate::frame::ResponseData::Connect(res) => {
                         let mut buf = BytesMut::with_capacity(res.encoded_len());
                         res.encode(&mut buf)?;
@@ -199,14 +250,128 @@ impl Connection {
                         res.encode(&mut buf)?;
                         buf
                     }
+                    crate::frame::ResponseData::UpdateLobby(res) => {
+                        let mut buf = BytesMut::with_capacity(res.encoded_len());
+                        res.encode(&mut buf)?;
+                        buf
+                    }
+                    crate::frame::ResponseData::Kick(res) => {
+                        let mut buf = BytesMut::with_capacity(res.encoded_len());
+                        res.encode(&mut buf)?;
+                        buf
+                    }
+                    crate::frame::ResponseData::Chat(res) => {
+                        let mut buf = BytesMut::with_capacity(res.encoded_len());
+                        res.encode(&mut buf)?;
+                        buf
+                    }
+                    crate::frame::ResponseData::LobbyListBroadcast(res) => {
+                        let mut buf = BytesMut::with_capacity(res.encoded_len());
+                        res.encode(&mut buf)?;
+                        buf
+                    }
+                    crate::frame::ResponseData::FriendBroadcast(res) => {
+                        let mut buf = BytesMut::with_capacity(res.encoded_len());
+                        res.encode(&mut buf)?;
+                        buf
+                    }
+                    crate::frame::ResponseData::Ping(res) => {
+                        let mut buf = BytesMut::with_capacity(res.encoded_len());
+                        res.encode(&mut buf)?;
+                        buf
+                    }
+                    crate::frame::ResponseData::QuickPlay(res) => {
+                        let mut buf = BytesMut::with_capacity(res.encoded_len());
+                        res.encode(&mut buf)?;
+                        buf
+                    }
+                    crate::frame::ResponseData::CancelQuickPlay(res) => {
+                        let mut buf = BytesMut::with_capacity(res.encoded_len());
+                        res.encode(&mut buf)?;
+                        buf
+                    }
+                    crate::frame::ResponseData::Rematch(res) => {
+                        let mut buf = BytesMut::with_capacity(res.encoded_len());
+                        res.encode(&mut buf)?;
+                        buf
+                    }
+                    crate::frame::ResponseData::Spectate(res) => {
+                        let mut buf = BytesMut::with_capacity(res.encoded_len());
+                        res.encode(&mut buf)?;
+                        buf
+                    }
+                    crate::frame::ResponseData::StopSpectating(res) => {
+                        let mut buf = BytesMut::with_capacity(res.encoded_len());
+                        res.encode(&mut buf)?;
+                        buf
+                    }
+                    crate::frame::ResponseData::Reconnect(res) => {
+                        let mut buf = BytesMut::with_capacity(res.encoded_len());
+                        res.encode(&mut buf)?;
+                        buf
+                    }
+                    crate::frame::ResponseData::GetProfile(res) => {
+                        let mut buf = BytesMut::with_capacity(res.encoded_len());
+                        res.encode(&mut buf)?;
+                        buf
+                    }
+                    crate::frame::ResponseData::GetMatchHistory(res) => {
+                        let mut buf = BytesMut::with_capacity(res.encoded_len());
+                        res.encode(&mut buf)?;
+                        buf
+                    }
+                    crate::frame::ResponseData::GetLeaderboard(res) => {
+                        let mut buf = BytesMut::with_capacity(res.encoded_len());
+                        res.encode(&mut buf)?;
+                        buf
+                    }
+                    crate::frame::ResponseData::AddFriend(res) => {
+                        let mut buf = BytesMut::with_capacity(res.encoded_len());
+                        res.encode(&mut buf)?;
+                        buf
+                    }
+                    crate::frame::ResponseData::AcceptFriend(res) => {
+                        let mut buf = BytesMut::with_capacity(res.encoded_len());
+                        res.encode(&mut buf)?;
+                        buf
+                    }
+                    crate::frame::ResponseData::RemoveFriend(res) => {
+                        let mut buf = BytesMut::with_capacity(res.encoded_len());
+                        res.encode(&mut buf)?;
+                        buf
+                    }
+                    crate::frame::ResponseData::ListFriends(res) => {
+                        let mut buf = BytesMut::with_capacity(res.encoded_len());
+                        res.encode(&mut buf)?;
+                        buf
+                    }
+                    crate::frame::ResponseData::Invite(res) => {
+                        let mut buf = BytesMut::with_capacity(res.encoded_len());
+                        res.encode(&mut buf)?;
+                        buf
+                    }
+                    crate::frame::ResponseData::VoteKick(res) => {
+                        let mut buf = BytesMut::with_capacity(res.encoded_len());
+                        res.encode(&mut buf)?;
+                        buf
+                    }
+                    crate::frame::ResponseData::Pong(res) => {
+                        let mut buf = BytesMut::with_capacity(res.encoded_len());
+                        res.encode(&mut buf)?;
+                        buf
+                    }
+                    crate::frame::ResponseData::GetBoard(res) => {
+                        let mut buf = BytesMut::with_capacity(res.encoded_len());
+                        res.encode(&mut buf)?;
+                        buf
+                    }
                 };
 
-                {
-                    let mut writer = self.writer.lock().await;
-                    writer.write_u32_le(res.get_state()).await?;
-                    writer.write_u32_le(buf.len().try_into().unwrap()).await?;
-                    writer.write_buf(&mut buf).await?;
-                }
+                let mut data = Vec::with_capacity(8 + buf.len());
+                data.put_u32_le(res.get_state());
+                data.put_u32_le(buf.len().try_into().unwrap());
+                data.put(buf);
+                self.writer.lock().await.write_frame(data).await?;
 
                 Ok(())
             }
@@ -214,3 +379,87 @@ impl Connection {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame::RequestData;
+    use tokio::io::{duplex, AsyncWriteExt};
+
+    #[tokio::test]
+    async fn read_frame_with_malformed_frame_should_skip_it(
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let (mut client, server) = duplex(64);
+        let connection = Connection::new(server);
+        let mut buf = vec![255, 0, 0, 0];
+        buf.put_u32(2);
+        buf.put_u32(3);
+        buf.put_slice(&[1, 2, 3]);
+        buf.put_slice(&[1, 0, 0, 0]);
+        buf.put_u32(3);
+        buf.put_u32(0);
+        client.write_all(&buf).await?;
+        match connection.read_frame().await? {
+            Some(Frame::Invalid { state, .. }) => assert_eq!(state, 2),
+            _ => panic!("frame should be invalid"),
+        }
+        match connection.read_frame().await? {
+            Some(Frame::Request(req)) => {
+                assert_eq!(req.get_state(), 3);
+                assert_eq!(*req.get_data(), RequestData::Disconnect);
+            }
+            _ => panic!("frame should be a request"),
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_frame_with_undecodable_payload_should_skip_it(
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let (mut client, server) = duplex(64);
+        let connection = Connection::new(server);
+        let mut buf = vec![0, 0, 0, 0];
+        buf.put_u32(2);
+        buf.put_u32(3);
+        buf.put_slice(&[0xff, 0xff, 0xff]);
+        buf.put_slice(&[1, 0, 0, 0]);
+        buf.put_u32(3);
+        buf.put_u32(0);
+        client.write_all(&buf).await?;
+        match connection.read_frame().await? {
+            Some(Frame::Invalid { state, .. }) => assert_eq!(state, 2),
+            _ => panic!("frame should be invalid"),
+        }
+        match connection.read_frame().await? {
+            Some(Frame::Request(req)) => assert_eq!(req.get_state(), 3),
+            _ => panic!("frame should be a request"),
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn send_with_full_queue_should_report_overflow() {
+        let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+        let sender = ClientSender::new(tx);
+        assert!(sender
+            .send(Frame::Invalid {
+                state: 2,
+                reason: String::new()
+            })
+            .is_ok());
+        assert!(matches!(
+            sender.send(Frame::Invalid {
+                state: 3,
+                reason: String::new()
+            }),
+            Err(TrySendError::Full(_))
+        ));
+        tokio::time::timeout(std::time::Duration::from_secs(1), sender.overflowed())
+            .await
+            .expect("overflow should be reported");
+        assert!(matches!(
+            rx.recv().await,
+            Some(Frame::Invalid { state: 2, .. })
+        ));
+    }
+}
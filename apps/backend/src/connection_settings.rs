@@ -0,0 +1,123 @@
+use std::error::Error;
+use std::time::Duration;
+
+pub const DEFAULT_PING_INTERVAL: Duration = Duration::from_secs(5);
+pub const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(10);
+pub const DEFAULT_DEAD_TIMEOUT: Duration = Duration::from_secs(20);
+pub const DEFAULT_MAX_BAD_FRAMES: u32 = 5;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionSettings {
+    pub ping_interval: Duration,
+    pub idle_timeout: Duration,
+    pub dead_timeout: Duration,
+    pub max_bad_frames: u32,
+}
+
+impl Default for ConnectionSettings {
+    fn default() -> Self {
+        Self {
+            ping_interval: DEFAULT_PING_INTERVAL,
+            idle_timeout: DEFAULT_IDLE_TIMEOUT,
+            dead_timeout: DEFAULT_DEAD_TIMEOUT,
+            max_bad_frames: DEFAULT_MAX_BAD_FRAMES,
+        }
+    }
+}
+
+impl ConnectionSettings {
+    pub fn from_env() -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let settings = Self {
+            ping_interval: ConnectionSettings::read_secs("PING_INTERVAL", DEFAULT_PING_INTERVAL)?,
+            idle_timeout: ConnectionSettings::read_secs("IDLE_TIMEOUT", DEFAULT_IDLE_TIMEOUT)?,
+            dead_timeout: ConnectionSettings::read_secs("DEAD_TIMEOUT", DEFAULT_DEAD_TIMEOUT)?,
+            max_bad_frames: ConnectionSettings::read_u32("MAX_BAD_FRAMES", DEFAULT_MAX_BAD_FRAMES)?,
+        };
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    fn read_secs(key: &str, default: Duration) -> Result<Duration, Box<dyn Error + Send + Sync>> {
+        match std::env::var(key) {
+            Ok(value) => match value.trim().parse::<u64>() {
+                Ok(secs) => Ok(Duration::from_secs(secs)),
+                Err(_) => Err(format!("Invalid {key}").into()),
+            },
+            Err(_) => Ok(default),
+        }
+    }
+
+    fn read_u32(key: &str, default: u32) -> Result<u32, Box<dyn Error + Send + Sync>> {
+        match std::env::var(key) {
+            Ok(value) => match value.trim().parse::<u32>() {
+                Ok(value) => Ok(value),
+                Err(_) => Err(format!("Invalid {key}").into()),
+            },
+            Err(_) => Ok(default),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        if self.ping_interval.is_zero() {
+            return Err("Invalid ping interval".into());
+        }
+        if self.idle_timeout <= self.ping_interval {
+            return Err("Idle timeout must be longer than the ping interval".into());
+        }
+        if self.dead_timeout <= self.idle_timeout {
+            return Err("Dead timeout must be longer than the idle timeout".into());
+        }
+        if self.max_bad_frames == 0 {
+            return Err("Invalid max bad frames".into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_with_default_settings_should_return_ok() {
+        assert!(ConnectionSettings::default().validate().is_ok());
+    }
+
+    #[test]
+    fn validate_with_zero_ping_interval_should_return_error() {
+        let settings = ConnectionSettings {
+            ping_interval: Duration::ZERO,
+            ..ConnectionSettings::default()
+        };
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn validate_with_idle_timeout_shorter_than_ping_interval_should_return_error() {
+        let settings = ConnectionSettings {
+            ping_interval: Duration::from_secs(10),
+            idle_timeout: Duration::from_secs(5),
+            ..ConnectionSettings::default()
+        };
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn validate_with_dead_timeout_shorter_than_idle_timeout_should_return_error() {
+        let settings = ConnectionSettings {
+            idle_timeout: Duration::from_secs(30),
+            dead_timeout: Duration::from_secs(20),
+            ..ConnectionSettings::default()
+        };
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn validate_with_zero_max_bad_frames_should_return_error() {
+        let settings = ConnectionSettings {
+            max_bad_frames: 0,
+            ..ConnectionSettings::default()
+        };
+        assert!(settings.validate().is_err());
+    }
+}
@@ -1,4 +1,8 @@
+pub mod chat;
 pub mod control;
 pub mod controller;
+pub mod friend;
 pub mod game;
 pub mod lobby;
+pub mod matchmaking;
+pub mod stats;
@@ -0,0 +1,121 @@
+use std::sync::Arc;
+
+use crate::frame::Request;
+use crate::model::chat::send::SendResponse;
+use crate::service::chat_service::ChatService;
+use crate::{
+    controller::controller::PrintableController,
+    frame::{RequestData, ResponseData},
+    router::RequestContext,
+    service::player_service::PlayerService,
+};
+
+use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
+
+#[derive(Debug, Clone)]
+pub struct SendController {
+    player_service: Arc<PlayerService>,
+    chat_service: Arc<ChatService>,
+}
+
+impl SendController {
+    pub fn new(player_service: Arc<PlayerService>, chat_service: Arc<ChatService>) -> Self {
+        Self {
+            player_service,
+            chat_service,
+        }
+    }
+}
+
+impl PrintableController for SendController {}
+
+#[async_trait]
+impl Controller for SendController {
+    async fn handle_request(
+        &self,
+        req: Request,
+        context: RequestContext,
+    ) -> Result<ResponseData, ServiceError> {
+        let data = req.get_data();
+        let req = match data.as_ref() {
+            RequestData::Chat(req) => req,
+            _ => panic!("invalid request"),
+        };
+        let player = match self.player_service.get_player(context.client_id) {
+            Some(player) => player,
+            None => return Err(ServiceError::PlayerNotFound),
+        };
+        let message = self.chat_service.send_message(player, &req.content).await?;
+        Ok(ResponseData::Chat(SendResponse {
+            success: true,
+            message: Some(crate::model::chat::message::ChatMessage::from(&message)),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        model::chat::send::SendRequest,
+        service::{game_service::GameService, lobby_service::LobbyService},
+    };
+    use std::{collections::HashSet, error::Error};
+
+    #[tokio::test]
+    async fn handle_request_with_test_user_in_lobby_should_send_message(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let player_service = Arc::new(PlayerService::new(
+            lobby_service.clone(),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        let player = player_service.add_player(0, String::from("test"));
+        let lobby = lobby_service.create_lobby(player, 4).await?;
+        let controller = SendController::new(player_service, Arc::new(ChatService::new()));
+        let res = match controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::Chat(SendRequest {
+                        content: String::from("hello"),
+                    })),
+                ),
+                RequestContext { client_id: 0 },
+            )
+            .await?
+        {
+            ResponseData::Chat(res) => res,
+            _ => panic!("invalid response"),
+        };
+        assert!(res.success);
+        assert_eq!(res.message.unwrap().content, "hello");
+        assert_eq!(lobby.get_chat_history().len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_not_exist_user_should_return_error(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let player_service = Arc::new(PlayerService::new(
+            Arc::new(LobbyService::new()),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        let controller = SendController::new(player_service, Arc::new(ChatService::new()));
+        assert!(controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::Chat(SendRequest {
+                        content: String::from("hello"),
+                    }))
+                ),
+                RequestContext { client_id: 0 },
+            )
+            .await
+            .is_err());
+        Ok(())
+    }
+}
@@ -3,50 +3,159 @@ use std::sync::Arc;
 use crate::{
     controller::controller::PrintableController,
     frame::{Request, RequestData, ResponseData},
-    model::control::connect::ConnectResponse,
+    model::control::connect::{ConnectResponse, NameRejection},
+    naming::name_policy::NamePolicy,
+    player::DEFAULT_RATING,
+    protocol::{self, PROTOCOL_VERSION},
     router::RequestContext,
-    service::player_service::PlayerService,
+    service::{
+        account_service::AccountService, blocking::run_blocking, player_service::PlayerService,
+        rating_service::RatingService,
+    },
 };
 
 use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
 
 #[derive(Debug, Clone)]
 pub struct ConnectController {
     player_service: Arc<PlayerService>,
+    account_service: Arc<AccountService>,
+    name_policy: Arc<NamePolicy>,
+    rating_service: Arc<RatingService>,
 }
 
 impl ConnectController {
-    pub fn new(player_service: Arc<PlayerService>) -> Self {
-        Self { player_service }
+    pub fn new(
+        player_service: Arc<PlayerService>,
+        account_service: Arc<AccountService>,
+        name_policy: Arc<NamePolicy>,
+        rating_service: Arc<RatingService>,
+    ) -> Self {
+        Self {
+            player_service,
+            account_service,
+            name_policy,
+            rating_service,
+        }
+    }
+
+    fn reject_name(
+        name_rejection: crate::naming::name_rejection::NameRejection,
+        features: u32,
+    ) -> Result<ResponseData, ServiceError> {
+        Ok(ResponseData::Connect(ConnectResponse {
+            success: false,
+            player: None,
+            session_token: String::new(),
+            name_rejection: NameRejection::from(name_rejection) as i32,
+            protocol_version: PROTOCOL_VERSION as u32,
+            features,
+        }))
     }
 }
 
 impl PrintableController for ConnectController {}
 
+#[async_trait]
 impl Controller for ConnectController {
-    fn handle_request(
+    async fn handle_request(
         &self,
         req: Request,
         context: RequestContext,
-    ) -> Result<ResponseData, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<ResponseData, ServiceError> {
         let data = req.get_data();
         let req = match data.as_ref() {
             RequestData::Connect(req) => req,
             _ => panic!("invalid request"),
         };
-        let player = match self.player_service.get_player(context.client_id) {
-            Some(_) => return Err("client already connected".into()),
-            None => self.player_service.add_player(
-                context.client_id,
-                req.name.clone(),
-                #[cfg(not(test))]
-                context.sender,
-            ),
+        protocol::check_version(req.protocol_version)?;
+        let features = protocol::negotiate_features(req.features);
+        if self.player_service.get_player(context.client_id).is_some() {
+            return Err(ServiceError::AlreadyConnected);
+        }
+        let name = match self
+            .name_policy
+            .validate(req.username.as_ref().unwrap_or(&req.name))
+        {
+            Ok(name) => name,
+            Err(name_rejection) => return ConnectController::reject_name(name_rejection, features),
         };
+        let mut rating = DEFAULT_RATING;
+        let account = match &req.username {
+            Some(_) => {
+                let password = match &req.password {
+                    Some(password) => password,
+                    None => return Err(ServiceError::PasswordRequired),
+                };
+                let account = match req.register {
+                    true => {
+                        if self.player_service.is_name_taken(&name) {
+                            return ConnectController::reject_name(
+                                crate::naming::name_rejection::NameRejection::Taken,
+                                features,
+                            );
+                        }
+                        let account_service = self.account_service.clone();
+                        let (name, password) = (name.clone(), password.clone());
+                        run_blocking(move || account_service.register(&name, &password)).await?
+                    }
+                    false => {
+                        let account_service = self.account_service.clone();
+                        let (name, password) = (name.clone(), password.clone());
+                        run_blocking(move || account_service.authenticate(&name, &password)).await?
+                    }
+                };
+                if self
+                    .player_service
+                    .get_player_by_account_id(account.id)
+                    .is_some()
+                {
+                    return Err(ServiceError::AccountAlreadyConnected);
+                }
+                let rating_service = self.rating_service.clone();
+                let account_id = account.id;
+                rating = run_blocking(move || rating_service.get_rating(account_id)).await?;
+                Some(account)
+            }
+            None => {
+                let account_service = self.account_service.clone();
+                let username = name.clone();
+                if run_blocking(move || account_service.is_username_registered(&username)).await? {
+                    return ConnectController::reject_name(
+                        crate::naming::name_rejection::NameRejection::Taken,
+                        features,
+                    );
+                }
+                None
+            }
+        };
+        let player = match self.player_service.add_player_with_unique_name(
+            context.client_id,
+            match &account {
+                Some(account) => account.username.clone(),
+                None => name,
+            },
+            #[cfg(not(test))]
+            context.sender,
+        ) {
+            Ok(player) => player,
+            Err(name_rejection) => return ConnectController::reject_name(name_rejection, features),
+        };
+        player.set_account(account);
+        player.set_rating(rating);
+        player.set_features(features);
+        #[cfg(not(test))]
+        player.set_address(Some(context.addr.ip()));
 
         Ok(ResponseData::Connect(ConnectResponse {
             success: true,
-            player: Some(crate::model::player::player::Player::from(player)),
+            player: Some(crate::model::player::player::Player::from(player.clone())),
+            session_token: player.get_session_token(),
+            name_rejection: NameRejection::None as i32,
+            protocol_version: PROTOCOL_VERSION as u32,
+            features,
         }))
     }
 }
@@ -55,60 +164,417 @@ impl Controller for ConnectController {
 mod tests {
     use super::*;
     use crate::{
+        account::account_repository::AccountRepository,
+        game::game::Game,
         model::control::connect::ConnectRequest,
-        service::{game_service::GameService, lobby_service::LobbyService},
+        protocol::Feature,
+        service::{
+            game_service::GameService, lobby_service::LobbyService, stats_service::StatsService,
+        },
+        stats::{
+            match_record::MatchRecord, match_repository::MatchRepository,
+            rating_repository::RatingRepository,
+        },
     };
     use std::{collections::HashSet, error::Error};
 
-    #[test]
-    fn handle_request_with_test_user_should_create_test_user(
+    #[tokio::test]
+    async fn handle_request_with_test_user_should_create_test_user(
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
-        let controller = ConnectController::new(Arc::new(PlayerService::new(
-            Arc::new(LobbyService::new()),
-            Arc::new(GameService::new(HashSet::new())),
-        )));
-        controller.handle_request(
-            Request::new(
-                0,
-                Arc::new(RequestData::Connect(ConnectRequest {
-                    name: String::from("test"),
-                })),
-            ),
-            RequestContext { client_id: 0 },
-        )?;
+        let controller = ConnectController::new(
+            Arc::new(PlayerService::new(
+                Arc::new(LobbyService::new()),
+                Arc::new(GameService::new(HashSet::new())),
+            )),
+            Arc::new(AccountService::new(AccountRepository::open_in_memory()?)),
+            Arc::new(NamePolicy::default()),
+            Arc::new(RatingService::new(RatingRepository::open_in_memory()?)),
+        );
+        controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::Connect(ConnectRequest {
+                        name: String::from("test"),
+                        username: None,
+                        password: None,
+                        register: false,
+                        protocol_version: PROTOCOL_VERSION as u32,
+                        features: 0,
+                    })),
+                ),
+                RequestContext { client_id: 0 },
+            )
+            .await?;
         let player = controller.player_service.get_player(0).unwrap();
         assert_eq!(player.id, 0);
         assert_eq!(player.name, String::from("test"));
         Ok(())
     }
 
-    #[test]
-    fn handle_request_with_test_user_who_already_connected_should_return_error(
+    #[tokio::test]
+    async fn handle_request_with_test_user_who_already_connected_should_return_error(
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
-        let controller = ConnectController::new(Arc::new(PlayerService::new(
-            Arc::new(LobbyService::new()),
-            Arc::new(GameService::new(HashSet::new())),
-        )));
-        controller.handle_request(
-            Request::new(
-                0,
-                Arc::new(RequestData::Connect(ConnectRequest {
-                    name: String::from("test"),
-                })),
-            ),
-            RequestContext { client_id: 0 },
-        )?;
+        let controller = ConnectController::new(
+            Arc::new(PlayerService::new(
+                Arc::new(LobbyService::new()),
+                Arc::new(GameService::new(HashSet::new())),
+            )),
+            Arc::new(AccountService::new(AccountRepository::open_in_memory()?)),
+            Arc::new(NamePolicy::default()),
+            Arc::new(RatingService::new(RatingRepository::open_in_memory()?)),
+        );
+        controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::Connect(ConnectRequest {
+                        name: String::from("test"),
+                        username: None,
+                        password: None,
+                        register: false,
+                        protocol_version: PROTOCOL_VERSION as u32,
+                        features: 0,
+                    })),
+                ),
+                RequestContext { client_id: 0 },
+            )
+            .await?;
         assert!(controller
             .handle_request(
                 Request::new(
                     0,
                     Arc::new(RequestData::Connect(ConnectRequest {
                         name: String::from("test"),
+                        username: None,
+                        password: None,
+                        register: false,
+                        protocol_version: PROTOCOL_VERSION as u32,
+                        features: 0,
                     }))
                 ),
                 RequestContext { client_id: 0 },
             )
+            .await
+            .is_err());
+        Ok(())
+    }
+
+    fn connect_request(name: &str, username: &str, password: &str, register: bool) -> Request {
+        Request::new(
+            0,
+            Arc::new(RequestData::Connect(ConnectRequest {
+                name: String::from(name),
+                username: Some(String::from(username)),
+                password: Some(String::from(password)),
+                register,
+                protocol_version: PROTOCOL_VERSION as u32,
+                features: 0,
+            })),
+        )
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_register_should_bind_account(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let controller = ConnectController::new(
+            Arc::new(PlayerService::new(
+                Arc::new(LobbyService::new()),
+                Arc::new(GameService::new(HashSet::new())),
+            )),
+            Arc::new(AccountService::new(AccountRepository::open_in_memory()?)),
+            Arc::new(NamePolicy::default()),
+            Arc::new(RatingService::new(RatingRepository::open_in_memory()?)),
+        );
+        let res = match controller
+            .handle_request(
+                connect_request("guest", "test_user", "password", true),
+                RequestContext { client_id: 0 },
+            )
+            .await?
+        {
+            ResponseData::Connect(res) => res,
+            _ => panic!("invalid response"),
+        };
+        let player = controller.player_service.get_player(0).unwrap();
+        assert_eq!(player.name, "test_user");
+        assert!(player.get_account_id().is_some());
+        assert_eq!(res.player.unwrap().account_id, player.get_account_id());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_registered_account_should_bind_same_account_id(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let controller = ConnectController::new(
+            Arc::new(PlayerService::new(
+                Arc::new(LobbyService::new()),
+                Arc::new(GameService::new(HashSet::new())),
+            )),
+            Arc::new(AccountService::new(AccountRepository::open_in_memory()?)),
+            Arc::new(NamePolicy::default()),
+            Arc::new(RatingService::new(RatingRepository::open_in_memory()?)),
+        );
+        let account = controller
+            .account_service
+            .register("test_user", "password")?;
+        controller
+            .handle_request(
+                connect_request("guest", "test_user", "password", false),
+                RequestContext { client_id: 0 },
+            )
+            .await?;
+        let player = controller.player_service.get_player(0).unwrap();
+        assert_eq!(player.get_account_id(), Some(account.id));
+        assert!(controller
+            .handle_request(
+                connect_request("guest", "test_user", "password", false),
+                RequestContext { client_id: 1 },
+            )
+            .await
+            .is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_wrong_password_should_return_error(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let controller = ConnectController::new(
+            Arc::new(PlayerService::new(
+                Arc::new(LobbyService::new()),
+                Arc::new(GameService::new(HashSet::new())),
+            )),
+            Arc::new(AccountService::new(AccountRepository::open_in_memory()?)),
+            Arc::new(NamePolicy::default()),
+            Arc::new(RatingService::new(RatingRepository::open_in_memory()?)),
+        );
+        controller
+            .account_service
+            .register("test_user", "password")?;
+        assert!(controller
+            .handle_request(
+                connect_request("guest", "test_user", "wrong password", false),
+                RequestContext { client_id: 0 },
+            )
+            .await
             .is_err());
+        assert!(controller.player_service.get_player(0).is_none());
+        Ok(())
+    }
+
+    fn guest_connect_request(name: &str) -> Request {
+        Request::new(
+            0,
+            Arc::new(RequestData::Connect(ConnectRequest {
+                name: String::from(name),
+                username: None,
+                password: None,
+                register: false,
+                protocol_version: PROTOCOL_VERSION as u32,
+                features: 0,
+            })),
+        )
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_blank_name_should_reject_empty_name(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let controller = ConnectController::new(
+            Arc::new(PlayerService::new(
+                Arc::new(LobbyService::new()),
+                Arc::new(GameService::new(HashSet::new())),
+            )),
+            Arc::new(AccountService::new(AccountRepository::open_in_memory()?)),
+            Arc::new(NamePolicy::default()),
+            Arc::new(RatingService::new(RatingRepository::open_in_memory()?)),
+        );
+        let res = match controller
+            .handle_request(guest_connect_request("  "), RequestContext { client_id: 0 })
+            .await?
+        {
+            ResponseData::Connect(res) => res,
+            _ => panic!("invalid response"),
+        };
+        assert!(!res.success);
+        assert_eq!(res.name_rejection, NameRejection::Empty as i32);
+        assert!(controller.player_service.get_player(0).is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_name_of_online_player_should_reject_taken_name(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let controller = ConnectController::new(
+            Arc::new(PlayerService::new(
+                Arc::new(LobbyService::new()),
+                Arc::new(GameService::new(HashSet::new())),
+            )),
+            Arc::new(AccountService::new(AccountRepository::open_in_memory()?)),
+            Arc::new(NamePolicy::default()),
+            Arc::new(RatingService::new(RatingRepository::open_in_memory()?)),
+        );
+        controller
+            .handle_request(
+                guest_connect_request("test"),
+                RequestContext { client_id: 0 },
+            )
+            .await?;
+        let res = match controller
+            .handle_request(
+                guest_connect_request(" TEST "),
+                RequestContext { client_id: 1 },
+            )
+            .await?
+        {
+            ResponseData::Connect(res) => res,
+            _ => panic!("invalid response"),
+        };
+        assert!(!res.success);
+        assert_eq!(res.name_rejection, NameRejection::Taken as i32);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_guest_using_registered_username_should_reject_taken_name(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let controller = ConnectController::new(
+            Arc::new(PlayerService::new(
+                Arc::new(LobbyService::new()),
+                Arc::new(GameService::new(HashSet::new())),
+            )),
+            Arc::new(AccountService::new(AccountRepository::open_in_memory()?)),
+            Arc::new(NamePolicy::default()),
+            Arc::new(RatingService::new(RatingRepository::open_in_memory()?)),
+        );
+        controller
+            .account_service
+            .register("test_user", "password")?;
+        let res = match controller
+            .handle_request(
+                guest_connect_request("test_user"),
+                RequestContext { client_id: 0 },
+            )
+            .await?
+        {
+            ResponseData::Connect(res) => res,
+            _ => panic!("invalid response"),
+        };
+        assert_eq!(res.name_rejection, NameRejection::Taken as i32);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_rated_account_should_load_stored_rating(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        // games are stored in the database the ratings are read from, like the server does
+        let database = "file:connect_controller?mode=memory&cache=shared";
+        let stats_service = Arc::new(StatsService::new(MatchRepository::open(database)?));
+        let controller = ConnectController::new(
+            Arc::new(PlayerService::new(
+                Arc::new(LobbyService::new()),
+                Arc::new(GameService::new(HashSet::new())),
+            )),
+            Arc::new(AccountService::new(AccountRepository::open_in_memory()?)),
+            Arc::new(NamePolicy::default()),
+            Arc::new(RatingService::new(RatingRepository::open(database)?)),
+        );
+        let account = controller
+            .account_service
+            .register("test_user", "password")?;
+        let player = Arc::new(crate::player::Player::new(1, String::from("test_user")));
+        player.set_account(Some(account));
+        let opponent = Arc::new(crate::player::Player::new(2, String::from("test")));
+        let game = Game::new(0, vec![player, opponent]);
+        game.get_player(2)
+            .unwrap()
+            .record_words(&[String::from("apple")]);
+        StatsService::record_result(
+            stats_service,
+            MatchRecord::from_game(&game),
+            RatingService::rate_game(&game),
+        )
+        .await?;
+        controller
+            .handle_request(
+                connect_request("guest", "test_user", "password", false),
+                RequestContext { client_id: 0 },
+            )
+            .await?;
+        let player = controller.player_service.get_player(0).unwrap();
+        assert_eq!(player.get_rating(), 1484);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_unsupported_protocol_version_should_return_error(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let controller = ConnectController::new(
+            Arc::new(PlayerService::new(
+                Arc::new(LobbyService::new()),
+                Arc::new(GameService::new(HashSet::new())),
+            )),
+            Arc::new(AccountService::new(AccountRepository::open_in_memory()?)),
+            Arc::new(NamePolicy::default()),
+            Arc::new(RatingService::new(RatingRepository::open_in_memory()?)),
+        );
+        assert!(controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::Connect(ConnectRequest {
+                        name: String::from("test"),
+                        username: None,
+                        password: None,
+                        register: false,
+                        protocol_version: PROTOCOL_VERSION as u32 + 1,
+                        features: 0,
+                    })),
+                ),
+                RequestContext { client_id: 0 },
+            )
+            .await
+            .is_err());
+        assert!(controller.player_service.get_player(0).is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_features_should_return_negotiated_features(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let controller = ConnectController::new(
+            Arc::new(PlayerService::new(
+                Arc::new(LobbyService::new()),
+                Arc::new(GameService::new(HashSet::new())),
+            )),
+            Arc::new(AccountService::new(AccountRepository::open_in_memory()?)),
+            Arc::new(NamePolicy::default()),
+            Arc::new(RatingService::new(RatingRepository::open_in_memory()?)),
+        );
+        let res = match controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::Connect(ConnectRequest {
+                        name: String::from("test"),
+                        username: None,
+                        password: None,
+                        register: false,
+                        protocol_version: PROTOCOL_VERSION as u32,
+                        features: Feature::Friends as u32 | 1 << 31,
+                    })),
+                ),
+                RequestContext { client_id: 0 },
+            )
+            .await?
+        {
+            ResponseData::Connect(res) => res,
+            _ => panic!("invalid response"),
+        };
+        assert_eq!(res.protocol_version, PROTOCOL_VERSION as u32);
+        assert_eq!(res.features, Feature::Friends as u32);
+        let player = controller.player_service.get_player(0).unwrap();
+        assert!(player.has_feature(Feature::Friends));
+        assert!(!player.has_feature(Feature::Ping));
         Ok(())
     }
 }
@@ -4,40 +4,51 @@ use crate::{
     controller::controller::PrintableController,
     frame::{Request, RequestData, ResponseData},
     router::RequestContext,
-    service::player_service::PlayerService,
+    service::{matchmaking_service::MatchmakingService, player_service::PlayerService},
 };
 
 use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
 
 use crate::model::control::disconnect::DisconnectResponse;
 #[derive(Debug, Clone)]
 pub struct DisconnectController {
     player_service: Arc<PlayerService>,
+    matchmaking_service: Arc<MatchmakingService>,
 }
 
 impl DisconnectController {
-    pub fn new(player_service: Arc<PlayerService>) -> Self {
-        Self { player_service }
+    pub fn new(
+        player_service: Arc<PlayerService>,
+        matchmaking_service: Arc<MatchmakingService>,
+    ) -> Self {
+        Self {
+            player_service,
+            matchmaking_service,
+        }
     }
 }
 
 impl PrintableController for DisconnectController {}
 
+#[async_trait]
 impl Controller for DisconnectController {
-    fn handle_request(
+    async fn handle_request(
         &self,
         req: Request,
         context: RequestContext,
-    ) -> Result<ResponseData, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<ResponseData, ServiceError> {
         assert!(match *req.get_data() {
             RequestData::Disconnect => true,
             _ => false,
         });
         let player = match self.player_service.get_player(context.client_id) {
             Some(player) => player,
-            None => return Err("Player not found".into()),
+            None => return Err(ServiceError::PlayerNotFound),
         };
-        self.player_service.remove_player(player)?;
+        let _ = self.matchmaking_service.leave_queue(&player);
+        self.player_service.remove_player(player).await?;
         Ok(ResponseData::Disconnect(DisconnectResponse {
             success: true,
         }))
@@ -52,35 +63,66 @@ mod tests {
 
     use super::*;
 
-    #[test]
-    fn handle_request_with_user_already_connected_should_be_removed(
-    ) -> Result<(), Box<dyn Error + Send + Sync>> {
-        let controller = DisconnectController::new(Arc::new(PlayerService::new(
-            Arc::new(LobbyService::new()),
+    fn disconnect_controller() -> DisconnectController {
+        let lobby_service = Arc::new(LobbyService::new());
+        let player_service = Arc::new(PlayerService::new(
+            lobby_service.clone(),
             Arc::new(GameService::new(HashSet::new())),
-        )));
+        ));
+        DisconnectController::new(
+            player_service.clone(),
+            Arc::new(MatchmakingService::new(player_service, lobby_service)),
+        )
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_user_already_connected_should_be_removed(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let controller = disconnect_controller();
         controller
             .player_service
             .add_player(0, String::from("test"));
-        controller.handle_request(
-            Request::new(0, Arc::new(RequestData::Disconnect)),
-            RequestContext { client_id: 0 },
-        )?;
+        controller
+            .handle_request(
+                Request::new(0, Arc::new(RequestData::Disconnect)),
+                RequestContext { client_id: 0 },
+            )
+            .await?;
         assert!(controller.player_service.get_player(0).is_none());
         Ok(())
     }
 
-    #[test]
-    fn handle_request_with_user_not_exist_should_return_error() -> Result<(), Box<dyn Error>> {
-        let controller = DisconnectController::new(Arc::new(PlayerService::new(
-            Arc::new(LobbyService::new()),
-            Arc::new(GameService::new(HashSet::new())),
-        )));
+    #[tokio::test]
+    async fn handle_request_with_queued_user_should_leave_queue(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let controller = disconnect_controller();
+        let player = controller
+            .player_service
+            .add_player(0, String::from("test"));
+        controller
+            .matchmaking_service
+            .join_queue(player.clone(), 4, String::from("en"))
+            .await?;
+        controller
+            .handle_request(
+                Request::new(0, Arc::new(RequestData::Disconnect)),
+                RequestContext { client_id: 0 },
+            )
+            .await?;
+        assert!(!controller.matchmaking_service.is_queued(&player));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_user_not_exist_should_return_error() -> Result<(), Box<dyn Error>>
+    {
+        let controller = disconnect_controller();
         assert!(controller
             .handle_request(
                 Request::new(0, Arc::new(RequestData::Disconnect)),
                 RequestContext { client_id: 0 }
             )
+            .await
             .is_err());
         Ok(())
     }
@@ -5,7 +5,9 @@ use crate::{
 };
 
 use crate::controller::controller::Controller;
+use crate::error::ServiceError;
 use crate::model::control::heartbeat::HeartbeatResponse;
+use async_trait::async_trait;
 #[derive(Debug, Clone)]
 pub struct HeartbeatController {}
 
@@ -17,12 +19,13 @@ impl HeartbeatController {
 
 impl PrintableController for HeartbeatController {}
 
+#[async_trait]
 impl Controller for HeartbeatController {
-    fn handle_request(
+    async fn handle_request(
         &self,
         req: Request,
         _: RequestContext,
-    ) -> Result<ResponseData, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<ResponseData, ServiceError> {
         assert!(match *req.get_data() {
             RequestData::Heartbeat => true,
             _ => false,
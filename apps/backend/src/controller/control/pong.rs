@@ -0,0 +1,119 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::{
+    controller::controller::PrintableController,
+    frame::{Request, RequestData, ResponseData},
+    router::RequestContext,
+    service::player_service::PlayerService,
+};
+
+use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use crate::model::control::ping::PongResponse;
+use async_trait::async_trait;
+
+#[derive(Debug, Clone)]
+pub struct PongController {
+    player_service: Arc<PlayerService>,
+}
+
+impl PongController {
+    pub fn new(player_service: Arc<PlayerService>) -> Self {
+        Self { player_service }
+    }
+}
+
+impl PrintableController for PongController {}
+
+#[async_trait]
+impl Controller for PongController {
+    async fn handle_request(
+        &self,
+        req: Request,
+        context: RequestContext,
+    ) -> Result<ResponseData, ServiceError> {
+        let data = req.get_data();
+        let req = match data.as_ref() {
+            RequestData::Pong(req) => req,
+            _ => panic!("invalid request"),
+        };
+        let player = match self.player_service.get_player(context.client_id) {
+            Some(player) => player,
+            None => return Err(ServiceError::PlayerNotFound),
+        };
+        let rtt = match player.finish_ping(req.sequence, Instant::now()) {
+            Some(rtt) => rtt,
+            None => return Err(ServiceError::UnknownPing),
+        };
+        Ok(ResponseData::Pong(PongResponse {
+            success: true,
+            rtt: rtt.as_millis() as u32,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::control::ping::PongRequest;
+    use crate::service::{game_service::GameService, lobby_service::LobbyService};
+    use std::collections::HashSet;
+    use std::error::Error;
+
+    #[tokio::test]
+    async fn handle_request_with_pending_ping_should_record_rtt(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let player_service = Arc::new(PlayerService::new(
+            Arc::new(LobbyService::new()),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        let player = player_service.add_player(0, String::from("test"));
+        let sequence = player.start_ping(Instant::now());
+        let controller = PongController::new(player_service);
+        let res = match controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::Pong(PongRequest {
+                        sequence,
+                        timestamp: 0,
+                    })),
+                ),
+                RequestContext { client_id: 0 },
+            )
+            .await?
+        {
+            ResponseData::Pong(res) => res,
+            _ => panic!("invalid response"),
+        };
+        assert!(res.success);
+        assert!(player.get_rtt().is_some());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_unknown_sequence_should_return_error() {
+        let player_service = Arc::new(PlayerService::new(
+            Arc::new(LobbyService::new()),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        let player = player_service.add_player(0, String::from("test"));
+        let sequence = player.start_ping(Instant::now());
+        let controller = PongController::new(player_service);
+        assert!(controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::Pong(PongRequest {
+                        sequence: sequence + 1,
+                        timestamp: 0,
+                    })),
+                ),
+                RequestContext { client_id: 0 },
+            )
+            .await
+            .is_err());
+        assert!(player.get_rtt().is_none());
+    }
+}
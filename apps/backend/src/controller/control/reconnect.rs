@@ -0,0 +1,270 @@
+use std::sync::Arc;
+
+use crate::{
+    controller::controller::PrintableController,
+    frame::{Request, RequestData, ResponseData},
+    model::{control::reconnect::ReconnectResponse, game::spectate::SpectateResponse},
+    router::RequestContext,
+    service::player_service::PlayerService,
+};
+
+use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
+
+#[derive(Debug, Clone)]
+pub struct ReconnectController {
+    player_service: Arc<PlayerService>,
+}
+
+impl ReconnectController {
+    pub fn new(player_service: Arc<PlayerService>) -> Self {
+        Self { player_service }
+    }
+}
+
+impl PrintableController for ReconnectController {}
+
+#[async_trait]
+impl Controller for ReconnectController {
+    async fn handle_request(
+        &self,
+        req: Request,
+        context: RequestContext,
+    ) -> Result<ResponseData, ServiceError> {
+        let data = req.get_data();
+        let req = match data.as_ref() {
+            RequestData::Reconnect(req) => req,
+            _ => panic!("invalid request"),
+        };
+        let player = self.player_service.reconnect_player(
+            context.client_id,
+            &req.session_token,
+            #[cfg(not(test))]
+            context.sender,
+        )?;
+        #[cfg(not(test))]
+        player.set_address(Some(context.addr.ip()));
+
+        let mut res = ReconnectResponse {
+            success: true,
+            player: Some(crate::model::player::player::Player::from(player.clone())),
+            session_token: player.get_session_token(),
+            lobby: player
+                .get_lobby()
+                .map(crate::model::lobby::lobby::Lobby::from),
+            game: None,
+            cards: None,
+            spectating: false,
+        };
+        if let Some(game) = player.get_game() {
+            res.game = Some(SpectateResponse::from(&game));
+            res.cards = game.get_player(player.id).map(|game_player| {
+                crate::model::game::cards::Cards::from(&game_player.get_cards())
+            });
+        } else if let Some(game) = player.get_spectating() {
+            res.game = Some(SpectateResponse::from(&game));
+            res.spectating = true;
+        }
+        Ok(ResponseData::Reconnect(res))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        model::control::reconnect::ReconnectRequest,
+        service::{game_service::GameService, lobby_service::LobbyService},
+    };
+    use std::{collections::HashSet, error::Error};
+
+    fn reconnect_request(session_token: &str) -> Request {
+        Request::new(
+            0,
+            Arc::new(RequestData::Reconnect(ReconnectRequest {
+                session_token: String::from(session_token),
+            })),
+        )
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_disconnected_player_token_should_reattach_player(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let controller = ReconnectController::new(Arc::new(PlayerService::new(
+            lobby_service.clone(),
+            Arc::new(GameService::new(HashSet::new())),
+        )));
+        let player = controller
+            .player_service
+            .add_player(0, String::from("test"));
+        let lobby = lobby_service.create_lobby(player.clone(), 4).await?;
+        controller.player_service.disconnect_player(0)?;
+        let res = match controller
+            .handle_request(
+                reconnect_request(&player.get_session_token()),
+                RequestContext { client_id: 1 },
+            )
+            .await?
+        {
+            ResponseData::Reconnect(res) => res,
+            _ => panic!("invalid response"),
+        };
+        assert!(res.success);
+        assert_eq!(res.player.unwrap().id, 0);
+        assert_eq!(res.lobby.unwrap().id, lobby.get_id());
+        assert!(res.game.is_none());
+        assert!(!res.spectating);
+        let reconnected = controller.player_service.get_player(1).unwrap();
+        assert!(Arc::ptr_eq(&reconnected, &player));
+        assert!(player.get_disconnected_at().is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_disconnected_player_token_should_renew_token(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let controller = ReconnectController::new(Arc::new(PlayerService::new(
+            Arc::new(LobbyService::new()),
+            Arc::new(GameService::new(HashSet::new())),
+        )));
+        let player = controller
+            .player_service
+            .add_player(0, String::from("test"));
+        let session_token = player.get_session_token();
+        controller.player_service.disconnect_player(0)?;
+        let res = match controller
+            .handle_request(
+                reconnect_request(&session_token),
+                RequestContext { client_id: 1 },
+            )
+            .await?
+        {
+            ResponseData::Reconnect(res) => res,
+            _ => panic!("invalid response"),
+        };
+        assert_ne!(res.session_token, session_token);
+        assert_eq!(res.session_token, player.get_session_token());
+        controller.player_service.disconnect_player(1)?;
+        assert!(controller
+            .handle_request(
+                reconnect_request(&session_token),
+                RequestContext { client_id: 2 },
+            )
+            .await
+            .is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_player_in_game_token_should_return_game_and_cards(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let game_service = Arc::new(GameService::new(HashSet::new()));
+        let controller = ReconnectController::new(Arc::new(PlayerService::new(
+            lobby_service.clone(),
+            game_service.clone(),
+        )));
+        let player = controller
+            .player_service
+            .add_player(0, String::from("test"));
+        let lobby = lobby_service.create_lobby(player.clone(), 4).await?;
+        lobby.get_player(0).unwrap().set_ready(true);
+        let game = GameService::start_game(game_service, player.clone(), lobby).await?;
+        controller.player_service.disconnect_player(0)?;
+        let res = match controller
+            .handle_request(
+                reconnect_request(&player.get_session_token()),
+                RequestContext { client_id: 1 },
+            )
+            .await?
+        {
+            ResponseData::Reconnect(res) => res,
+            _ => panic!("invalid response"),
+        };
+        assert!(!res.spectating);
+        assert!(res.cards.is_some());
+        let snapshot = res.game.unwrap();
+        assert_eq!(snapshot.board_sequence, game.get_board_sequence());
+        assert_eq!(snapshot.current_player.unwrap().id, 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_spectator_token_should_return_spectated_game(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let game_service = Arc::new(GameService::new(HashSet::new()));
+        let controller = ReconnectController::new(Arc::new(PlayerService::new(
+            lobby_service.clone(),
+            game_service.clone(),
+        )));
+        let leader = controller
+            .player_service
+            .add_player(0, String::from("test1"));
+        let spectator = controller
+            .player_service
+            .add_player(1, String::from("test2"));
+        let lobby = lobby_service.create_lobby(leader.clone(), 4).await?;
+        lobby.get_player(0).unwrap().set_ready(true);
+        let game = GameService::start_game(game_service.clone(), leader, lobby.clone()).await?;
+        game_service.spectate(spectator.clone(), lobby).await?;
+        controller.player_service.disconnect_player(1)?;
+        let res = match controller
+            .handle_request(
+                reconnect_request(&spectator.get_session_token()),
+                RequestContext { client_id: 2 },
+            )
+            .await?
+        {
+            ResponseData::Reconnect(res) => res,
+            _ => panic!("invalid response"),
+        };
+        assert!(res.spectating);
+        assert!(res.cards.is_none());
+        let snapshot = res.game.unwrap();
+        assert!(snapshot.board.is_some());
+        assert_eq!(snapshot.board_sequence, game.get_board_sequence());
+        assert_eq!(snapshot.players.unwrap().players.len(), 1);
+        assert_eq!(snapshot.spectators.unwrap().players.len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_unknown_token_should_return_error(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let controller = ReconnectController::new(Arc::new(PlayerService::new(
+            Arc::new(LobbyService::new()),
+            Arc::new(GameService::new(HashSet::new())),
+        )));
+        assert!(controller
+            .handle_request(
+                reconnect_request("unknown"),
+                RequestContext { client_id: 0 }
+            )
+            .await
+            .is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_connected_player_token_should_return_error(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let controller = ReconnectController::new(Arc::new(PlayerService::new(
+            Arc::new(LobbyService::new()),
+            Arc::new(GameService::new(HashSet::new())),
+        )));
+        let player = controller
+            .player_service
+            .add_player(0, String::from("test"));
+        assert!(controller
+            .handle_request(
+                reconnect_request(&player.get_session_token()),
+                RequestContext { client_id: 1 },
+            )
+            .await
+            .is_err());
+        Ok(())
+    }
+}
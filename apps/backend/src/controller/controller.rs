@@ -1,16 +1,22 @@
 use crate::{
+    error::ServiceError,
     frame::{Request, ResponseData},
     router::RequestContext,
 };
-use std::error::Error;
+use async_trait::async_trait;
 use std::fmt::Debug;
 
+/**
+ * Handles the requests of one operation. Broadcasts caused by a request must be sent before the
+ * handler returns, so they reach the clients ahead of the response.
+ */
+#[async_trait]
 pub trait Controller {
-    fn handle_request(
+    async fn handle_request(
         &self,
         req: Request,
         context: RequestContext,
-    ) -> Result<ResponseData, Box<dyn Error + Send + Sync>>;
+    ) -> Result<ResponseData, ServiceError>;
 }
 
 pub trait PrintableController: Controller + Debug + Send + Sync {}
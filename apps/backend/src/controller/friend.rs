@@ -0,0 +1,5 @@
+pub mod accept;
+pub mod add;
+pub mod invite;
+pub mod list;
+pub mod remove;
@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use crate::{
+    controller::controller::PrintableController,
+    frame::{Request, RequestData, ResponseData},
+    model::friend::{accept::AcceptFriendResponse, friend::Friend},
+    router::RequestContext,
+    service::{friend_service::FriendService, player_service::PlayerService},
+};
+
+use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
+
+#[derive(Debug, Clone)]
+pub struct AcceptFriendController {
+    player_service: Arc<PlayerService>,
+    friend_service: Arc<FriendService>,
+}
+
+impl AcceptFriendController {
+    pub fn new(player_service: Arc<PlayerService>, friend_service: Arc<FriendService>) -> Self {
+        Self {
+            player_service,
+            friend_service,
+        }
+    }
+}
+
+impl PrintableController for AcceptFriendController {}
+
+#[async_trait]
+impl Controller for AcceptFriendController {
+    async fn handle_request(
+        &self,
+        req: Request,
+        context: RequestContext,
+    ) -> Result<ResponseData, ServiceError> {
+        let data = req.get_data();
+        let req = match data.as_ref() {
+            RequestData::AcceptFriend(req) => req,
+            _ => panic!("invalid request"),
+        };
+        let player = match self.player_service.get_player(context.client_id) {
+            Some(player) => player,
+            None => return Err(ServiceError::PlayerNotFound),
+        };
+        let friend = self
+            .friend_service
+            .accept_friend(&player, req.account_id)
+            .await?;
+        Ok(ResponseData::AcceptFriend(AcceptFriendResponse {
+            success: true,
+            friend: Some(Friend::from(&friend)),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        account::account_repository::AccountRepository,
+        model::friend::{accept::AcceptFriendRequest, friend::FriendStatus},
+        service::{
+            account_service::AccountService, game_service::GameService, lobby_service::LobbyService,
+        },
+    };
+    use std::{collections::HashSet, error::Error};
+
+    fn accept_controller() -> Result<AcceptFriendController, Box<dyn Error + Send + Sync>> {
+        let player_service = Arc::new(PlayerService::new(
+            Arc::new(LobbyService::new()),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        let account_service = Arc::new(AccountService::new(AccountRepository::open_in_memory()?));
+        let player1 = player_service.add_player(0, String::from("test1"));
+        player1.set_account(Some(account_service.register("test1", "password")?));
+        let player2 = player_service.add_player(1, String::from("test2"));
+        player2.set_account(Some(account_service.register("test2", "password")?));
+        Ok(AcceptFriendController::new(
+            player_service.clone(),
+            Arc::new(FriendService::new(player_service, account_service)),
+        ))
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_incoming_request_should_accept_friend(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let controller = accept_controller()?;
+        let player1 = controller.player_service.get_player(0).unwrap();
+        let player2 = controller.player_service.get_player(1).unwrap();
+        controller
+            .friend_service
+            .add_friend(&player2, "test1")
+            .await?;
+        let res = match controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::AcceptFriend(AcceptFriendRequest {
+                        account_id: player2.get_account_id().unwrap(),
+                    })),
+                ),
+                RequestContext { client_id: 0 },
+            )
+            .await?
+        {
+            ResponseData::AcceptFriend(res) => res,
+            _ => panic!("invalid response"),
+        };
+        assert_eq!(res.friend.unwrap().status, FriendStatus::Accepted as i32);
+        assert_eq!(
+            controller.friend_service.get_friends(&player1).await?.len(),
+            1
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,126 @@
+use std::sync::Arc;
+
+use crate::{
+    controller::controller::PrintableController,
+    frame::{Request, RequestData, ResponseData},
+    model::friend::{add::AddFriendResponse, friend::Friend},
+    router::RequestContext,
+    service::{friend_service::FriendService, player_service::PlayerService},
+};
+
+use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
+
+#[derive(Debug, Clone)]
+pub struct AddFriendController {
+    player_service: Arc<PlayerService>,
+    friend_service: Arc<FriendService>,
+}
+
+impl AddFriendController {
+    pub fn new(player_service: Arc<PlayerService>, friend_service: Arc<FriendService>) -> Self {
+        Self {
+            player_service,
+            friend_service,
+        }
+    }
+}
+
+impl PrintableController for AddFriendController {}
+
+#[async_trait]
+impl Controller for AddFriendController {
+    async fn handle_request(
+        &self,
+        req: Request,
+        context: RequestContext,
+    ) -> Result<ResponseData, ServiceError> {
+        let data = req.get_data();
+        let req = match data.as_ref() {
+            RequestData::AddFriend(req) => req,
+            _ => panic!("invalid request"),
+        };
+        let player = match self.player_service.get_player(context.client_id) {
+            Some(player) => player,
+            None => return Err(ServiceError::PlayerNotFound),
+        };
+        let friend = self
+            .friend_service
+            .add_friend(&player, &req.username)
+            .await?;
+        Ok(ResponseData::AddFriend(AddFriendResponse {
+            success: true,
+            friend: Some(Friend::from(&friend)),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        account::account_repository::AccountRepository,
+        model::friend::{add::AddFriendRequest, friend::FriendStatus},
+        service::{
+            account_service::AccountService, game_service::GameService, lobby_service::LobbyService,
+        },
+    };
+    use std::{collections::HashSet, error::Error};
+
+    fn add_controller() -> Result<AddFriendController, Box<dyn Error + Send + Sync>> {
+        let player_service = Arc::new(PlayerService::new(
+            Arc::new(LobbyService::new()),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        let account_service = Arc::new(AccountService::new(AccountRepository::open_in_memory()?));
+        let player1 = player_service.add_player(0, String::from("test1"));
+        player1.set_account(Some(account_service.register("test1", "password")?));
+        let player2 = player_service.add_player(1, String::from("test2"));
+        player2.set_account(Some(account_service.register("test2", "password")?));
+        Ok(AddFriendController::new(
+            player_service.clone(),
+            Arc::new(FriendService::new(player_service, account_service)),
+        ))
+    }
+
+    fn add_friend_request(username: &str) -> Request {
+        Request::new(
+            0,
+            Arc::new(RequestData::AddFriend(AddFriendRequest {
+                username: String::from(username),
+            })),
+        )
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_username_should_send_friend_request(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let controller = add_controller()?;
+        let res = match controller
+            .handle_request(add_friend_request("test2"), RequestContext { client_id: 0 })
+            .await?
+        {
+            ResponseData::AddFriend(res) => res,
+            _ => panic!("invalid response"),
+        };
+        let friend = res.friend.unwrap();
+        assert_eq!(friend.username, "test2");
+        assert_eq!(friend.status, FriendStatus::Outgoing as i32);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_unknown_username_should_return_error(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let controller = add_controller()?;
+        assert!(controller
+            .handle_request(
+                add_friend_request("unknown"),
+                RequestContext { client_id: 0 }
+            )
+            .await
+            .is_err());
+        Ok(())
+    }
+}
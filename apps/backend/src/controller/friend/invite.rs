@@ -0,0 +1,110 @@
+use std::sync::Arc;
+
+use crate::{
+    controller::controller::PrintableController,
+    frame::{Request, RequestData, ResponseData},
+    model::friend::invite::InviteResponse,
+    router::RequestContext,
+    service::{friend_service::FriendService, player_service::PlayerService},
+};
+
+use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
+
+#[derive(Debug, Clone)]
+pub struct InviteController {
+    player_service: Arc<PlayerService>,
+    friend_service: Arc<FriendService>,
+}
+
+impl InviteController {
+    pub fn new(player_service: Arc<PlayerService>, friend_service: Arc<FriendService>) -> Self {
+        Self {
+            player_service,
+            friend_service,
+        }
+    }
+}
+
+impl PrintableController for InviteController {}
+
+#[async_trait]
+impl Controller for InviteController {
+    async fn handle_request(
+        &self,
+        req: Request,
+        context: RequestContext,
+    ) -> Result<ResponseData, ServiceError> {
+        let data = req.get_data();
+        let req = match data.as_ref() {
+            RequestData::Invite(req) => req,
+            _ => panic!("invalid request"),
+        };
+        let player = match self.player_service.get_player(context.client_id) {
+            Some(player) => player,
+            None => return Err(ServiceError::PlayerNotFound),
+        };
+        self.friend_service.invite(&player, req.account_id).await?;
+        Ok(ResponseData::Invite(InviteResponse { success: true }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        account::account_repository::AccountRepository,
+        model::friend::invite::InviteRequest,
+        service::{
+            account_service::AccountService, game_service::GameService, lobby_service::LobbyService,
+        },
+    };
+    use std::{collections::HashSet, error::Error};
+
+    fn invite_controller() -> Result<InviteController, Box<dyn Error + Send + Sync>> {
+        let player_service = Arc::new(PlayerService::new(
+            Arc::new(LobbyService::new()),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        let account_service = Arc::new(AccountService::new(AccountRepository::open_in_memory()?));
+        let player1 = player_service.add_player(0, String::from("test1"));
+        player1.set_account(Some(account_service.register("test1", "password")?));
+        let player2 = player_service.add_player(1, String::from("test2"));
+        player2.set_account(Some(account_service.register("test2", "password")?));
+        Ok(InviteController::new(
+            player_service.clone(),
+            Arc::new(FriendService::new(player_service, account_service)),
+        ))
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_friend_should_invite_friend(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let controller = invite_controller()?;
+        let player1 = controller.player_service.get_player(0).unwrap();
+        let player2 = controller.player_service.get_player(1).unwrap();
+        controller
+            .friend_service
+            .add_friend(&player1, "test2")
+            .await?;
+        controller
+            .friend_service
+            .add_friend(&player2, "test1")
+            .await?;
+        let lobby = LobbyService::new().create_lobby(player1, 4).await?;
+        controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::Invite(InviteRequest {
+                        account_id: player2.get_account_id().unwrap(),
+                    })),
+                ),
+                RequestContext { client_id: 0 },
+            )
+            .await?;
+        assert!(lobby.is_invited(&player2));
+        Ok(())
+    }
+}
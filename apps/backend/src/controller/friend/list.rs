@@ -0,0 +1,113 @@
+use std::sync::Arc;
+
+use crate::{
+    controller::controller::PrintableController,
+    frame::{Request, RequestData, ResponseData},
+    model::friend::{friend::Friends, list::ListFriendsResponse},
+    router::RequestContext,
+    service::{friend_service::FriendService, player_service::PlayerService},
+};
+
+use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
+
+#[derive(Debug, Clone)]
+pub struct ListFriendsController {
+    player_service: Arc<PlayerService>,
+    friend_service: Arc<FriendService>,
+}
+
+impl ListFriendsController {
+    pub fn new(player_service: Arc<PlayerService>, friend_service: Arc<FriendService>) -> Self {
+        Self {
+            player_service,
+            friend_service,
+        }
+    }
+}
+
+impl PrintableController for ListFriendsController {}
+
+#[async_trait]
+impl Controller for ListFriendsController {
+    async fn handle_request(
+        &self,
+        req: Request,
+        context: RequestContext,
+    ) -> Result<ResponseData, ServiceError> {
+        match *req.get_data() {
+            RequestData::ListFriends => {}
+            _ => panic!("invalid request"),
+        };
+        let player = match self.player_service.get_player(context.client_id) {
+            Some(player) => player,
+            None => return Err(ServiceError::PlayerNotFound),
+        };
+        let friends = self.friend_service.get_friends(&player).await?;
+        Ok(ResponseData::ListFriends(ListFriendsResponse {
+            success: true,
+            friends: Some(Friends::from(&friends)),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        account::account_repository::AccountRepository,
+        model::friend::friend::{FriendStatus, Presence},
+        service::{
+            account_service::AccountService, game_service::GameService, lobby_service::LobbyService,
+        },
+    };
+    use std::{collections::HashSet, error::Error};
+
+    fn list_controller() -> Result<ListFriendsController, Box<dyn Error + Send + Sync>> {
+        let player_service = Arc::new(PlayerService::new(
+            Arc::new(LobbyService::new()),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        let account_service = Arc::new(AccountService::new(AccountRepository::open_in_memory()?));
+        let player1 = player_service.add_player(0, String::from("test1"));
+        player1.set_account(Some(account_service.register("test1", "password")?));
+        let player2 = player_service.add_player(1, String::from("test2"));
+        player2.set_account(Some(account_service.register("test2", "password")?));
+        Ok(ListFriendsController::new(
+            player_service.clone(),
+            Arc::new(FriendService::new(player_service, account_service)),
+        ))
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_friends_should_list_friends_with_presence(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let controller = list_controller()?;
+        let player1 = controller.player_service.get_player(0).unwrap();
+        let player2 = controller.player_service.get_player(1).unwrap();
+        controller
+            .friend_service
+            .add_friend(&player1, "test2")
+            .await?;
+        controller
+            .friend_service
+            .add_friend(&player2, "test1")
+            .await?;
+        let res = match controller
+            .handle_request(
+                Request::new(0, Arc::new(RequestData::ListFriends)),
+                RequestContext { client_id: 0 },
+            )
+            .await?
+        {
+            ResponseData::ListFriends(res) => res,
+            _ => panic!("invalid response"),
+        };
+        let friends = res.friends.unwrap().friends;
+        assert_eq!(friends.len(), 1);
+        assert_eq!(friends[0].status, FriendStatus::Accepted as i32);
+        assert_eq!(friends[0].presence, Presence::Online as i32);
+        Ok(())
+    }
+}
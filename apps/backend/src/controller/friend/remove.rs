@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use crate::{
+    controller::controller::PrintableController,
+    frame::{Request, RequestData, ResponseData},
+    model::friend::remove::RemoveFriendResponse,
+    router::RequestContext,
+    service::{friend_service::FriendService, player_service::PlayerService},
+};
+
+use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
+
+#[derive(Debug, Clone)]
+pub struct RemoveFriendController {
+    player_service: Arc<PlayerService>,
+    friend_service: Arc<FriendService>,
+}
+
+impl RemoveFriendController {
+    pub fn new(player_service: Arc<PlayerService>, friend_service: Arc<FriendService>) -> Self {
+        Self {
+            player_service,
+            friend_service,
+        }
+    }
+}
+
+impl PrintableController for RemoveFriendController {}
+
+#[async_trait]
+impl Controller for RemoveFriendController {
+    async fn handle_request(
+        &self,
+        req: Request,
+        context: RequestContext,
+    ) -> Result<ResponseData, ServiceError> {
+        let data = req.get_data();
+        let req = match data.as_ref() {
+            RequestData::RemoveFriend(req) => req,
+            _ => panic!("invalid request"),
+        };
+        let player = match self.player_service.get_player(context.client_id) {
+            Some(player) => player,
+            None => return Err(ServiceError::PlayerNotFound),
+        };
+        self.friend_service
+            .remove_friend(&player, req.account_id)
+            .await?;
+        Ok(ResponseData::RemoveFriend(RemoveFriendResponse {
+            success: true,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        account::account_repository::AccountRepository,
+        model::friend::remove::RemoveFriendRequest,
+        service::{
+            account_service::AccountService, game_service::GameService, lobby_service::LobbyService,
+        },
+    };
+    use std::{collections::HashSet, error::Error};
+
+    fn remove_controller() -> Result<RemoveFriendController, Box<dyn Error + Send + Sync>> {
+        let player_service = Arc::new(PlayerService::new(
+            Arc::new(LobbyService::new()),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        let account_service = Arc::new(AccountService::new(AccountRepository::open_in_memory()?));
+        let player1 = player_service.add_player(0, String::from("test1"));
+        player1.set_account(Some(account_service.register("test1", "password")?));
+        let player2 = player_service.add_player(1, String::from("test2"));
+        player2.set_account(Some(account_service.register("test2", "password")?));
+        Ok(RemoveFriendController::new(
+            player_service.clone(),
+            Arc::new(FriendService::new(player_service, account_service)),
+        ))
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_pending_request_should_remove_it(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let controller = remove_controller()?;
+        let player1 = controller.player_service.get_player(0).unwrap();
+        let player2 = controller.player_service.get_player(1).unwrap();
+        controller
+            .friend_service
+            .add_friend(&player2, "test1")
+            .await?;
+        let req = Request::new(
+            0,
+            Arc::new(RequestData::RemoveFriend(RemoveFriendRequest {
+                account_id: player2.get_account_id().unwrap(),
+            })),
+        );
+        controller
+            .handle_request(req.clone(), RequestContext { client_id: 0 })
+            .await?;
+        assert!(controller
+            .friend_service
+            .get_friends(&player1)
+            .await?
+            .is_empty());
+        assert!(controller
+            .handle_request(req, RequestContext { client_id: 0 })
+            .await
+            .is_err());
+        Ok(())
+    }
+}
@@ -1,6 +1,10 @@
 pub mod cancel;
 pub mod exit;
 pub mod finish_turn;
+pub mod get_board;
 pub mod get_new_card;
 pub mod set_tile;
+pub mod spectate;
 pub mod start;
+pub mod stop_spectating;
+pub mod vote_kick;
@@ -11,6 +11,8 @@ use crate::{
 use std::sync::Arc;
 
 use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
 
 #[derive(Debug, Clone)]
 pub struct CancelController {
@@ -29,12 +31,13 @@ impl CancelController {
 
 impl PrintableController for CancelController {}
 
+#[async_trait]
 impl Controller for CancelController {
-    fn handle_request(
+    async fn handle_request(
         &self,
         req: Request,
         context: RequestContext,
-    ) -> Result<ResponseData, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<ResponseData, ServiceError> {
         let data = req.get_data();
         let req = match data.as_ref() {
             RequestData::Cancel(req) => req,
@@ -42,16 +45,16 @@ impl Controller for CancelController {
         };
         let player = match self.player_service.get_player(context.client_id) {
             Some(player) => player,
-            None => return Err("Player not found".into()),
+            None => return Err(ServiceError::PlayerNotFound),
         };
         let game = match player.get_game() {
             Some(game) => game,
-            None => return Err("Player not in a game".into()),
+            None => return Err(ServiceError::NotInGame),
         };
 
         let game_player = match game.get_player(player.id) {
             Some(game_player) => game_player,
-            None => return Err("Player not found".into()),
+            None => return Err(ServiceError::PlayerNotFound),
         };
 
         let card = match game.get_board().lock().unwrap().tiles[BOARD_SIZE - req.y as usize - 1]
@@ -59,12 +62,14 @@ impl Controller for CancelController {
             .clone()
         {
             Some(card) => card,
-            None => return Err("card not in the board".into()),
+            None => return Err(ServiceError::CardNotOnBoard),
         };
         if card.turn != game.get_turns() {
-            return Err("card not place in this turn".into());
+            return Err(ServiceError::CardNotPlacedThisTurn);
         }
-        self.game_service.remove_selected_tile(req.x, req.y, game);
+        self.game_service
+            .remove_selected_tile(req.x, req.y, game)
+            .await;
         game_player.return_cancel_card(card.char);
         Ok(ResponseData::Cancel(CancelResponse {
             success: true,
@@ -101,14 +106,15 @@ mod tests {
         let player = controller
             .player_service
             .add_player(0, String::from("test"));
-        let lobby = lobby_service.create_lobby(player.clone(), 4)?;
+        let lobby = lobby_service.create_lobby(player.clone(), 4).await?;
         let lobby_player = lobby.clone().get_player(player.clone().id).unwrap();
         lobby_player.set_ready(true);
-        let game = GameService::start_game(game_service, player.clone(), lobby.clone())?;
+        let game = GameService::start_game(game_service, player.clone(), lobby.clone()).await?;
         let tile = Tile::new('z', player, 1);
         controller
             .game_service
-            .place_tile_on_board(game.clone(), tile, 1, 1);
+            .place_tile_on_board(game.clone(), tile, 1, 1)
+            .await;
         game.next_turn();
         assert!(controller
             .handle_request(
@@ -118,6 +124,7 @@ mod tests {
                 ),
                 RequestContext { client_id: 0 },
             )
+            .await
             .is_err());
         Ok(())
     }
@@ -138,10 +145,10 @@ mod tests {
         let player = controller
             .player_service
             .add_player(0, String::from("test"));
-        let lobby = lobby_service.create_lobby(player.clone(), 4)?;
+        let lobby = lobby_service.create_lobby(player.clone(), 4).await?;
         let lobby_player = lobby.clone().get_player(player.clone().id).unwrap();
         lobby_player.set_ready(true);
-        let game = GameService::start_game(game_service, player.clone(), lobby.clone())?;
+        let game = GameService::start_game(game_service, player.clone(), lobby.clone()).await?;
         let tile = Tile::new(
             game.get_player_in_this_turn().get_cards()[0].char,
             player,
@@ -149,7 +156,8 @@ mod tests {
         );
         controller
             .game_service
-            .place_tile_on_board(game.clone(), tile, 1, 1);
+            .place_tile_on_board(game.clone(), tile, 1, 1)
+            .await;
         game.next_turn();
         assert!(controller
             .handle_request(
@@ -159,6 +167,7 @@ mod tests {
                 ),
                 RequestContext { client_id: 0 },
             )
+            .await
             .is_err());
         Ok(())
     }
@@ -179,10 +188,10 @@ mod tests {
         let player = controller
             .player_service
             .add_player(0, String::from("test"));
-        let lobby = lobby_service.create_lobby(player.clone(), 4)?;
+        let lobby = lobby_service.create_lobby(player.clone(), 4).await?;
         let lobby_player = lobby.clone().get_player(player.clone().id).unwrap();
         lobby_player.set_ready(true);
-        let game = GameService::start_game(game_service, player.clone(), lobby.clone())?;
+        let game = GameService::start_game(game_service, player.clone(), lobby.clone()).await?;
         let tile = Tile::new(
             game.get_player_in_this_turn().get_cards()[0].char,
             player,
@@ -191,14 +200,17 @@ mod tests {
         game.get_player_in_this_turn().get_cards()[0].used = true;
         controller
             .game_service
-            .place_tile_on_board(game.clone(), tile, 1, 1);
-        controller.handle_request(
-            Request::new(
-                0,
-                Arc::new(RequestData::Cancel(CancelRequest { x: 24, y: 1 })),
-            ),
-            RequestContext { client_id: 0 },
-        )?;
+            .place_tile_on_board(game.clone(), tile, 1, 1)
+            .await;
+        controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::Cancel(CancelRequest { x: 24, y: 1 })),
+                ),
+                RequestContext { client_id: 0 },
+            )
+            .await?;
         assert!(game.get_player_in_this_turn().get_cards()[0].used == false);
         Ok(())
     }
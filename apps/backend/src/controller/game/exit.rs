@@ -10,6 +10,8 @@ use crate::{
 use std::sync::Arc;
 
 use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
 
 #[derive(Debug, Clone)]
 pub struct ExitController {
@@ -28,12 +30,13 @@ impl ExitController {
 
 impl PrintableController for ExitController {}
 
+#[async_trait]
 impl Controller for ExitController {
-    fn handle_request(
+    async fn handle_request(
         &self,
         req: Request,
         context: RequestContext,
-    ) -> Result<ResponseData, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<ResponseData, ServiceError> {
         let data = req.get_data();
         match data.as_ref() {
             RequestData::Exit => true,
@@ -42,18 +45,22 @@ impl Controller for ExitController {
 
         let player = match self.player_service.get_player(context.client_id) {
             Some(player) => player,
-            None => return Err("Player not found".into()),
+            None => return Err(ServiceError::PlayerNotFound),
         };
 
         let game = match player.get_game() {
             Some(game) => game,
-            None => return Err("Player not in a game".into()),
+            None => return Err(ServiceError::NotInGame),
         };
         match game.get_player(player.id) {
             Some(game_player) => game_player,
-            None => return Err("Player not found".into()),
+            None => return Err(ServiceError::PlayerNotFound),
         };
-        match self.game_service.remove_player_from_game(player.clone()) {
+        match self
+            .game_service
+            .remove_player_from_game(player.clone())
+            .await
+        {
             Ok(_) => Ok(ResponseData::Exit(ExitResponse { success: true })),
             Err(_) => Ok(ResponseData::Exit(ExitResponse { success: false })),
         }
@@ -83,16 +90,18 @@ mod tests {
         let player = controller
             .player_service
             .add_player(0, String::from("test"));
-        let lobby = lobby_service.create_lobby(player.clone(), 4)?;
+        let lobby = lobby_service.create_lobby(player.clone(), 4).await?;
         let lobby_player = lobby.clone().get_player(player.clone().id).unwrap();
         lobby_player.set_ready(true);
-        GameService::start_game(game_service, player.clone(), lobby)?;
-        let res = controller.handle_request(
-            Request::new(0, Arc::new(RequestData::Exit)),
-            RequestContext {
-                client_id: player.id,
-            },
-        )?;
+        GameService::start_game(game_service, player.clone(), lobby).await?;
+        let res = controller
+            .handle_request(
+                Request::new(0, Arc::new(RequestData::Exit)),
+                RequestContext {
+                    client_id: player.id,
+                },
+            )
+            .await?;
         match res {
             ResponseData::Exit(data) => {
                 let success = data.success;
@@ -118,7 +127,7 @@ mod tests {
         let player = controller
             .player_service
             .add_player(0, String::from("test"));
-        let lobby = lobby_service.create_lobby(player.clone(), 4)?;
+        let lobby = lobby_service.create_lobby(player.clone(), 4).await?;
         let lobby_player = lobby.clone().get_player(player.clone().id).unwrap();
         lobby_player.set_ready(true);
         assert!(controller
@@ -128,6 +137,7 @@ mod tests {
                     client_id: player.id,
                 },
             )
+            .await
             .is_err());
         Ok(())
     }
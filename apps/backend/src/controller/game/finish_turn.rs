@@ -10,6 +10,8 @@ use crate::{
 use std::sync::Arc;
 
 use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
 
 #[derive(Debug, Clone)]
 pub struct FinishTurnController {
@@ -28,12 +30,13 @@ impl FinishTurnController {
 
 impl PrintableController for FinishTurnController {}
 
+#[async_trait]
 impl Controller for FinishTurnController {
-    fn handle_request(
+    async fn handle_request(
         &self,
         req: Request,
         context: RequestContext,
-    ) -> Result<ResponseData, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<ResponseData, ServiceError> {
         let data = req.get_data();
         match data.as_ref() {
             RequestData::FinishTurn => true,
@@ -42,21 +45,23 @@ impl Controller for FinishTurnController {
 
         let player = match self.player_service.get_player(context.client_id) {
             Some(player) => player,
-            None => return Err("Player not found".into()),
+            None => return Err(ServiceError::PlayerNotFound),
         };
 
         let game = match player.get_game() {
             Some(game) => game,
-            None => return Err("Player not in a game".into()),
+            None => return Err(ServiceError::NotInGame),
         };
         let request_game_player = match game.get_player(player.id) {
             Some(game_player) => game_player,
-            None => return Err("Player not found".into()),
+            None => return Err(ServiceError::PlayerNotFound),
         };
         if request_game_player != game.get_player_in_this_turn() {
-            return Err("Player not in his turn".into());
+            return Err(ServiceError::NotYourTurn);
         }
-        match GameService::validate_board_and_finish_turn(self.game_service.clone(), game.clone()) {
+        match GameService::validate_board_and_finish_turn(self.game_service.clone(), game.clone())
+            .await
+        {
             Ok(words) => Ok(ResponseData::FinishTurn(FinishTurnResponse {
                 success: true,
                 current_player: Some(crate::model::player::player::Player::from(
@@ -106,10 +111,10 @@ mod tests {
             .player_service
             .add_player(0, String::from("test"));
         let lobby_service = Arc::new(lobby_service::LobbyService::new());
-        let lobby = lobby_service.create_lobby(player.clone(), 4)?;
+        let lobby = lobby_service.create_lobby(player.clone(), 4).await?;
         let lobby_player = lobby.clone().get_player(player.clone().id).unwrap();
         lobby_player.set_ready(true);
-        let game = GameService::start_game(game_service, player, lobby)?;
+        let game = GameService::start_game(game_service, player, lobby).await?;
         let player_now = game.get_player_in_this_turn();
         assert!(controller
             .handle_request(
@@ -118,6 +123,7 @@ mod tests {
                     client_id: player_now.player.id
                 },
             )
+            .await
             .is_ok());
         Ok(())
     }
@@ -138,13 +144,15 @@ mod tests {
         let player1 = controller
             .player_service
             .add_player(1, String::from("test1"));
-        let lobby = lobby_service.create_lobby(player.clone(), 4)?;
-        lobby_service.add_player_to_lobby(player1.clone(), lobby.clone())?;
+        let lobby = lobby_service.create_lobby(player.clone(), 4).await?;
+        lobby_service
+            .add_player_to_lobby(player1.clone(), lobby.clone())
+            .await?;
         let lobby_player = lobby.clone().get_player(player.clone().id).unwrap();
         let lobby_player1 = lobby.clone().get_player(player1.clone().id).unwrap();
         lobby_player.set_ready(true);
         lobby_player1.set_ready(true);
-        let game = GameService::start_game(game_service, player, lobby)?;
+        let game = GameService::start_game(game_service, player, lobby).await?;
         let player_now = game.get_player_in_this_turn();
         assert!(controller
             .handle_request(
@@ -157,6 +165,7 @@ mod tests {
                     }
                 },
             )
+            .await
             .is_err());
         Ok(())
     }
@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use crate::frame::Request;
+use crate::model::game::board::Board;
+use crate::model::game::get_board::GetBoardResponse;
+use crate::{
+    controller::controller::PrintableController,
+    frame::{RequestData, ResponseData},
+    router::RequestContext,
+    service::player_service::PlayerService,
+};
+
+use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
+
+#[derive(Debug, Clone)]
+pub struct GetBoardController {
+    player_service: Arc<PlayerService>,
+}
+
+impl GetBoardController {
+    pub fn new(player_service: Arc<PlayerService>) -> Self {
+        Self { player_service }
+    }
+}
+
+impl PrintableController for GetBoardController {}
+
+#[async_trait]
+impl Controller for GetBoardController {
+    async fn handle_request(
+        &self,
+        req: Request,
+        context: RequestContext,
+    ) -> Result<ResponseData, ServiceError> {
+        match *req.get_data() {
+            RequestData::GetBoard => req,
+            _ => panic!("invalid request"),
+        };
+        let player = match self.player_service.get_player(context.client_id) {
+            Some(player) => player,
+            None => return Err(ServiceError::PlayerNotFound),
+        };
+        let game = match player.get_game().or_else(|| player.get_spectating()) {
+            Some(game) => game,
+            None => return Err(ServiceError::NotInGame),
+        };
+        let board = Board::from(&*game.get_board().lock().unwrap());
+        Ok(ResponseData::GetBoard(GetBoardResponse {
+            success: true,
+            board: Some(board),
+            board_sequence: game.get_board_sequence(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::{game_service::GameService, lobby_service::LobbyService};
+    use std::{collections::HashSet, error::Error};
+
+    #[tokio::test]
+    async fn handle_request_with_player_not_in_game_should_return_error() {
+        let game_service = Arc::new(GameService::new(HashSet::new()));
+        let player_service = Arc::new(PlayerService::new(
+            Arc::new(LobbyService::new()),
+            game_service,
+        ));
+        player_service.add_player(0, String::from("test"));
+        let controller = GetBoardController::new(player_service);
+        assert!(controller
+            .handle_request(
+                Request::new(0, Arc::new(RequestData::GetBoard)),
+                RequestContext { client_id: 0 },
+            )
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_player_in_game_should_return_board(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let game_service = Arc::new(GameService::new(HashSet::new()));
+        let player_service = Arc::new(PlayerService::new(
+            lobby_service.clone(),
+            game_service.clone(),
+        ));
+        let leader = player_service.add_player(0, String::from("test"));
+        let lobby = lobby_service.create_lobby(leader.clone(), 4).await?;
+        lobby.get_player(0).unwrap().set_ready(true);
+        let game = GameService::start_game(game_service, leader, lobby).await?;
+        let controller = GetBoardController::new(player_service);
+        match controller
+            .handle_request(
+                Request::new(0, Arc::new(RequestData::GetBoard)),
+                RequestContext { client_id: 0 },
+            )
+            .await?
+        {
+            ResponseData::GetBoard(res) => {
+                assert!(res.success);
+                assert!(res.board.is_some());
+                assert_eq!(res.board_sequence, game.get_board_sequence());
+            }
+            _ => panic!("invalid response"),
+        }
+        Ok(())
+    }
+}
@@ -10,8 +10,10 @@ use crate::{
 use std::sync::Arc;
 
 use crate::controller::controller::Controller;
+use crate::error::ServiceError;
 #[cfg(not_test)]
 use crate::model::game::card::Card;
+use async_trait::async_trait;
 
 #[derive(Debug, Clone)]
 pub struct GetNewCardController {
@@ -30,40 +32,44 @@ impl GetNewCardController {
 
 impl PrintableController for GetNewCardController {}
 
+#[async_trait]
 impl Controller for GetNewCardController {
-    fn handle_request(
+    async fn handle_request(
         &self,
         req: Request,
         context: RequestContext,
-    ) -> Result<ResponseData, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<ResponseData, ServiceError> {
         match *req.get_data() {
             RequestData::GetNewCard => req,
             _ => panic!("invalid request"),
         };
         let player = match self.player_service.get_player(context.client_id) {
             Some(player) => player,
-            None => return Err("Player not found".into()),
+            None => return Err(ServiceError::PlayerNotFound),
         };
         let game = match player.get_game() {
             Some(game) => game,
-            None => return Err("Player not in a game".into()),
+            None => return Err(ServiceError::NotInGame),
         };
         let game_player = match game.get_player(player.id) {
             Some(game_player) => game_player,
-            None => return Err("Player not found".into()),
+            None => return Err(ServiceError::PlayerNotFound),
         };
         let turn_player = game.get_player_in_this_turn();
         if turn_player != game_player {
-            return Err("Player get new card when not his turn".into());
+            return Err(ServiceError::NotYourTurn);
         };
         if turn_player.get_has_shuffled() {
-            return Err("Player has shuffled in this turn".into());
+            return Err(ServiceError::AlreadyShuffled);
         }
-        let cards = self.game_service.shuffle(
-            #[cfg(not(test))]
-            game,
-            turn_player,
-        )?;
+        let cards = self
+            .game_service
+            .shuffle(
+                #[cfg(not(test))]
+                game,
+                turn_player,
+            )
+            .await?;
         Ok(ResponseData::GetNewCard(GetNewCardResponse {
             success: true,
             cards: Some(crate::model::game::cards::Cards::from(&cards)),
@@ -95,14 +101,16 @@ mod tests {
             .player_service
             .add_player(0, String::from("test"));
         let lobby_service = Arc::new(lobby_service::LobbyService::new());
-        let lobby = lobby_service.create_lobby(player.clone(), 4)?;
+        let lobby = lobby_service.create_lobby(player.clone(), 4).await?;
         let lobby_player = lobby.clone().get_player(player.clone().id).unwrap();
         lobby_player.set_ready(true);
-        GameService::start_game(game_service, player, lobby)?;
-        let res = controller.handle_request(
-            Request::new(0, Arc::new(RequestData::GetNewCard)),
-            RequestContext { client_id: 0 },
-        )?;
+        GameService::start_game(game_service, player, lobby).await?;
+        let res = controller
+            .handle_request(
+                Request::new(0, Arc::new(RequestData::GetNewCard)),
+                RequestContext { client_id: 0 },
+            )
+            .await?;
         match res {
             ResponseData::GetNewCard(data) => {
                 let cards = data.cards.unwrap().cards;
@@ -131,13 +139,15 @@ mod tests {
             .player_service
             .add_player(1, String::from("test1"));
         let lobby_service = Arc::new(lobby_service::LobbyService::new());
-        let lobby = lobby_service.create_lobby(player.clone(), 4)?;
-        lobby_service.add_player_to_lobby(player1.clone(), lobby.clone())?;
+        let lobby = lobby_service.create_lobby(player.clone(), 4).await?;
+        lobby_service
+            .add_player_to_lobby(player1.clone(), lobby.clone())
+            .await?;
         let lobby_player = lobby.clone().get_player(player.clone().id).unwrap();
         let lobby_player1 = lobby.clone().get_player(player1.clone().id).unwrap();
         lobby_player.set_ready(true);
         lobby_player1.set_ready(true);
-        let game = GameService::start_game(game_service.clone(), player, lobby)?;
+        let game = GameService::start_game(game_service.clone(), player, lobby).await?;
         let player_now = game.get_player_in_this_turn();
         assert!(controller
             .handle_request(
@@ -150,6 +160,7 @@ mod tests {
                     }
                 },
             )
+            .await
             .is_err());
         Ok(())
     }
@@ -169,19 +180,22 @@ mod tests {
             .player_service
             .add_player(0, String::from("test"));
         let lobby_service = Arc::new(lobby_service::LobbyService::new());
-        let lobby = lobby_service.create_lobby(player.clone(), 4)?;
+        let lobby = lobby_service.create_lobby(player.clone(), 4).await?;
         let lobby_player = lobby.clone().get_player(player.clone().id).unwrap();
         lobby_player.set_ready(true);
-        GameService::start_game(game_service, player, lobby)?;
-        controller.handle_request(
-            Request::new(0, Arc::new(RequestData::GetNewCard)),
-            RequestContext { client_id: 0 },
-        )?;
+        GameService::start_game(game_service, player, lobby).await?;
+        controller
+            .handle_request(
+                Request::new(0, Arc::new(RequestData::GetNewCard)),
+                RequestContext { client_id: 0 },
+            )
+            .await?;
         assert!(controller
             .handle_request(
                 Request::new(0, Arc::new(RequestData::GetNewCard)),
                 RequestContext { client_id: 0 }
             )
+            .await
             .is_err());
         Ok(())
     }
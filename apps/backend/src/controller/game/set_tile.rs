@@ -11,6 +11,8 @@ use crate::{
 use std::sync::Arc;
 
 use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
 
 #[derive(Debug, Clone)]
 pub struct SetTileController {
@@ -29,12 +31,13 @@ impl SetTileController {
 
 impl PrintableController for SetTileController {}
 
+#[async_trait]
 impl Controller for SetTileController {
-    fn handle_request(
+    async fn handle_request(
         &self,
         req: Request,
         context: RequestContext,
-    ) -> Result<ResponseData, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<ResponseData, ServiceError> {
         let data = req.get_data();
         let req = match data.as_ref() {
             RequestData::SetTile(req) => req,
@@ -42,40 +45,42 @@ impl Controller for SetTileController {
         };
         let player = match self.player_service.get_player(context.client_id) {
             Some(player) => player,
-            None => return Err("Player not found".into()),
+            None => return Err(ServiceError::PlayerNotFound),
         };
         let game = match player.get_game() {
             Some(game) => game,
-            None => return Err("Player not in a game".into()),
+            None => return Err(ServiceError::NotInGame),
         };
         let game_player = match game.get_player(player.id) {
             Some(game_player) => game_player,
-            None => return Err("Player not found".into()),
+            None => return Err(ServiceError::PlayerNotFound),
         };
         let card = game_player.get_card(req.card_index as usize);
         if card.used {
-            return Err("Card has used".into());
+            return Err(ServiceError::CardAlreadyUsed);
         }
         let turn_player = game.get_player_in_this_turn();
         if turn_player != game_player {
-            return Err("Player can't place tile when not his turn".into());
+            return Err(ServiceError::NotYourTurn);
         }
         if req.x >= 26 {
-            return Err("Tile out of board".into());
+            return Err(ServiceError::TileOutOfBoard);
         }
         if req.y >= 26 {
-            return Err("Tile out of board".into());
+            return Err(ServiceError::TileOutOfBoard);
         }
-        self.game_service.place_tile_on_board(
-            game.clone(),
-            Tile {
-                char: card.char,
-                owner: player,
-                turn: game.get_turns(),
-            },
-            req.x as usize,
-            req.y as usize,
-        );
+        self.game_service
+            .place_tile_on_board(
+                game.clone(),
+                Tile {
+                    char: card.char,
+                    owner: player,
+                    turn: game.get_turns(),
+                },
+                req.x as usize,
+                req.y as usize,
+            )
+            .await;
         game_player.take_card(req.card_index as usize);
         Ok(ResponseData::SetTile(SetTileResponse { success: true }))
     }
@@ -107,21 +112,23 @@ mod tests {
             .player_service
             .add_player(0, String::from("test"));
         let lobby_service = Arc::new(lobby_service::LobbyService::new());
-        let lobby = lobby_service.create_lobby(player.clone(), 4)?;
+        let lobby = lobby_service.create_lobby(player.clone(), 4).await?;
         let lobby_player = lobby.clone().get_player(player.clone().id).unwrap();
         lobby_player.set_ready(true);
-        let game = GameService::start_game(game_service, player, lobby)?;
-        controller.handle_request(
-            Request::new(
-                0,
-                Arc::new(RequestData::SetTile(SetTileRequest {
-                    x: 1,
-                    y: 2,
-                    card_index: 1,
-                })),
-            ),
-            RequestContext { client_id: 0 },
-        )?;
+        let game = GameService::start_game(game_service, player, lobby).await?;
+        controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::SetTile(SetTileRequest {
+                        x: 1,
+                        y: 2,
+                        card_index: 1,
+                    })),
+                ),
+                RequestContext { client_id: 0 },
+            )
+            .await?;
         assert!(game.get_board().lock().unwrap().tiles[23][1].is_some());
         Ok(())
     }
@@ -144,13 +151,15 @@ mod tests {
             .player_service
             .add_player(1, String::from("test1"));
         let lobby_service = Arc::new(lobby_service::LobbyService::new());
-        let lobby = lobby_service.create_lobby(player.clone(), 4)?;
-        lobby_service.add_player_to_lobby(player1.clone(), lobby.clone())?;
+        let lobby = lobby_service.create_lobby(player.clone(), 4).await?;
+        lobby_service
+            .add_player_to_lobby(player1.clone(), lobby.clone())
+            .await?;
         let lobby_player = lobby.clone().get_player(player.clone().id).unwrap();
         let lobby_player1 = lobby.clone().get_player(player1.clone().id).unwrap();
         lobby_player.set_ready(true);
         lobby_player1.set_ready(true);
-        let game = GameService::start_game(game_service, player, lobby)?;
+        let game = GameService::start_game(game_service, player, lobby).await?;
         let player_now = game.get_player_in_this_turn();
         assert!(controller
             .handle_request(
@@ -170,6 +179,7 @@ mod tests {
                     }
                 },
             )
+            .await
             .is_err());
         Ok(())
     }
@@ -189,7 +199,7 @@ mod tests {
             .player_service
             .add_player(0, String::from("test"));
         let lobby_service = Arc::new(lobby_service::LobbyService::new());
-        let lobby = lobby_service.create_lobby(player.clone(), 4)?;
+        let lobby = lobby_service.create_lobby(player.clone(), 4).await?;
         let lobby_player = lobby.clone().get_player(player.clone().id).unwrap();
         lobby_player.set_ready(true);
         assert!(controller
@@ -204,6 +214,7 @@ mod tests {
                 ),
                 RequestContext { client_id: 0 },
             )
+            .await
             .is_err());
         Ok(())
     }
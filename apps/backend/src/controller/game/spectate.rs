@@ -0,0 +1,178 @@
+use std::sync::Arc;
+
+use crate::frame::Request;
+use crate::model::game::spectate::SpectateResponse;
+use crate::{
+    controller::controller::PrintableController,
+    frame::{RequestData, ResponseData},
+    router::RequestContext,
+    service::{
+        game_service::GameService, lobby_service::LobbyService, player_service::PlayerService,
+    },
+};
+
+use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
+
+#[derive(Debug, Clone)]
+pub struct SpectateController {
+    player_service: Arc<PlayerService>,
+    lobby_service: Arc<LobbyService>,
+    game_service: Arc<GameService>,
+}
+
+impl SpectateController {
+    pub fn new(
+        player_service: Arc<PlayerService>,
+        lobby_service: Arc<LobbyService>,
+        game_service: Arc<GameService>,
+    ) -> Self {
+        Self {
+            player_service,
+            lobby_service,
+            game_service,
+        }
+    }
+}
+
+impl PrintableController for SpectateController {}
+
+#[async_trait]
+impl Controller for SpectateController {
+    async fn handle_request(
+        &self,
+        req: Request,
+        context: RequestContext,
+    ) -> Result<ResponseData, ServiceError> {
+        let data = req.get_data();
+        let req = match data.as_ref() {
+            RequestData::Spectate(req) => req,
+            _ => panic!("invalid request"),
+        };
+        let player = match self.player_service.get_player(context.client_id) {
+            Some(player) => player,
+            None => return Err(ServiceError::PlayerNotFound),
+        };
+        let lobby = match self
+            .lobby_service
+            .get_lobby(req.lobby_id)
+            .filter(|lobby| !lobby.is_private())
+        {
+            Some(lobby) => lobby,
+            None => return Err(ServiceError::LobbyNotFound),
+        };
+        let game = self.game_service.spectate(player, lobby).await?;
+        Ok(ResponseData::Spectate(SpectateResponse::from(&game)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{lobby::lobby_settings::LobbySettings, model::game::spectate::SpectateRequest};
+    use std::{collections::HashSet, error::Error};
+
+    async fn setup(
+    ) -> Result<(SpectateController, Arc<crate::lobby::lobby::Lobby>), Box<dyn Error + Send + Sync>>
+    {
+        let lobby_service = Arc::new(LobbyService::new());
+        let game_service = Arc::new(GameService::new(HashSet::new()));
+        let player_service = Arc::new(PlayerService::new(
+            lobby_service.clone(),
+            game_service.clone(),
+        ));
+        let leader = player_service.add_player(0, String::from("test1"));
+        player_service.add_player(1, String::from("test2"));
+        let lobby = lobby_service.create_lobby(leader.clone(), 4).await?;
+        lobby.get_player(0).unwrap().set_ready(true);
+        GameService::start_game(game_service.clone(), leader, lobby.clone()).await?;
+        Ok((
+            SpectateController::new(player_service, lobby_service, game_service),
+            lobby,
+        ))
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_started_game_should_spectate_game(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let (controller, _) = setup().await?;
+        let res = match controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::Spectate(SpectateRequest { lobby_id: 0 })),
+                ),
+                RequestContext { client_id: 1 },
+            )
+            .await?
+        {
+            ResponseData::Spectate(res) => res,
+            _ => panic!("invalid response"),
+        };
+        assert_eq!(res.players.unwrap().players.len(), 1);
+        assert_eq!(res.spectators.unwrap().players[0].id, 1);
+        let spectator = controller.player_service.get_player(1).unwrap();
+        assert!(spectator.get_spectating().is_some());
+        assert!(spectator.get_game().is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_spectators_not_allowed_should_return_error(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let game_service = Arc::new(GameService::new(HashSet::new()));
+        let player_service = Arc::new(PlayerService::new(
+            lobby_service.clone(),
+            game_service.clone(),
+        ));
+        let leader = player_service.add_player(0, String::from("test1"));
+        player_service.add_player(1, String::from("test2"));
+        let lobby = lobby_service.create_lobby(leader.clone(), 4).await?;
+        lobby.set_settings(LobbySettings {
+            allow_spectators: false,
+            ..lobby.get_settings()
+        });
+        lobby.get_player(0).unwrap().set_ready(true);
+        GameService::start_game(game_service.clone(), leader, lobby).await?;
+        let controller = SpectateController::new(player_service, lobby_service, game_service);
+        assert!(controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::Spectate(SpectateRequest { lobby_id: 0 }))
+                ),
+                RequestContext { client_id: 1 },
+            )
+            .await
+            .is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_lobby_not_started_should_return_error(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let game_service = Arc::new(GameService::new(HashSet::new()));
+        let player_service = Arc::new(PlayerService::new(
+            lobby_service.clone(),
+            game_service.clone(),
+        ));
+        let leader = player_service.add_player(0, String::from("test1"));
+        player_service.add_player(1, String::from("test2"));
+        lobby_service.create_lobby(leader, 4).await?;
+        let controller = SpectateController::new(player_service, lobby_service, game_service);
+        assert!(controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::Spectate(SpectateRequest { lobby_id: 0 }))
+                ),
+                RequestContext { client_id: 1 },
+            )
+            .await
+            .is_err());
+        Ok(())
+    }
+}
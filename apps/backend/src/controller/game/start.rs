@@ -13,6 +13,8 @@ use crate::{
 };
 
 use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
 
 #[derive(Debug, Clone)]
 pub struct StartController {
@@ -31,12 +33,13 @@ impl StartController {
 
 impl PrintableController for StartController {}
 
+#[async_trait]
 impl Controller for StartController {
-    fn handle_request(
+    async fn handle_request(
         &self,
         req: Request,
         context: RequestContext,
-    ) -> Result<ResponseData, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<ResponseData, ServiceError> {
         match *req.get_data() {
             RequestData::StartGame => req,
             _ => panic!("invalid request"),
@@ -44,20 +47,24 @@ impl Controller for StartController {
 
         let player = match self.player_service.get_player(context.client_id) {
             Some(player) => player,
-            None => return Err("Player not found".into()),
+            None => return Err(ServiceError::PlayerNotFound),
         };
         let lobby = match player.get_lobby() {
             Some(lobby) => lobby,
-            None => return Err("Player not in lobby".into()),
+            None => return Err(ServiceError::NotInLobby),
         };
+        // the auto-start countdown takes the same lock, so the game is started only once
+        let _guard = lobby.lock_start().await;
         match player.get_game() {
-            Some(_) => return Err("player already in game".into()),
+            Some(_) => return Err(ServiceError::AlreadyInGame),
             None => (),
         };
-        let game = GameService::start_game(self.game_service.clone(), player.clone(), lobby)?;
+        let game =
+            GameService::start_game(self.game_service.clone(), player.clone(), lobby.clone())
+                .await?;
         let game_player = match game.get_player(player.id) {
             Some(game_player) => game_player,
-            None => return Err("find no player".into()),
+            None => return Err(ServiceError::PlayerNotFound),
         };
         Ok(ResponseData::StartGame(StartResponse {
             success: true,
@@ -82,16 +89,17 @@ impl Controller for StartController {
 mod tests {
     use std::{collections::HashSet, error::Error};
 
-    use crate::service::lobby_service::{self, LobbyService};
+    use crate::service::lobby_service::LobbyService;
 
     use super::*;
 
     #[tokio::test]
     async fn handle_request_with_test_user_in_test_lobby_should_start_game(
     ) -> Result<(), Box<dyn Error + Sync + Send>> {
+        let lobby_service = Arc::new(LobbyService::new());
         let controller = StartController::new(
             Arc::new(PlayerService::new(
-                Arc::new(LobbyService::new()),
+                lobby_service.clone(),
                 Arc::new(GameService::new(HashSet::new())),
             )),
             Arc::new(GameService::new(HashSet::new())),
@@ -99,14 +107,16 @@ mod tests {
         let player = controller
             .player_service
             .add_player(0, String::from("test"));
-        let lobby_service = Arc::new(lobby_service::LobbyService::new());
-        let lobby = lobby_service.create_lobby(player.clone(), 4)?;
+        let lobby = lobby_service.create_lobby(player.clone(), 4).await?;
         let lobby_player = lobby.get_player(player.clone().id).unwrap();
         lobby_player.set_ready(true);
-        let res = match controller.handle_request(
-            Request::new(0, Arc::new(RequestData::StartGame)),
-            RequestContext { client_id: 0 },
-        )? {
+        let res = match controller
+            .handle_request(
+                Request::new(0, Arc::new(RequestData::StartGame)),
+                RequestContext { client_id: 0 },
+            )
+            .await?
+        {
             ResponseData::StartGame(res) => res,
             _ => panic!("invalid response"),
         };
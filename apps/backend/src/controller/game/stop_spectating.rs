@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use crate::frame::Request;
+use crate::model::game::stop_spectating::StopSpectatingResponse;
+use crate::{
+    controller::controller::PrintableController,
+    frame::{RequestData, ResponseData},
+    router::RequestContext,
+    service::{game_service::GameService, player_service::PlayerService},
+};
+
+use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
+
+#[derive(Debug, Clone)]
+pub struct StopSpectatingController {
+    player_service: Arc<PlayerService>,
+    game_service: Arc<GameService>,
+}
+
+impl StopSpectatingController {
+    pub fn new(player_service: Arc<PlayerService>, game_service: Arc<GameService>) -> Self {
+        Self {
+            player_service,
+            game_service,
+        }
+    }
+}
+
+impl PrintableController for StopSpectatingController {}
+
+#[async_trait]
+impl Controller for StopSpectatingController {
+    async fn handle_request(
+        &self,
+        req: Request,
+        context: RequestContext,
+    ) -> Result<ResponseData, ServiceError> {
+        match *req.get_data() {
+            RequestData::StopSpectating => req,
+            _ => panic!("invalid request"),
+        };
+        let player = match self.player_service.get_player(context.client_id) {
+            Some(player) => player,
+            None => return Err(ServiceError::PlayerNotFound),
+        };
+        self.game_service.stop_spectating(player).await?;
+        Ok(ResponseData::StopSpectating(StopSpectatingResponse {
+            success: true,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::lobby_service::LobbyService;
+    use std::{collections::HashSet, error::Error};
+
+    #[tokio::test]
+    async fn handle_request_with_player_not_spectating_should_return_error() {
+        let game_service = Arc::new(GameService::new(HashSet::new()));
+        let player_service = Arc::new(PlayerService::new(
+            Arc::new(LobbyService::new()),
+            game_service.clone(),
+        ));
+        player_service.add_player(0, String::from("test"));
+        let controller = StopSpectatingController::new(player_service, game_service);
+        assert!(controller
+            .handle_request(
+                Request::new(0, Arc::new(RequestData::StopSpectating)),
+                RequestContext { client_id: 0 },
+            )
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_spectator_should_stop_spectating(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let game_service = Arc::new(GameService::new(HashSet::new()));
+        let player_service = Arc::new(PlayerService::new(
+            lobby_service.clone(),
+            game_service.clone(),
+        ));
+        let leader = player_service.add_player(0, String::from("test1"));
+        let spectator = player_service.add_player(1, String::from("test2"));
+        let lobby = lobby_service.create_lobby(leader.clone(), 4).await?;
+        lobby.get_player(0).unwrap().set_ready(true);
+        let game = GameService::start_game(game_service.clone(), leader, lobby.clone()).await?;
+        game_service.spectate(spectator.clone(), lobby).await?;
+        let controller = StopSpectatingController::new(player_service, game_service);
+        controller
+            .handle_request(
+                Request::new(0, Arc::new(RequestData::StopSpectating)),
+                RequestContext { client_id: 1 },
+            )
+            .await?;
+        assert!(spectator.get_spectating().is_none());
+        assert!(game.get_spectators().is_empty());
+        Ok(())
+    }
+}
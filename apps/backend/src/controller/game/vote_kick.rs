@@ -0,0 +1,124 @@
+use std::sync::Arc;
+
+use crate::frame::Request;
+use crate::model::game::vote_kick::VoteKickResponse;
+use crate::{
+    controller::controller::PrintableController,
+    frame::{RequestData, ResponseData},
+    router::RequestContext,
+    service::{game_service::GameService, player_service::PlayerService},
+};
+
+use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
+
+#[derive(Debug, Clone)]
+pub struct VoteKickController {
+    player_service: Arc<PlayerService>,
+    game_service: Arc<GameService>,
+}
+
+impl VoteKickController {
+    pub fn new(player_service: Arc<PlayerService>, game_service: Arc<GameService>) -> Self {
+        Self {
+            player_service,
+            game_service,
+        }
+    }
+}
+
+impl PrintableController for VoteKickController {}
+
+#[async_trait]
+impl Controller for VoteKickController {
+    async fn handle_request(
+        &self,
+        req: Request,
+        context: RequestContext,
+    ) -> Result<ResponseData, ServiceError> {
+        let data = req.get_data();
+        let req = match data.as_ref() {
+            RequestData::VoteKick(req) => req,
+            _ => panic!("invalid request"),
+        };
+        let player = match self.player_service.get_player(context.client_id) {
+            Some(player) => player,
+            None => return Err(ServiceError::PlayerNotFound),
+        };
+        let (votes, kicked) =
+            GameService::vote_kick(self.game_service.clone(), player, req.player_id).await?;
+        Ok(ResponseData::VoteKick(VoteKickResponse {
+            success: true,
+            votes,
+            kicked,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{model::game::vote_kick::VoteKickRequest, service::lobby_service::LobbyService};
+    use std::{collections::HashSet, error::Error};
+
+    fn vote_kick_request(player_id: u32) -> Request {
+        Request::new(
+            0,
+            Arc::new(RequestData::VoteKick(VoteKickRequest { player_id })),
+        )
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_majority_vote_against_afk_player_should_kick_player(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let game_service = Arc::new(GameService::new(HashSet::new()));
+        let controller = VoteKickController::new(
+            Arc::new(PlayerService::new(
+                lobby_service.clone(),
+                game_service.clone(),
+            )),
+            game_service.clone(),
+        );
+        let leader = controller
+            .player_service
+            .add_player(0, String::from("test1"));
+        let lobby = lobby_service.create_lobby(leader.clone(), 4).await?;
+        for id in 1..3 {
+            let player = controller
+                .player_service
+                .add_player(id, format!("test{}", id + 1));
+            lobby_service
+                .add_player_to_lobby(player, lobby.clone())
+                .await?;
+        }
+        for lobby_player in lobby.get_players() {
+            lobby_player.set_ready(true);
+        }
+        let game = GameService::start_game(game_service, leader, lobby).await?;
+        game.get_player(2).unwrap().set_afk(true);
+        assert!(controller
+            .handle_request(vote_kick_request(1), RequestContext { client_id: 0 })
+            .await
+            .is_err());
+        let res = match controller
+            .handle_request(vote_kick_request(2), RequestContext { client_id: 0 })
+            .await?
+        {
+            ResponseData::VoteKick(res) => res,
+            _ => panic!("invalid response"),
+        };
+        assert!(!res.kicked);
+        let res = match controller
+            .handle_request(vote_kick_request(2), RequestContext { client_id: 1 })
+            .await?
+        {
+            ResponseData::VoteKick(res) => res,
+            _ => panic!("invalid response"),
+        };
+        assert!(res.kicked);
+        assert!(game.get_player(2).is_none());
+        Ok(())
+    }
+}
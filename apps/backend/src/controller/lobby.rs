@@ -1,5 +1,8 @@
 pub mod create;
 pub mod join;
+pub mod kick;
 pub mod list;
 pub mod quit;
 pub mod ready;
+pub mod rematch;
+pub mod update;
@@ -1,6 +1,8 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::frame::Request;
+use crate::lobby::{lobby::Lobby, lobby_settings::LobbySettings};
 use crate::model::lobby::create::CreateResponse;
 use crate::{
     controller::controller::PrintableController,
@@ -10,6 +12,8 @@ use crate::{
 };
 
 use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
 
 #[derive(Debug, Clone)]
 pub struct CreateController {
@@ -28,12 +32,13 @@ impl CreateController {
 
 impl PrintableController for CreateController {}
 
+#[async_trait]
 impl Controller for CreateController {
-    fn handle_request(
+    async fn handle_request(
         &self,
         req: Request,
         context: RequestContext,
-    ) -> Result<ResponseData, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<ResponseData, ServiceError> {
         let data = req.get_data();
         let req = match data.as_ref() {
             RequestData::CreateLobby(req) => req,
@@ -41,9 +46,44 @@ impl Controller for CreateController {
         };
         let leader = match self.player_service.get_player(context.client_id) {
             Some(player) => player,
-            None => return Err("Player not found".into()),
+            None => return Err(ServiceError::PlayerNotFound),
         };
-        let lobby = self.lobby_service.create_lobby(leader, req.max_players)?;
+        let mut settings = LobbySettings::new(
+            match req.name.trim().is_empty() {
+                true => Lobby::default_name(&leader),
+                false => req.name.clone(),
+            },
+            req.is_private,
+            req.password.clone().filter(|password| !password.is_empty()),
+        );
+        if let Some(language) = &req.language {
+            settings.language = language.clone();
+        }
+        if let Some(dictionary) = &req.dictionary {
+            settings.dictionary = dictionary.clone();
+        }
+        if let Some(allow_spectators) = req.allow_spectators {
+            settings.allow_spectators = allow_spectators;
+        }
+        if let Some(spectator_delay) = req.spectator_delay {
+            settings.spectator_delay = Duration::from_secs(spectator_delay as u64);
+        }
+        if let Some(auto_start) = req.auto_start {
+            settings.auto_start = auto_start;
+        }
+        if let Some(auto_start_min_players) = req.auto_start_min_players {
+            settings.auto_start_min_players = auto_start_min_players;
+        }
+        if let Some(afk_turns) = req.afk_turns {
+            settings.afk_turns = afk_turns;
+        }
+        if let Some(afk_removal_turns) = req.afk_removal_turns {
+            settings.afk_removal_turns = afk_removal_turns;
+        }
+        let lobby = self
+            .lobby_service
+            .create_lobby_with_settings(leader, req.max_players, settings)
+            .await?;
 
         Ok(ResponseData::CreateLobby(CreateResponse {
             success: true,
@@ -58,8 +98,8 @@ mod tests {
     use crate::{model::lobby::create::CreateRequest, service::game_service::GameService};
     use std::{collections::HashSet, error::Error};
 
-    #[test]
-    fn handle_request_with_test_user_should_create_lobby(
+    #[tokio::test]
+    async fn handle_request_with_test_user_should_create_lobby(
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let player_service = Arc::new(PlayerService::new(
             Arc::new(LobbyService::new()),
@@ -67,13 +107,29 @@ mod tests {
         ));
         player_service.add_player(0, String::from("test"));
         let controller = CreateController::new(player_service, Arc::new(LobbyService::new()));
-        let res = match controller.handle_request(
-            Request::new(
-                0,
-                Arc::new(RequestData::CreateLobby(CreateRequest { max_players: 4 })),
-            ),
-            RequestContext { client_id: 0 },
-        )? {
+        let res = match controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::CreateLobby(CreateRequest {
+                        max_players: 4,
+                        name: String::new(),
+                        is_private: false,
+                        password: None,
+                        language: None,
+                        dictionary: None,
+                        allow_spectators: None,
+                        spectator_delay: None,
+                        auto_start: None,
+                        auto_start_min_players: None,
+                        afk_turns: None,
+                        afk_removal_turns: None,
+                    })),
+                ),
+                RequestContext { client_id: 0 },
+            )
+            .await?
+        {
             ResponseData::CreateLobby(res) => res,
             _ => panic!("invalid response"),
         };
@@ -82,8 +138,8 @@ mod tests {
         Ok(())
     }
 
-    #[test]
-    fn handle_request_with_test_user_and_invaild_max_players_should_return_error(
+    #[tokio::test]
+    async fn handle_request_with_test_user_and_invaild_max_players_should_return_error(
     ) -> Result<(), Box<dyn Error + Sync + Send>> {
         let player_service = Arc::new(PlayerService::new(
             Arc::new(LobbyService::new()),
@@ -95,25 +151,53 @@ mod tests {
             .handle_request(
                 Request::new(
                     0,
-                    Arc::new(RequestData::CreateLobby(CreateRequest { max_players: 3 }))
+                    Arc::new(RequestData::CreateLobby(CreateRequest {
+                        max_players: 3,
+                        name: String::new(),
+                        is_private: false,
+                        password: None,
+                        language: None,
+                        dictionary: None,
+                        allow_spectators: None,
+                        spectator_delay: None,
+                        auto_start: None,
+                        auto_start_min_players: None,
+                        afk_turns: None,
+                        afk_removal_turns: None,
+                    }))
                 ),
                 RequestContext { client_id: 0 },
             )
+            .await
             .is_err());
         assert!(controller
             .handle_request(
                 Request::new(
                     0,
-                    Arc::new(RequestData::CreateLobby(CreateRequest { max_players: 9 }))
+                    Arc::new(RequestData::CreateLobby(CreateRequest {
+                        max_players: 9,
+                        name: String::new(),
+                        is_private: false,
+                        password: None,
+                        language: None,
+                        dictionary: None,
+                        allow_spectators: None,
+                        spectator_delay: None,
+                        auto_start: None,
+                        auto_start_min_players: None,
+                        afk_turns: None,
+                        afk_removal_turns: None,
+                    }))
                 ),
                 RequestContext { client_id: 0 },
             )
+            .await
             .is_err());
         Ok(())
     }
 
-    #[test]
-    fn handle_request_with_not_exist_user_should_return_error(
+    #[tokio::test]
+    async fn handle_request_with_not_exist_user_should_return_error(
     ) -> Result<(), Box<dyn Error + Sync + Send>> {
         let player_service = Arc::new(PlayerService::new(
             Arc::new(LobbyService::new()),
@@ -124,16 +208,30 @@ mod tests {
             .handle_request(
                 Request::new(
                     0,
-                    Arc::new(RequestData::CreateLobby(CreateRequest { max_players: 4 }))
+                    Arc::new(RequestData::CreateLobby(CreateRequest {
+                        max_players: 4,
+                        name: String::new(),
+                        is_private: false,
+                        password: None,
+                        language: None,
+                        dictionary: None,
+                        allow_spectators: None,
+                        spectator_delay: None,
+                        auto_start: None,
+                        auto_start_min_players: None,
+                        afk_turns: None,
+                        afk_removal_turns: None,
+                    }))
                 ),
                 RequestContext { client_id: 0 },
             )
+            .await
             .is_err());
         Ok(())
     }
 
-    #[test]
-    fn handle_request_with_test_user_should_contains_test_user(
+    #[tokio::test]
+    async fn handle_request_with_test_user_should_contains_test_user(
     ) -> Result<(), Box<dyn Error + Sync + Send>> {
         let player_service = Arc::new(PlayerService::new(
             Arc::new(LobbyService::new()),
@@ -141,20 +239,134 @@ mod tests {
         ));
         let player = player_service.add_player(0, String::from("test"));
         let controller = CreateController::new(player_service, Arc::new(LobbyService::new()));
-        let res = match controller.handle_request(
-            Request::new(
-                0,
-                Arc::new(RequestData::CreateLobby(CreateRequest { max_players: 4 })),
-            ),
-            RequestContext { client_id: 0 },
-        )? {
+        let res = match controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::CreateLobby(CreateRequest {
+                        max_players: 4,
+                        name: String::new(),
+                        is_private: false,
+                        password: None,
+                        language: None,
+                        dictionary: None,
+                        allow_spectators: None,
+                        spectator_delay: None,
+                        auto_start: None,
+                        auto_start_min_players: None,
+                        afk_turns: None,
+                        afk_removal_turns: None,
+                    })),
+                ),
+                RequestContext { client_id: 0 },
+            )
+            .await?
+        {
             ResponseData::CreateLobby(res) => res,
             _ => panic!("invalid response"),
         };
         assert_eq!(
             res.lobby.unwrap().players[0],
-            crate::model::player::player::Player::from(player)
+            crate::model::lobby::lobby::LobbyPlayer {
+                id: player.id,
+                name: player.name.clone(),
+                ready: false,
+                leader: true,
+                rematch_vote: None,
+                rating: player.get_rating(),
+                rating_band: player.get_rating_band(),
+                rtt: None,
+                idle: false,
+            }
         );
         Ok(())
     }
+
+    #[tokio::test]
+    async fn handle_request_with_lobby_settings_should_create_lobby_with_settings(
+    ) -> Result<(), Box<dyn Error + Sync + Send>> {
+        let player_service = Arc::new(PlayerService::new(
+            Arc::new(LobbyService::new()),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        player_service.add_player(0, String::from("test"));
+        let lobby_service = Arc::new(LobbyService::new());
+        let controller = CreateController::new(player_service, lobby_service.clone());
+        let res = match controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::CreateLobby(CreateRequest {
+                        max_players: 4,
+                        name: String::from("room"),
+                        is_private: true,
+                        password: Some(String::from("1234")),
+                        language: None,
+                        dictionary: None,
+                        allow_spectators: None,
+                        spectator_delay: None,
+                        auto_start: None,
+                        auto_start_min_players: None,
+                        afk_turns: None,
+                        afk_removal_turns: None,
+                    })),
+                ),
+                RequestContext { client_id: 0 },
+            )
+            .await?
+        {
+            ResponseData::CreateLobby(res) => res,
+            _ => panic!("invalid response"),
+        };
+        let lobby = res.lobby.unwrap();
+        assert_eq!(lobby.name, "room");
+        assert!(lobby.is_private);
+        assert!(lobby.has_password);
+        assert!(lobby_service
+            .get_lobby(lobby.id)
+            .unwrap()
+            .check_password(Some("1234")));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_without_lobby_name_should_use_default_name(
+    ) -> Result<(), Box<dyn Error + Sync + Send>> {
+        let player_service = Arc::new(PlayerService::new(
+            Arc::new(LobbyService::new()),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        player_service.add_player(0, String::from("test"));
+        let controller = CreateController::new(player_service, Arc::new(LobbyService::new()));
+        let res = match controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::CreateLobby(CreateRequest {
+                        max_players: 4,
+                        name: String::from("  "),
+                        is_private: false,
+                        password: Some(String::new()),
+                        language: None,
+                        dictionary: None,
+                        allow_spectators: None,
+                        spectator_delay: None,
+                        auto_start: None,
+                        auto_start_min_players: None,
+                        afk_turns: None,
+                        afk_removal_turns: None,
+                    })),
+                ),
+                RequestContext { client_id: 0 },
+            )
+            .await?
+        {
+            ResponseData::CreateLobby(res) => res,
+            _ => panic!("invalid response"),
+        };
+        let lobby = res.lobby.unwrap();
+        assert_eq!(lobby.name, "test's lobby");
+        assert!(!lobby.has_password);
+        Ok(())
+    }
 }
@@ -10,6 +10,8 @@ use crate::{
 };
 
 use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
 
 #[derive(Debug, Clone)]
 pub struct JoinController {
@@ -28,12 +30,13 @@ impl JoinController {
 
 impl PrintableController for JoinController {}
 
+#[async_trait]
 impl Controller for JoinController {
-    fn handle_request(
+    async fn handle_request(
         &self,
         req: Request,
         context: RequestContext,
-    ) -> Result<ResponseData, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<ResponseData, ServiceError> {
         let data = req.get_data();
         let req = match data.as_ref() {
             RequestData::JoinLobby(req) => req,
@@ -41,17 +44,34 @@ impl Controller for JoinController {
         };
         let player = match self.player_service.get_player(context.client_id) {
             Some(player) => player,
-            None => return Err("Player not found".into()),
+            None => return Err(ServiceError::PlayerNotFound),
         };
-        let lobby = match self.lobby_service.get_lobby(req.lobby_id) {
+        // private lobbies can only be found by their invite code
+        let lobby = match &req.invite_code {
+            Some(invite_code) => self.lobby_service.get_lobby_by_invite_code(invite_code),
+            None => self
+                .lobby_service
+                .get_lobby(req.lobby_id)
+                .filter(|lobby| !lobby.is_private()),
+        };
+        let lobby = match lobby {
             Some(lobby) => lobby,
-            None => return Err("Lobby not found".into()),
+            None => return Err(ServiceError::LobbyNotFound),
         };
+        // players invited by the leader and joining with the invite code skip the password
+        let invited = req.invite_code.is_some() && lobby.is_invited(&player);
+        if !invited && !lobby.check_password(req.password.as_deref()) {
+            return Err(ServiceError::WrongLobbyPassword);
+        }
         self.lobby_service
-            .add_player_to_lobby(player, lobby.clone())?;
+            .add_player_to_lobby(player, lobby.clone())
+            .await?;
         Ok(ResponseData::JoinLobby(JoinResponse {
             success: true,
-            lobby: Some(crate::model::lobby::lobby::Lobby::from(lobby)),
+            lobby: Some(crate::model::lobby::lobby::Lobby::from(lobby.clone())),
+            chat_history: Some(crate::model::chat::message::ChatMessages::from(
+                &lobby.get_chat_history(),
+            )),
         }))
     }
 }
@@ -59,15 +79,16 @@ impl Controller for JoinController {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::lobby::lobby_settings::LobbySettings;
     use crate::model::lobby::join::JoinRequest;
     use crate::player::Player;
     use crate::service::game_service::GameService;
     use std::collections::HashSet;
     use std::error::Error;
 
-    #[test]
-    fn handle_request_with_test_user_should_join_lobby() -> Result<(), Box<dyn Error + Send + Sync>>
-    {
+    #[tokio::test]
+    async fn handle_request_with_test_user_should_join_lobby(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let player_service = Arc::new(PlayerService::new(
             Arc::new(LobbyService::new()),
             Arc::new(GameService::new(HashSet::new())),
@@ -75,15 +96,22 @@ mod tests {
         let leader = player_service.add_player(0, String::from("test1"));
         player_service.add_player(1, String::from("test2"));
         let lobby_service = Arc::new(LobbyService::new());
-        lobby_service.create_lobby(leader, 4)?;
+        lobby_service.create_lobby(leader, 4).await?;
         let controller = JoinController::new(player_service, lobby_service);
-        let res = match controller.handle_request(
-            Request::new(
-                0,
-                Arc::new(RequestData::JoinLobby(JoinRequest { lobby_id: 0 })),
-            ),
-            RequestContext { client_id: 1 },
-        )? {
+        let res = match controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::JoinLobby(JoinRequest {
+                        lobby_id: 0,
+                        password: None,
+                        invite_code: None,
+                    })),
+                ),
+                RequestContext { client_id: 1 },
+            )
+            .await?
+        {
             ResponseData::JoinLobby(res) => res,
             _ => panic!("invalid response"),
         };
@@ -93,8 +121,8 @@ mod tests {
         Ok(())
     }
 
-    #[test]
-    fn handle_request_with_not_exist_user_and_test_lobby_should_return_error(
+    #[tokio::test]
+    async fn handle_request_with_not_exist_user_and_test_lobby_should_return_error(
     ) -> Result<(), Box<dyn Error + Sync + Send>> {
         let player_service = Arc::new(PlayerService::new(
             Arc::new(LobbyService::new()),
@@ -102,22 +130,29 @@ mod tests {
         ));
         player_service.add_player(0, String::from("test1"));
         let lobby_service = Arc::new(LobbyService::new());
-        lobby_service.create_lobby(Arc::new(Player::new(0, String::from("test"))), 4)?;
+        lobby_service
+            .create_lobby(Arc::new(Player::new(0, String::from("test"))), 4)
+            .await?;
         let controller = JoinController::new(player_service, lobby_service);
         assert!(controller
             .handle_request(
                 Request::new(
                     0,
-                    Arc::new(RequestData::JoinLobby(JoinRequest { lobby_id: 0 }))
+                    Arc::new(RequestData::JoinLobby(JoinRequest {
+                        lobby_id: 0,
+                        password: None,
+                        invite_code: None,
+                    }))
                 ),
                 RequestContext { client_id: 1 },
             )
+            .await
             .is_err());
         Ok(())
     }
 
-    #[test]
-    fn handle_request_with_test_user_and_not_exist_lobby_should_return_error(
+    #[tokio::test]
+    async fn handle_request_with_test_user_and_not_exist_lobby_should_return_error(
     ) -> Result<(), Box<dyn Error + Sync + Send>> {
         let player_service = Arc::new(PlayerService::new(
             Arc::new(LobbyService::new()),
@@ -130,16 +165,21 @@ mod tests {
             .handle_request(
                 Request::new(
                     0,
-                    Arc::new(RequestData::JoinLobby(JoinRequest { lobby_id: 0 }))
+                    Arc::new(RequestData::JoinLobby(JoinRequest {
+                        lobby_id: 0,
+                        password: None,
+                        invite_code: None,
+                    }))
                 ),
                 RequestContext { client_id: 0 },
             )
+            .await
             .is_err());
         Ok(())
     }
 
-    #[test]
-    fn handle_request_with_not_exist_user_and_not_exist_lobby_should_return_error(
+    #[tokio::test]
+    async fn handle_request_with_not_exist_user_and_not_exist_lobby_should_return_error(
     ) -> Result<(), Box<dyn Error + Sync + Send>> {
         let player_service = Arc::new(PlayerService::new(
             Arc::new(LobbyService::new()),
@@ -150,11 +190,160 @@ mod tests {
             .handle_request(
                 Request::new(
                     0,
-                    Arc::new(RequestData::JoinLobby(JoinRequest { lobby_id: 0 }))
+                    Arc::new(RequestData::JoinLobby(JoinRequest {
+                        lobby_id: 0,
+                        password: None,
+                        invite_code: None,
+                    }))
                 ),
                 RequestContext { client_id: 0 },
             )
+            .await
             .is_err());
         Ok(())
     }
+
+    #[tokio::test]
+    async fn handle_request_with_private_lobby_id_should_return_error(
+    ) -> Result<(), Box<dyn Error + Sync + Send>> {
+        let player_service = Arc::new(PlayerService::new(
+            Arc::new(LobbyService::new()),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        let leader = player_service.add_player(0, String::from("test1"));
+        player_service.add_player(1, String::from("test2"));
+        let lobby_service = Arc::new(LobbyService::new());
+        let lobby = lobby_service.create_lobby(leader, 4).await?;
+        lobby.set_settings(LobbySettings::new(String::from("test"), true, None));
+        let controller = JoinController::new(player_service, lobby_service);
+        assert!(controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::JoinLobby(JoinRequest {
+                        lobby_id: 0,
+                        password: None,
+                        invite_code: None,
+                    }))
+                ),
+                RequestContext { client_id: 1 },
+            )
+            .await
+            .is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_private_lobby_invite_code_should_join_lobby(
+    ) -> Result<(), Box<dyn Error + Sync + Send>> {
+        let player_service = Arc::new(PlayerService::new(
+            Arc::new(LobbyService::new()),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        let leader = player_service.add_player(0, String::from("test1"));
+        let player = player_service.add_player(1, String::from("test2"));
+        let lobby_service = Arc::new(LobbyService::new());
+        let lobby = lobby_service.create_lobby(leader, 4).await?;
+        lobby.set_settings(LobbySettings::new(String::from("test"), true, None));
+        let controller = JoinController::new(player_service, lobby_service);
+        controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::JoinLobby(JoinRequest {
+                        lobby_id: 0,
+                        password: None,
+                        invite_code: Some(lobby.get_invite_code().to_string()),
+                    })),
+                ),
+                RequestContext { client_id: 1 },
+            )
+            .await?;
+        assert_eq!(player.get_lobby().unwrap(), lobby);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_wrong_password_should_return_error(
+    ) -> Result<(), Box<dyn Error + Sync + Send>> {
+        let player_service = Arc::new(PlayerService::new(
+            Arc::new(LobbyService::new()),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        let leader = player_service.add_player(0, String::from("test1"));
+        let player = player_service.add_player(1, String::from("test2"));
+        let lobby_service = Arc::new(LobbyService::new());
+        let lobby = lobby_service.create_lobby(leader, 4).await?;
+        lobby.set_settings(LobbySettings::new(
+            String::from("test"),
+            false,
+            Some(String::from("1234")),
+        ));
+        let controller = JoinController::new(player_service, lobby_service);
+        assert!(controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::JoinLobby(JoinRequest {
+                        lobby_id: 0,
+                        password: Some(String::from("4321")),
+                        invite_code: None,
+                    }))
+                ),
+                RequestContext { client_id: 1 },
+            )
+            .await
+            .is_err());
+        assert!(player.get_lobby().is_none());
+        controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::JoinLobby(JoinRequest {
+                        lobby_id: 0,
+                        password: Some(String::from("1234")),
+                        invite_code: None,
+                    })),
+                ),
+                RequestContext { client_id: 1 },
+            )
+            .await?;
+        assert_eq!(player.get_lobby().unwrap(), lobby);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_invited_player_and_invite_code_should_skip_password(
+    ) -> Result<(), Box<dyn Error + Sync + Send>> {
+        let player_service = Arc::new(PlayerService::new(
+            Arc::new(LobbyService::new()),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        let leader = player_service.add_player(0, String::from("test1"));
+        let player = player_service.add_player(1, String::from("test2"));
+        let lobby_service = Arc::new(LobbyService::new());
+        let lobby = lobby_service.create_lobby(leader, 4).await?;
+        lobby.set_settings(LobbySettings::new(
+            String::from("test"),
+            true,
+            Some(String::from("1234")),
+        ));
+        lobby.invite_player(&player);
+        let controller = JoinController::new(player_service, lobby_service);
+        controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::JoinLobby(JoinRequest {
+                        lobby_id: lobby.get_id(),
+                        password: None,
+                        invite_code: Some(String::from(lobby.get_invite_code())),
+                    })),
+                ),
+                RequestContext { client_id: 1 },
+            )
+            .await?;
+        assert_eq!(player.get_lobby().unwrap(), lobby);
+        Ok(())
+    }
 }
@@ -0,0 +1,197 @@
+use std::sync::Arc;
+
+use crate::frame::Request;
+use crate::model::lobby::kick::KickResponse;
+use crate::{
+    controller::controller::PrintableController,
+    frame::{RequestData, ResponseData},
+    router::RequestContext,
+    service::{
+        game_service::GameService, lobby_service::LobbyService, player_service::PlayerService,
+    },
+};
+
+use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
+
+#[derive(Debug, Clone)]
+pub struct KickController {
+    player_service: Arc<PlayerService>,
+    lobby_service: Arc<LobbyService>,
+    game_service: Arc<GameService>,
+}
+
+impl KickController {
+    pub fn new(
+        player_service: Arc<PlayerService>,
+        lobby_service: Arc<LobbyService>,
+        game_service: Arc<GameService>,
+    ) -> Self {
+        Self {
+            player_service,
+            lobby_service,
+            game_service,
+        }
+    }
+}
+
+impl PrintableController for KickController {}
+
+#[async_trait]
+impl Controller for KickController {
+    async fn handle_request(
+        &self,
+        req: Request,
+        context: RequestContext,
+    ) -> Result<ResponseData, ServiceError> {
+        let data = req.get_data();
+        let req = match data.as_ref() {
+            RequestData::Kick(req) => req,
+            _ => panic!("invalid request"),
+        };
+        let player = match self.player_service.get_player(context.client_id) {
+            Some(player) => player,
+            None => return Err(ServiceError::PlayerNotFound),
+        };
+        self.lobby_service
+            .kick_player(player.clone(), req.player_id, req.ban)
+            .await?;
+        // the kicked player may have been the last one not ready
+        if let Some(lobby) = player.get_lobby() {
+            LobbyService::schedule_auto_start(
+                self.lobby_service.clone(),
+                self.game_service.clone(),
+                lobby,
+            )
+            .await;
+        }
+        Ok(ResponseData::Kick(KickResponse {
+            success: true,
+            lobby: player
+                .get_lobby()
+                .map(crate::model::lobby::lobby::Lobby::from),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lobby::lobby_settings::LobbySettings;
+    use crate::model::lobby::kick::KickRequest;
+    use std::{collections::HashSet, error::Error};
+
+    #[tokio::test]
+    async fn handle_request_with_leader_should_kick_target(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let player_service = Arc::new(PlayerService::new(
+            lobby_service.clone(),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        let leader = player_service.add_player(0, String::from("test1"));
+        let player = player_service.add_player(1, String::from("test2"));
+        let lobby = lobby_service.create_lobby(leader, 4).await?;
+        lobby_service
+            .add_player_to_lobby(player.clone(), lobby)
+            .await?;
+        let controller = KickController::new(
+            player_service,
+            lobby_service,
+            Arc::new(GameService::new(HashSet::new())),
+        );
+        let res = match controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::Kick(KickRequest {
+                        player_id: 1,
+                        ban: false,
+                    })),
+                ),
+                RequestContext { client_id: 0 },
+            )
+            .await?
+        {
+            ResponseData::Kick(res) => res,
+            _ => panic!("invalid response"),
+        };
+        assert!(res.success);
+        assert_eq!(res.lobby.unwrap().players.len(), 1);
+        assert!(player.get_lobby().is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_target_not_in_lobby_should_return_error(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let player_service = Arc::new(PlayerService::new(
+            lobby_service.clone(),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        let leader = player_service.add_player(0, String::from("test1"));
+        player_service.add_player(1, String::from("test2"));
+        lobby_service.create_lobby(leader, 4).await?;
+        let controller = KickController::new(
+            player_service,
+            lobby_service,
+            Arc::new(GameService::new(HashSet::new())),
+        );
+        assert!(controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::Kick(KickRequest {
+                        player_id: 1,
+                        ban: true,
+                    }))
+                ),
+                RequestContext { client_id: 0 },
+            )
+            .await
+            .is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_only_unready_target_should_start_countdown(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let game_service = Arc::new(GameService::new(HashSet::new()));
+        let player_service = Arc::new(PlayerService::new(
+            lobby_service.clone(),
+            game_service.clone(),
+        ));
+        let leader = player_service.add_player(0, String::from("test1"));
+        let lobby = lobby_service.create_lobby(leader, 4).await?;
+        for id in 1..3 {
+            let player = player_service.add_player(id, format!("test{}", id + 1));
+            lobby_service
+                .add_player_to_lobby(player, lobby.clone())
+                .await?;
+        }
+        lobby.set_settings(LobbySettings {
+            auto_start: true,
+            ..lobby.get_settings()
+        });
+        lobby.get_player(0).unwrap().set_ready(true);
+        lobby.get_player(1).unwrap().set_ready(true);
+        let controller = KickController::new(player_service, lobby_service, game_service);
+        controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::Kick(KickRequest {
+                        player_id: 2,
+                        ban: false,
+                    })),
+                ),
+                RequestContext { client_id: 0 },
+            )
+            .await?;
+        assert!(lobby.has_auto_start_task());
+        Ok(())
+    }
+}
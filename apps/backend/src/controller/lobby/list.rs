@@ -1,44 +1,195 @@
 use std::sync::Arc;
 
 use crate::frame::Request;
+use crate::lobby::lobby_filter::LobbyFilter;
 use crate::model::lobby::list::ListResponse;
+use crate::service::lobby_service::{DEFAULT_LOBBY_PAGE_SIZE, MAX_LOBBY_PAGE_SIZE};
 use crate::{
     controller::controller::PrintableController,
     frame::{RequestData, ResponseData},
     router::RequestContext,
-    service::lobby_service::LobbyService,
+    service::{lobby_service::LobbyService, player_service::PlayerService},
 };
 
 use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
 
 #[derive(Debug, Clone)]
 pub struct ListController {
+    player_service: Arc<PlayerService>,
     lobby_service: Arc<LobbyService>,
 }
 
 impl ListController {
-    pub fn new(lobby_service: Arc<LobbyService>) -> Self {
-        Self { lobby_service }
+    pub fn new(player_service: Arc<PlayerService>, lobby_service: Arc<LobbyService>) -> Self {
+        Self {
+            player_service,
+            lobby_service,
+        }
     }
 }
 
 impl PrintableController for ListController {}
 
+#[async_trait]
 impl Controller for ListController {
-    fn handle_request(
+    async fn handle_request(
         &self,
         req: Request,
-        _: RequestContext,
-    ) -> Result<ResponseData, Box<dyn std::error::Error + Send + Sync>> {
-        match *req.get_data() {
-            RequestData::ListLobby => req,
+        context: RequestContext,
+    ) -> Result<ResponseData, ServiceError> {
+        let data = req.get_data();
+        let req = match data.as_ref() {
+            RequestData::ListLobby(req) => req,
             _ => panic!("invalid request"),
         };
+        if let Some(subscribe) = req.subscribe {
+            let player = match self.player_service.get_player(context.client_id) {
+                Some(player) => player,
+                None => return Err(ServiceError::PlayerNotFound),
+            };
+            match subscribe {
+                true => self
+                    .lobby_service
+                    .subscribe_lobby_list(player, LobbyFilter::from(req)),
+                false => self.lobby_service.unsubscribe_lobby_list(&player),
+            }
+        }
+        let page_size = match req.page_size {
+            0 => DEFAULT_LOBBY_PAGE_SIZE,
+            page_size => page_size.min(MAX_LOBBY_PAGE_SIZE),
+        };
+        let (lobbies, total) = self.lobby_service.get_public_lobbies_page(
+            &LobbyFilter::from(req),
+            req.page,
+            page_size,
+        );
         Ok(ResponseData::ListLobby(ListResponse {
             success: true,
-            lobby_infos: Some(crate::model::lobby::list::LobbyInfos::from(
-                self.lobby_service.get_lobbies(),
-            )),
+            lobby_infos: Some(crate::model::lobby::list::LobbyInfos::from(lobbies)),
+            page: req.page,
+            page_size,
+            total,
         }))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{model::lobby::list::ListRequest, service::game_service::GameService};
+    use std::{collections::HashSet, error::Error};
+
+    async fn list(
+        controller: &ListController,
+        req: ListRequest,
+    ) -> Result<ListResponse, Box<dyn Error + Send + Sync>> {
+        match controller
+            .handle_request(
+                Request::new(0, Arc::new(RequestData::ListLobby(req))),
+                RequestContext { client_id: 0 },
+            )
+            .await?
+        {
+            ResponseData::ListLobby(res) => Ok(res),
+            _ => panic!("invalid response"),
+        }
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_page_size_should_return_one_page(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let player_service = Arc::new(PlayerService::new(
+            lobby_service.clone(),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        for id in 0..5 {
+            let player = player_service.add_player(id, String::from("test"));
+            lobby_service.create_lobby(player, 4).await?;
+        }
+        let controller = ListController::new(player_service, lobby_service);
+        let res = list(
+            &controller,
+            ListRequest {
+                page: 1,
+                page_size: 2,
+                ..Default::default()
+            },
+        )
+        .await?;
+        let ids = res
+            .lobby_infos
+            .unwrap()
+            .lobby_infos
+            .iter()
+            .map(|lobby_info| lobby_info.id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![2, 3]);
+        assert_eq!(res.total, 5);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_language_filter_should_only_return_matching_lobbies(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let player_service = Arc::new(PlayerService::new(
+            lobby_service.clone(),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        let player = player_service.add_player(0, String::from("test1"));
+        lobby_service.create_lobby(player, 4).await?;
+        let player = player_service.add_player(1, String::from("test2"));
+        let lobby = lobby_service.create_lobby(player, 4).await?;
+        lobby.set_settings(crate::lobby::lobby_settings::LobbySettings {
+            language: String::from("zh"),
+            ..lobby.get_settings()
+        });
+        let controller = ListController::new(player_service, lobby_service);
+        let res = list(
+            &controller,
+            ListRequest {
+                language: Some(String::from("zh")),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let lobby_infos = res.lobby_infos.unwrap().lobby_infos;
+        assert_eq!(lobby_infos.len(), 1);
+        assert_eq!(lobby_infos[0].id, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_subscribe_should_subscribe_lobby_list(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let player_service = Arc::new(PlayerService::new(
+            lobby_service.clone(),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        let player = player_service.add_player(0, String::from("test"));
+        let controller = ListController::new(player_service, lobby_service.clone());
+        list(
+            &controller,
+            ListRequest {
+                subscribe: Some(true),
+                ..Default::default()
+            },
+        )
+        .await?;
+        assert!(lobby_service.is_subscribed_lobby_list(&player));
+        list(
+            &controller,
+            ListRequest {
+                subscribe: Some(false),
+                ..Default::default()
+            },
+        )
+        .await?;
+        assert!(!lobby_service.is_subscribed_lobby_list(&player));
+        Ok(())
+    }
+}
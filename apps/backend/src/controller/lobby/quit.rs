@@ -6,43 +6,64 @@ use crate::{
     controller::controller::PrintableController,
     frame::{RequestData, ResponseData},
     router::RequestContext,
-    service::{lobby_service::LobbyService, player_service::PlayerService},
+    service::{
+        game_service::GameService, lobby_service::LobbyService, player_service::PlayerService,
+    },
 };
 
 use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
 
 #[derive(Debug, Clone)]
 pub struct QuitController {
     player_service: Arc<PlayerService>,
     lobby_service: Arc<LobbyService>,
+    game_service: Arc<GameService>,
 }
 
 impl QuitController {
-    pub fn new(player_service: Arc<PlayerService>, lobby_service: Arc<LobbyService>) -> Self {
+    pub fn new(
+        player_service: Arc<PlayerService>,
+        lobby_service: Arc<LobbyService>,
+        game_service: Arc<GameService>,
+    ) -> Self {
         Self {
             player_service,
             lobby_service,
+            game_service,
         }
     }
 }
 
 impl PrintableController for QuitController {}
 
+#[async_trait]
 impl Controller for QuitController {
-    fn handle_request(
+    async fn handle_request(
         &self,
         req: Request,
         context: RequestContext,
-    ) -> Result<ResponseData, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<ResponseData, ServiceError> {
         match *req.get_data() {
             RequestData::QuitLobby => req,
             _ => panic!("invalid request"),
         };
         let player = match self.player_service.get_player(context.client_id) {
             Some(player) => player,
-            None => return Err("Player not found".into()),
+            None => return Err(ServiceError::PlayerNotFound),
         };
-        self.lobby_service.remove_player_from_lobby(player)?;
+        let lobby = player.get_lobby();
+        self.lobby_service.remove_player_from_lobby(player).await?;
+        // the player may have been the last one not ready
+        if let Some(lobby) = lobby {
+            LobbyService::schedule_auto_start(
+                self.lobby_service.clone(),
+                self.game_service.clone(),
+                lobby,
+            )
+            .await;
+        }
         Ok(ResponseData::QuitLobby(QuitResponse { success: true }))
     }
 }
@@ -6,30 +6,45 @@ use crate::{
     controller::controller::PrintableController,
     frame::{RequestData, ResponseData},
     router::RequestContext,
-    service::player_service::PlayerService,
+    service::{
+        game_service::GameService, lobby_service::LobbyService, player_service::PlayerService,
+    },
 };
 
 use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
 
 #[derive(Debug, Clone)]
 pub struct ReadyController {
     player_service: Arc<PlayerService>,
+    lobby_service: Arc<LobbyService>,
+    game_service: Arc<GameService>,
 }
 
 impl ReadyController {
-    pub fn new(player_service: Arc<PlayerService>) -> Self {
-        Self { player_service }
+    pub fn new(
+        player_service: Arc<PlayerService>,
+        lobby_service: Arc<LobbyService>,
+        game_service: Arc<GameService>,
+    ) -> Self {
+        Self {
+            player_service,
+            lobby_service,
+            game_service,
+        }
     }
 }
 
 impl PrintableController for ReadyController {}
 
+#[async_trait]
 impl Controller for ReadyController {
-    fn handle_request(
+    async fn handle_request(
         &self,
         req: Request,
         context: RequestContext,
-    ) -> Result<ResponseData, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<ResponseData, ServiceError> {
         match *req.get_data() {
             RequestData::Ready => req,
             _ => panic!("invalid request"),
@@ -37,21 +52,20 @@ impl Controller for ReadyController {
 
         let player = match self.player_service.get_player(context.client_id) {
             Some(player) => player,
-            None => return Err("Player not found".into()),
+            None => return Err(ServiceError::PlayerNotFound),
         };
 
-        match player.get_lobby() {
-            Some(lobby) => match lobby.get_player(player.id) {
-                Some(lobby_player) => {
-                    {
-                        lobby_player.set_ready(!lobby_player.get_ready());
-                    }
-                    Ok(ResponseData::Ready(ReadyResponse { success: true }))
-                }
-                None => panic!("Player in lobby but LobbyPlayer not found"),
-            },
-            None => Err("Player not in lobby".into()),
-        }
+        let lobby = self.lobby_service.toggle_ready(player).await?;
+        LobbyService::schedule_auto_start(
+            self.lobby_service.clone(),
+            self.game_service.clone(),
+            lobby.clone(),
+        )
+        .await;
+        Ok(ResponseData::Ready(ReadyResponse {
+            success: true,
+            lobby: Some(crate::model::lobby::lobby::Lobby::from(lobby)),
+        }))
     }
 }
 
@@ -59,48 +73,167 @@ impl Controller for ReadyController {
 mod tests {
     use std::{collections::HashSet, error::Error};
 
-    use crate::service::{game_service::GameService, lobby_service::LobbyService};
+    use crate::lobby::lobby_settings::LobbySettings;
 
     use super::*;
 
-    #[test]
-    fn handle_request_with_test_user_in_test_lobby_should_ready(
+    #[tokio::test]
+    async fn handle_request_with_test_user_in_test_lobby_should_ready(
     ) -> Result<(), Box<dyn Error + Sync + Send>> {
-        let controller = ReadyController::new(Arc::new(PlayerService::new(
-            Arc::new(LobbyService::new()),
-            Arc::new(GameService::new(HashSet::new())),
-        )));
-        let lobby_service = LobbyService::new();
+        let lobby_service = Arc::new(LobbyService::new());
+        let game_service = Arc::new(GameService::new(HashSet::new()));
+        let controller = ReadyController::new(
+            Arc::new(PlayerService::new(
+                lobby_service.clone(),
+                game_service.clone(),
+            )),
+            lobby_service.clone(),
+            game_service,
+        );
         let leader = controller
             .player_service
             .add_player(0, String::from("test"));
-        let lobby = lobby_service.create_lobby(leader, 4)?;
-        controller.handle_request(
-            Request::new(0, Arc::new(RequestData::Ready)),
-            RequestContext { client_id: 0 },
-        )?;
+        let lobby = lobby_service.create_lobby(leader, 4).await?;
+        controller
+            .handle_request(
+                Request::new(0, Arc::new(RequestData::Ready)),
+                RequestContext { client_id: 0 },
+            )
+            .await?;
         assert!(lobby.get_player(0).unwrap().get_ready());
         Ok(())
     }
 
-    #[test]
-    fn handle_request_with_test_user_in_test_lobby_should_not_ready(
+    #[tokio::test]
+    async fn handle_request_with_test_user_in_test_lobby_should_not_ready(
     ) -> Result<(), Box<dyn Error + Sync + Send>> {
-        let controller = ReadyController::new(Arc::new(PlayerService::new(
-            Arc::new(LobbyService::new()),
-            Arc::new(GameService::new(HashSet::new())),
-        )));
-        let lobby_service = LobbyService::new();
+        let lobby_service = Arc::new(LobbyService::new());
+        let game_service = Arc::new(GameService::new(HashSet::new()));
+        let controller = ReadyController::new(
+            Arc::new(PlayerService::new(
+                lobby_service.clone(),
+                game_service.clone(),
+            )),
+            lobby_service.clone(),
+            game_service,
+        );
         let leader = controller
             .player_service
             .add_player(0, String::from("test"));
-        let lobby = lobby_service.create_lobby(leader, 4)?;
+        let lobby = lobby_service.create_lobby(leader, 4).await?;
         lobby.get_player(0).unwrap().set_ready(true);
-        controller.handle_request(
-            Request::new(0, Arc::new(RequestData::Ready)),
-            RequestContext { client_id: 0 },
-        )?;
+        controller
+            .handle_request(
+                Request::new(0, Arc::new(RequestData::Ready)),
+                RequestContext { client_id: 0 },
+            )
+            .await?;
         assert!(!lobby.get_player(0).unwrap().get_ready());
         Ok(())
     }
+
+    #[tokio::test]
+    async fn handle_request_with_test_user_should_return_lobby_with_ready_state(
+    ) -> Result<(), Box<dyn Error + Sync + Send>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let game_service = Arc::new(GameService::new(HashSet::new()));
+        let controller = ReadyController::new(
+            Arc::new(PlayerService::new(
+                lobby_service.clone(),
+                game_service.clone(),
+            )),
+            lobby_service.clone(),
+            game_service,
+        );
+        let leader = controller
+            .player_service
+            .add_player(0, String::from("test"));
+        lobby_service.create_lobby(leader, 4).await?;
+        let res = match controller
+            .handle_request(
+                Request::new(0, Arc::new(RequestData::Ready)),
+                RequestContext { client_id: 0 },
+            )
+            .await?
+        {
+            ResponseData::Ready(res) => res,
+            _ => panic!("invalid response"),
+        };
+        let lobby = res.lobby.unwrap();
+        assert!(lobby.players[0].ready);
+        assert!(lobby.players[0].leader);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_every_seat_ready_and_auto_start_should_start_countdown(
+    ) -> Result<(), Box<dyn Error + Sync + Send>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let game_service = Arc::new(GameService::new(HashSet::new()));
+        let controller = ReadyController::new(
+            Arc::new(PlayerService::new(
+                lobby_service.clone(),
+                game_service.clone(),
+            )),
+            lobby_service.clone(),
+            game_service,
+        );
+        let leader = controller
+            .player_service
+            .add_player(0, String::from("test1"));
+        let player = controller
+            .player_service
+            .add_player(1, String::from("test2"));
+        let lobby = lobby_service.create_lobby(leader, 4).await?;
+        lobby_service
+            .add_player_to_lobby(player, lobby.clone())
+            .await?;
+        lobby.set_settings(LobbySettings {
+            auto_start: true,
+            ..lobby.get_settings()
+        });
+        lobby.get_player(0).unwrap().set_ready(true);
+        controller
+            .handle_request(
+                Request::new(1, Arc::new(RequestData::Ready)),
+                RequestContext { client_id: 1 },
+            )
+            .await?;
+        assert!(lobby.has_auto_start_task());
+        controller
+            .handle_request(
+                Request::new(1, Arc::new(RequestData::Ready)),
+                RequestContext { client_id: 1 },
+            )
+            .await?;
+        assert!(!lobby.has_auto_start_task());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_without_auto_start_should_not_start_countdown(
+    ) -> Result<(), Box<dyn Error + Sync + Send>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let game_service = Arc::new(GameService::new(HashSet::new()));
+        let controller = ReadyController::new(
+            Arc::new(PlayerService::new(
+                lobby_service.clone(),
+                game_service.clone(),
+            )),
+            lobby_service.clone(),
+            game_service,
+        );
+        let leader = controller
+            .player_service
+            .add_player(0, String::from("test"));
+        let lobby = lobby_service.create_lobby(leader, 4).await?;
+        controller
+            .handle_request(
+                Request::new(0, Arc::new(RequestData::Ready)),
+                RequestContext { client_id: 0 },
+            )
+            .await?;
+        assert!(!lobby.has_auto_start_task());
+        Ok(())
+    }
 }
@@ -0,0 +1,159 @@
+use std::sync::Arc;
+
+use crate::frame::Request;
+use crate::model::lobby::rematch::RematchResponse;
+use crate::{
+    controller::controller::PrintableController,
+    frame::{RequestData, ResponseData},
+    router::RequestContext,
+    service::{
+        game_service::GameService, lobby_service::LobbyService, player_service::PlayerService,
+    },
+};
+
+use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
+
+#[derive(Debug, Clone)]
+pub struct RematchController {
+    player_service: Arc<PlayerService>,
+    lobby_service: Arc<LobbyService>,
+    game_service: Arc<GameService>,
+}
+
+impl RematchController {
+    pub fn new(
+        player_service: Arc<PlayerService>,
+        lobby_service: Arc<LobbyService>,
+        game_service: Arc<GameService>,
+    ) -> Self {
+        Self {
+            player_service,
+            lobby_service,
+            game_service,
+        }
+    }
+}
+
+impl PrintableController for RematchController {}
+
+#[async_trait]
+impl Controller for RematchController {
+    async fn handle_request(
+        &self,
+        req: Request,
+        context: RequestContext,
+    ) -> Result<ResponseData, ServiceError> {
+        let data = req.get_data();
+        let req = match data.as_ref() {
+            RequestData::Rematch(req) => req,
+            _ => panic!("invalid request"),
+        };
+        let player = match self.player_service.get_player(context.client_id) {
+            Some(player) => player,
+            None => return Err(ServiceError::PlayerNotFound),
+        };
+        let lobby = player.get_lobby();
+        let passed = self
+            .lobby_service
+            .vote_rematch(player.clone(), req.accept)
+            .await?;
+        // the lobby may auto-start once the vote is over
+        if let Some(lobby) = lobby {
+            LobbyService::schedule_auto_start(
+                self.lobby_service.clone(),
+                self.game_service.clone(),
+                lobby,
+            )
+            .await;
+        }
+        Ok(ResponseData::Rematch(RematchResponse {
+            success: true,
+            lobby: player
+                .get_lobby()
+                .map(crate::model::lobby::lobby::Lobby::from),
+            passed,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::lobby::rematch::RematchRequest;
+    use std::{collections::HashSet, error::Error};
+
+    #[tokio::test]
+    async fn handle_request_with_only_player_accepted_should_pass_vote(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let player_service = Arc::new(PlayerService::new(
+            lobby_service.clone(),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        let leader = player_service.add_player(0, String::from("test"));
+        let lobby = lobby_service.create_lobby(leader, 4).await?;
+        lobby.open_rematch_vote();
+        let controller = RematchController::new(
+            player_service,
+            lobby_service,
+            Arc::new(GameService::new(HashSet::new())),
+        );
+        let res = match controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::Rematch(RematchRequest { accept: true })),
+                ),
+                RequestContext { client_id: 0 },
+            )
+            .await?
+        {
+            ResponseData::Rematch(res) => res,
+            _ => panic!("invalid response"),
+        };
+        assert!(res.passed);
+        assert!(!res.lobby.unwrap().rematch_vote_open);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_declined_should_leave_lobby(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let player_service = Arc::new(PlayerService::new(
+            lobby_service.clone(),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        let leader = player_service.add_player(0, String::from("test1"));
+        let player = player_service.add_player(1, String::from("test2"));
+        let lobby = lobby_service.create_lobby(leader, 4).await?;
+        lobby_service
+            .add_player_to_lobby(player.clone(), lobby.clone())
+            .await?;
+        lobby.open_rematch_vote();
+        let controller = RematchController::new(
+            player_service,
+            lobby_service,
+            Arc::new(GameService::new(HashSet::new())),
+        );
+        let res = match controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::Rematch(RematchRequest { accept: false })),
+                ),
+                RequestContext { client_id: 1 },
+            )
+            .await?
+        {
+            ResponseData::Rematch(res) => res,
+            _ => panic!("invalid response"),
+        };
+        assert!(!res.passed);
+        assert!(res.lobby.is_none());
+        assert!(player.get_lobby().is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,340 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::frame::Request;
+use crate::lobby::{lobby::Lobby, lobby_settings::LobbySettings};
+use crate::model::lobby::update::UpdateResponse;
+use crate::{
+    controller::controller::PrintableController,
+    frame::{RequestData, ResponseData},
+    router::RequestContext,
+    service::{
+        game_service::GameService, lobby_service::LobbyService, player_service::PlayerService,
+    },
+};
+
+use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
+
+#[derive(Debug, Clone)]
+pub struct UpdateController {
+    player_service: Arc<PlayerService>,
+    lobby_service: Arc<LobbyService>,
+    game_service: Arc<GameService>,
+}
+
+impl UpdateController {
+    pub fn new(
+        player_service: Arc<PlayerService>,
+        lobby_service: Arc<LobbyService>,
+        game_service: Arc<GameService>,
+    ) -> Self {
+        Self {
+            player_service,
+            lobby_service,
+            game_service,
+        }
+    }
+}
+
+impl PrintableController for UpdateController {}
+
+#[async_trait]
+impl Controller for UpdateController {
+    async fn handle_request(
+        &self,
+        req: Request,
+        context: RequestContext,
+    ) -> Result<ResponseData, ServiceError> {
+        let data = req.get_data();
+        let req = match data.as_ref() {
+            RequestData::UpdateLobby(req) => req,
+            _ => panic!("invalid request"),
+        };
+        let player = match self.player_service.get_player(context.client_id) {
+            Some(player) => player,
+            None => return Err(ServiceError::PlayerNotFound),
+        };
+        let current_settings = match player.get_lobby() {
+            Some(lobby) => lobby.get_settings(),
+            None => return Err(ServiceError::NotInLobby),
+        };
+        // fields left out of the request keep their current value
+        let settings = LobbySettings {
+            name: match &req.name {
+                Some(name) if name.trim().is_empty() => Lobby::default_name(&player),
+                Some(name) => name.clone(),
+                None => current_settings.name,
+            },
+            private: req.is_private.unwrap_or(current_settings.private),
+            // an empty password removes it
+            password: match &req.password {
+                Some(password) if password.is_empty() => None,
+                Some(password) => Some(password.clone()),
+                None => current_settings.password,
+            },
+            language: req.language.clone().unwrap_or(current_settings.language),
+            dictionary: req
+                .dictionary
+                .clone()
+                .unwrap_or(current_settings.dictionary),
+            allow_spectators: req
+                .allow_spectators
+                .unwrap_or(current_settings.allow_spectators),
+            spectator_delay: req
+                .spectator_delay
+                .map(|spectator_delay| Duration::from_secs(spectator_delay as u64))
+                .unwrap_or(current_settings.spectator_delay),
+            auto_start: req.auto_start.unwrap_or(current_settings.auto_start),
+            auto_start_min_players: req
+                .auto_start_min_players
+                .unwrap_or(current_settings.auto_start_min_players),
+            afk_turns: req.afk_turns.unwrap_or(current_settings.afk_turns),
+            afk_removal_turns: req
+                .afk_removal_turns
+                .unwrap_or(current_settings.afk_removal_turns),
+        };
+        let lobby = self
+            .lobby_service
+            .update_lobby_settings(player, settings)
+            .await?;
+        // the new settings may let the lobby auto-start
+        LobbyService::schedule_auto_start(
+            self.lobby_service.clone(),
+            self.game_service.clone(),
+            lobby.clone(),
+        )
+        .await;
+        Ok(ResponseData::UpdateLobby(UpdateResponse {
+            success: true,
+            lobby: Some(crate::model::lobby::lobby::Lobby::from(lobby)),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::lobby::update::UpdateRequest;
+    use std::{collections::HashSet, error::Error};
+
+    #[tokio::test]
+    async fn handle_request_with_leader_should_update_lobby(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let player_service = Arc::new(PlayerService::new(
+            lobby_service.clone(),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        let leader = player_service.add_player(0, String::from("test"));
+        let lobby = lobby_service.create_lobby(leader, 4).await?;
+        let controller = UpdateController::new(
+            player_service,
+            lobby_service,
+            Arc::new(GameService::new(HashSet::new())),
+        );
+        let res = match controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::UpdateLobby(UpdateRequest {
+                        name: Some(String::from("room")),
+                        is_private: Some(true),
+                        password: Some(String::from("1234")),
+                        language: None,
+                        dictionary: None,
+                        allow_spectators: None,
+                        spectator_delay: None,
+                        auto_start: None,
+                        auto_start_min_players: None,
+                        afk_turns: None,
+                        afk_removal_turns: None,
+                    })),
+                ),
+                RequestContext { client_id: 0 },
+            )
+            .await?
+        {
+            ResponseData::UpdateLobby(res) => res,
+            _ => panic!("invalid response"),
+        };
+        assert!(res.success);
+        assert_eq!(res.lobby.unwrap().name, "room");
+        assert!(lobby.is_private());
+        assert!(lobby.check_password(Some("1234")));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_not_leader_should_return_error(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let player_service = Arc::new(PlayerService::new(
+            lobby_service.clone(),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        let leader = player_service.add_player(0, String::from("test1"));
+        let player = player_service.add_player(1, String::from("test2"));
+        let lobby = lobby_service.create_lobby(leader, 4).await?;
+        lobby_service.add_player_to_lobby(player, lobby).await?;
+        let controller = UpdateController::new(
+            player_service,
+            lobby_service,
+            Arc::new(GameService::new(HashSet::new())),
+        );
+        assert!(controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::UpdateLobby(UpdateRequest {
+                        name: Some(String::from("room")),
+                        is_private: Some(true),
+                        password: None,
+                        language: None,
+                        dictionary: None,
+                        allow_spectators: None,
+                        spectator_delay: None,
+                        auto_start: None,
+                        auto_start_min_players: None,
+                        afk_turns: None,
+                        afk_removal_turns: None,
+                    }))
+                ),
+                RequestContext { client_id: 1 },
+            )
+            .await
+            .is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_player_not_in_lobby_should_return_error(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let player_service = Arc::new(PlayerService::new(
+            lobby_service.clone(),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        player_service.add_player(0, String::from("test"));
+        let controller = UpdateController::new(
+            player_service,
+            lobby_service,
+            Arc::new(GameService::new(HashSet::new())),
+        );
+        assert!(controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::UpdateLobby(UpdateRequest {
+                        name: Some(String::from("room")),
+                        is_private: Some(false),
+                        password: None,
+                        language: None,
+                        dictionary: None,
+                        allow_spectators: None,
+                        spectator_delay: None,
+                        auto_start: None,
+                        auto_start_min_players: None,
+                        afk_turns: None,
+                        afk_removal_turns: None,
+                    }))
+                ),
+                RequestContext { client_id: 0 },
+            )
+            .await
+            .is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_auto_start_and_all_ready_should_start_countdown(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let game_service = Arc::new(GameService::new(HashSet::new()));
+        let player_service = Arc::new(PlayerService::new(
+            lobby_service.clone(),
+            game_service.clone(),
+        ));
+        let leader = player_service.add_player(0, String::from("test1"));
+        let player = player_service.add_player(1, String::from("test2"));
+        let lobby = lobby_service.create_lobby(leader, 4).await?;
+        lobby_service
+            .add_player_to_lobby(player, lobby.clone())
+            .await?;
+        for lobby_player in lobby.get_players() {
+            lobby_player.set_ready(true);
+        }
+        let controller = UpdateController::new(player_service, lobby_service, game_service);
+        controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::UpdateLobby(UpdateRequest {
+                        name: None,
+                        is_private: None,
+                        password: None,
+                        language: None,
+                        dictionary: None,
+                        allow_spectators: None,
+                        spectator_delay: None,
+                        auto_start: Some(true),
+                        auto_start_min_players: None,
+                        afk_turns: None,
+                        afk_removal_turns: None,
+                    })),
+                ),
+                RequestContext { client_id: 0 },
+            )
+            .await?;
+        assert!(lobby.has_auto_start_task());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_one_field_should_keep_other_settings(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let game_service = Arc::new(GameService::new(HashSet::new()));
+        let player_service = Arc::new(PlayerService::new(
+            lobby_service.clone(),
+            game_service.clone(),
+        ));
+        let leader = player_service.add_player(0, String::from("test"));
+        let lobby = lobby_service
+            .create_lobby_with_settings(
+                leader,
+                4,
+                LobbySettings::new(String::from("room"), true, Some(String::from("1234"))),
+            )
+            .await?;
+        let controller = UpdateController::new(player_service, lobby_service, game_service);
+        controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::UpdateLobby(UpdateRequest {
+                        name: None,
+                        is_private: None,
+                        password: None,
+                        language: None,
+                        dictionary: None,
+                        allow_spectators: None,
+                        spectator_delay: None,
+                        auto_start: None,
+                        auto_start_min_players: None,
+                        afk_turns: Some(3),
+                        afk_removal_turns: None,
+                    })),
+                ),
+                RequestContext { client_id: 0 },
+            )
+            .await?;
+        let settings = lobby.get_settings();
+        assert_eq!(settings.afk_turns, 3);
+        assert_eq!(settings.name, "room");
+        assert!(settings.private);
+        assert_eq!(settings.password, Some(String::from("1234")));
+        Ok(())
+    }
+}
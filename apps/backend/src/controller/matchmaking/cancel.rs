@@ -0,0 +1,114 @@
+use std::sync::Arc;
+
+use crate::frame::Request;
+use crate::model::matchmaking::cancel::CancelQuickPlayResponse;
+use crate::{
+    controller::controller::PrintableController,
+    frame::{RequestData, ResponseData},
+    router::RequestContext,
+    service::{matchmaking_service::MatchmakingService, player_service::PlayerService},
+};
+
+use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
+
+#[derive(Debug, Clone)]
+pub struct CancelQuickPlayController {
+    player_service: Arc<PlayerService>,
+    matchmaking_service: Arc<MatchmakingService>,
+}
+
+impl CancelQuickPlayController {
+    pub fn new(
+        player_service: Arc<PlayerService>,
+        matchmaking_service: Arc<MatchmakingService>,
+    ) -> Self {
+        Self {
+            player_service,
+            matchmaking_service,
+        }
+    }
+}
+
+impl PrintableController for CancelQuickPlayController {}
+
+#[async_trait]
+impl Controller for CancelQuickPlayController {
+    async fn handle_request(
+        &self,
+        req: Request,
+        context: RequestContext,
+    ) -> Result<ResponseData, ServiceError> {
+        match *req.get_data() {
+            RequestData::CancelQuickPlay => req,
+            _ => panic!("invalid request"),
+        };
+        let player = match self.player_service.get_player(context.client_id) {
+            Some(player) => player,
+            None => return Err(ServiceError::PlayerNotFound),
+        };
+        self.matchmaking_service.leave_queue(&player)?;
+        Ok(ResponseData::CancelQuickPlay(CancelQuickPlayResponse {
+            success: true,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::{game_service::GameService, lobby_service::LobbyService};
+    use std::{collections::HashSet, error::Error};
+
+    #[tokio::test]
+    async fn handle_request_with_queued_player_should_leave_queue(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let player_service = Arc::new(PlayerService::new(
+            lobby_service.clone(),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        let player = player_service.add_player(0, String::from("test"));
+        let matchmaking_service = Arc::new(MatchmakingService::new(
+            player_service.clone(),
+            lobby_service,
+        ));
+        matchmaking_service
+            .join_queue(player.clone(), 4, String::from("en"))
+            .await?;
+        let controller =
+            CancelQuickPlayController::new(player_service, matchmaking_service.clone());
+        controller
+            .handle_request(
+                Request::new(0, Arc::new(RequestData::CancelQuickPlay)),
+                RequestContext { client_id: 0 },
+            )
+            .await?;
+        assert!(!matchmaking_service.is_queued(&player));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_player_not_in_queue_should_return_error(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let player_service = Arc::new(PlayerService::new(
+            lobby_service.clone(),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        player_service.add_player(0, String::from("test"));
+        let controller = CancelQuickPlayController::new(
+            player_service.clone(),
+            Arc::new(MatchmakingService::new(player_service, lobby_service)),
+        );
+        assert!(controller
+            .handle_request(
+                Request::new(0, Arc::new(RequestData::CancelQuickPlay)),
+                RequestContext { client_id: 0 },
+            )
+            .await
+            .is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,160 @@
+use std::sync::Arc;
+
+use crate::frame::Request;
+use crate::lobby::lobby_settings::DEFAULT_LOBBY_LANGUAGE;
+use crate::matchmaking::matchmaking_result::MatchmakingResult;
+use crate::model::matchmaking::quick_play::QuickPlayResponse;
+use crate::{
+    controller::controller::PrintableController,
+    frame::{RequestData, ResponseData},
+    router::RequestContext,
+    service::{matchmaking_service::MatchmakingService, player_service::PlayerService},
+};
+
+use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
+
+#[derive(Debug, Clone)]
+pub struct QuickPlayController {
+    player_service: Arc<PlayerService>,
+    matchmaking_service: Arc<MatchmakingService>,
+}
+
+impl QuickPlayController {
+    pub fn new(
+        player_service: Arc<PlayerService>,
+        matchmaking_service: Arc<MatchmakingService>,
+    ) -> Self {
+        Self {
+            player_service,
+            matchmaking_service,
+        }
+    }
+}
+
+impl PrintableController for QuickPlayController {}
+
+#[async_trait]
+impl Controller for QuickPlayController {
+    async fn handle_request(
+        &self,
+        req: Request,
+        context: RequestContext,
+    ) -> Result<ResponseData, ServiceError> {
+        let data = req.get_data();
+        let req = match data.as_ref() {
+            RequestData::QuickPlay(req) => req,
+            _ => panic!("invalid request"),
+        };
+        let player = match self.player_service.get_player(context.client_id) {
+            Some(player) => player,
+            None => return Err(ServiceError::PlayerNotFound),
+        };
+        let language = req
+            .language
+            .clone()
+            .filter(|language| !language.trim().is_empty())
+            .unwrap_or_else(|| String::from(DEFAULT_LOBBY_LANGUAGE));
+        let rating_band = player.get_rating_band();
+        Ok(ResponseData::QuickPlay(
+            match self
+                .matchmaking_service
+                .join_queue(player, req.max_players, language)
+                .await?
+            {
+                MatchmakingResult::Queued {
+                    estimated_wait,
+                    queue_size,
+                } => QuickPlayResponse {
+                    success: true,
+                    lobby: None,
+                    estimated_wait: estimated_wait.as_secs() as u32,
+                    queue_size,
+                    rating_band,
+                },
+                MatchmakingResult::Matched(lobby) => QuickPlayResponse {
+                    success: true,
+                    lobby: Some(crate::model::lobby::lobby::Lobby::from(lobby)),
+                    estimated_wait: 0,
+                    queue_size: 0,
+                    rating_band,
+                },
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        model::matchmaking::quick_play::QuickPlayRequest,
+        service::{game_service::GameService, lobby_service::LobbyService},
+    };
+    use std::{collections::HashSet, error::Error};
+
+    #[tokio::test]
+    async fn handle_request_with_test_user_should_queue_player(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let player_service = Arc::new(PlayerService::new(
+            lobby_service.clone(),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        player_service.add_player(0, String::from("test"));
+        let controller = QuickPlayController::new(
+            player_service.clone(),
+            Arc::new(MatchmakingService::new(player_service, lobby_service)),
+        );
+        let res = match controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::QuickPlay(QuickPlayRequest {
+                        max_players: 4,
+                        language: None,
+                    })),
+                ),
+                RequestContext { client_id: 0 },
+            )
+            .await?
+        {
+            ResponseData::QuickPlay(res) => res,
+            _ => panic!("invalid response"),
+        };
+        assert!(res.success);
+        assert!(res.lobby.is_none());
+        assert_eq!(res.queue_size, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_invalid_max_players_should_return_error(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let player_service = Arc::new(PlayerService::new(
+            lobby_service.clone(),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        player_service.add_player(0, String::from("test"));
+        let controller = QuickPlayController::new(
+            player_service.clone(),
+            Arc::new(MatchmakingService::new(player_service, lobby_service)),
+        );
+        assert!(controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::QuickPlay(QuickPlayRequest {
+                        max_players: 2,
+                        language: None,
+                    }))
+                ),
+                RequestContext { client_id: 0 },
+            )
+            .await
+            .is_err());
+        Ok(())
+    }
+}
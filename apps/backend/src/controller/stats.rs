@@ -0,0 +1,3 @@
+pub mod history;
+pub mod leaderboard;
+pub mod profile;
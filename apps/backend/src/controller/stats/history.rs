@@ -0,0 +1,117 @@
+use std::sync::Arc;
+
+use crate::{
+    controller::controller::PrintableController,
+    frame::{Request, RequestData, ResponseData},
+    model::stats::{history::HistoryResponse, match_record::MatchRecord},
+    router::RequestContext,
+    service::{blocking::run_blocking, player_service::PlayerService, stats_service::StatsService},
+};
+
+use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
+
+#[derive(Debug, Clone)]
+pub struct HistoryController {
+    player_service: Arc<PlayerService>,
+    stats_service: Arc<StatsService>,
+}
+
+impl HistoryController {
+    pub fn new(player_service: Arc<PlayerService>, stats_service: Arc<StatsService>) -> Self {
+        Self {
+            player_service,
+            stats_service,
+        }
+    }
+}
+
+impl PrintableController for HistoryController {}
+
+#[async_trait]
+impl Controller for HistoryController {
+    async fn handle_request(
+        &self,
+        req: Request,
+        context: RequestContext,
+    ) -> Result<ResponseData, ServiceError> {
+        let data = req.get_data();
+        let req = match data.as_ref() {
+            RequestData::GetMatchHistory(req) => req,
+            _ => panic!("invalid request"),
+        };
+        let player = match self.player_service.get_player(context.client_id) {
+            Some(player) => player,
+            None => return Err(ServiceError::PlayerNotFound),
+        };
+        let account_id = match req.account_id.or(player.get_account_id()) {
+            Some(account_id) => account_id,
+            None => return Err(ServiceError::NoAccount),
+        };
+        let stats_service = self.stats_service.clone();
+        let limit = req.limit;
+        let matches =
+            run_blocking(move || stats_service.get_match_history(account_id, limit)).await?;
+        Ok(ResponseData::GetMatchHistory(HistoryResponse {
+            success: true,
+            matches: matches.iter().map(MatchRecord::from).collect(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        account::account::Account,
+        game::game::Game,
+        model::stats::history::HistoryRequest,
+        service::{game_service::GameService, lobby_service::LobbyService},
+        stats::{match_record::MatchRecord, match_repository::MatchRepository},
+    };
+    use std::{collections::HashSet, error::Error};
+
+    #[tokio::test]
+    async fn handle_request_with_limit_should_return_latest_matches(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let controller = HistoryController::new(
+            Arc::new(PlayerService::new(
+                Arc::new(LobbyService::new()),
+                Arc::new(GameService::new(HashSet::new())),
+            )),
+            Arc::new(StatsService::new(MatchRepository::open_in_memory()?)),
+        );
+        let player = controller
+            .player_service
+            .add_player(0, String::from("test"));
+        player.set_account(Some(Account::new(1, String::from("test"))));
+        for id in 0..3 {
+            StatsService::record_result(
+                controller.stats_service.clone(),
+                MatchRecord::from_game(&Game::new(id, vec![player.clone()])),
+                Vec::new(),
+            )
+            .await?;
+        }
+        let res = match controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::GetMatchHistory(HistoryRequest {
+                        account_id: None,
+                        limit: 2,
+                    })),
+                ),
+                RequestContext { client_id: 0 },
+            )
+            .await?
+        {
+            ResponseData::GetMatchHistory(res) => res,
+            _ => panic!("invalid response"),
+        };
+        assert_eq!(res.matches.len(), 2);
+        assert_eq!(res.matches[0].players[0].name, "test");
+        Ok(())
+    }
+}
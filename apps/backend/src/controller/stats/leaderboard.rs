@@ -0,0 +1,148 @@
+use std::sync::Arc;
+
+use crate::{
+    controller::controller::PrintableController,
+    frame::{Request, RequestData, ResponseData},
+    model::stats::leaderboard::{LeaderboardEntry, LeaderboardResponse},
+    router::RequestContext,
+    service::{
+        blocking::run_blocking,
+        player_service::PlayerService,
+        rating_service::{RatingService, DEFAULT_LEADERBOARD_PAGE_SIZE, MAX_LEADERBOARD_PAGE_SIZE},
+    },
+};
+
+use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
+
+#[derive(Debug, Clone)]
+pub struct LeaderboardController {
+    player_service: Arc<PlayerService>,
+    rating_service: Arc<RatingService>,
+}
+
+impl LeaderboardController {
+    pub fn new(player_service: Arc<PlayerService>, rating_service: Arc<RatingService>) -> Self {
+        Self {
+            player_service,
+            rating_service,
+        }
+    }
+}
+
+impl PrintableController for LeaderboardController {}
+
+#[async_trait]
+impl Controller for LeaderboardController {
+    async fn handle_request(
+        &self,
+        req: Request,
+        context: RequestContext,
+    ) -> Result<ResponseData, ServiceError> {
+        let data = req.get_data();
+        let req = match data.as_ref() {
+            RequestData::GetLeaderboard(req) => req,
+            _ => panic!("invalid request"),
+        };
+        let player = match self.player_service.get_player(context.client_id) {
+            Some(player) => player,
+            None => return Err(ServiceError::PlayerNotFound),
+        };
+        let page_size = match req.page_size {
+            0 => DEFAULT_LEADERBOARD_PAGE_SIZE,
+            page_size => page_size.min(MAX_LEADERBOARD_PAGE_SIZE),
+        };
+        let rating_service = self.rating_service.clone();
+        let (page, account_id) = (req.page, player.get_account_id());
+        let ((entries, total), own_entry) = run_blocking(move || {
+            Ok((
+                rating_service.get_leaderboard_page(page, page_size)?,
+                match account_id {
+                    Some(account_id) => rating_service.get_leaderboard_entry(account_id)?,
+                    None => None,
+                },
+            ))
+        })
+        .await?;
+        Ok(ResponseData::GetLeaderboard(LeaderboardResponse {
+            success: true,
+            entries: entries.iter().map(LeaderboardEntry::from).collect(),
+            page: req.page,
+            page_size,
+            total,
+            own_entry: own_entry.as_ref().map(LeaderboardEntry::from),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        account::account::Account,
+        game::game::Game,
+        model::stats::leaderboard::LeaderboardRequest,
+        player::Player,
+        service::{
+            game_service::GameService, lobby_service::LobbyService, stats_service::StatsService,
+        },
+        stats::{
+            match_record::MatchRecord, match_repository::MatchRepository,
+            rating_repository::RatingRepository,
+        },
+    };
+    use std::{collections::HashSet, error::Error};
+
+    #[tokio::test]
+    async fn handle_request_with_rated_player_should_return_page_and_own_rank(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        // games are stored in the database the ratings are read from, like the server does
+        let database = "file:leaderboard_controller?mode=memory&cache=shared";
+        let stats_service = Arc::new(StatsService::new(MatchRepository::open(database)?));
+        let controller = LeaderboardController::new(
+            Arc::new(PlayerService::new(
+                Arc::new(LobbyService::new()),
+                Arc::new(GameService::new(HashSet::new())),
+            )),
+            Arc::new(RatingService::new(RatingRepository::open(database)?)),
+        );
+        let player = controller
+            .player_service
+            .add_player(0, String::from("test1"));
+        player.set_account(Some(Account::new(1, String::from("test1"))));
+        let opponent = Arc::new(Player::new(1, String::from("test2")));
+        opponent.set_account(Some(Account::new(2, String::from("test2"))));
+        let game = Game::new(0, vec![player, opponent]);
+        game.get_player(1)
+            .unwrap()
+            .record_words(&[String::from("apple")]);
+        StatsService::record_result(
+            stats_service,
+            MatchRecord::from_game(&game),
+            RatingService::rate_game(&game),
+        )
+        .await?;
+        let res = match controller
+            .handle_request(
+                Request::new(
+                    0,
+                    Arc::new(RequestData::GetLeaderboard(LeaderboardRequest {
+                        page: 0,
+                        page_size: 1,
+                    })),
+                ),
+                RequestContext { client_id: 0 },
+            )
+            .await?
+        {
+            ResponseData::GetLeaderboard(res) => res,
+            _ => panic!("invalid response"),
+        };
+        assert_eq!(res.total, 2);
+        assert_eq!(res.entries.len(), 1);
+        assert_eq!(res.entries[0].username, "test2");
+        assert_eq!(res.own_entry.unwrap().rank, 2);
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+use std::sync::Arc;
+
+use crate::{
+    controller::controller::PrintableController,
+    frame::{Request, RequestData, ResponseData},
+    model::stats::{player_stats::PlayerStats, profile::ProfileResponse},
+    router::RequestContext,
+    service::{blocking::run_blocking, player_service::PlayerService, stats_service::StatsService},
+};
+
+use crate::controller::controller::Controller;
+use crate::error::ServiceError;
+use async_trait::async_trait;
+
+#[derive(Debug, Clone)]
+pub struct ProfileController {
+    player_service: Arc<PlayerService>,
+    stats_service: Arc<StatsService>,
+}
+
+impl ProfileController {
+    pub fn new(player_service: Arc<PlayerService>, stats_service: Arc<StatsService>) -> Self {
+        Self {
+            player_service,
+            stats_service,
+        }
+    }
+}
+
+impl PrintableController for ProfileController {}
+
+#[async_trait]
+impl Controller for ProfileController {
+    async fn handle_request(
+        &self,
+        req: Request,
+        context: RequestContext,
+    ) -> Result<ResponseData, ServiceError> {
+        let data = req.get_data();
+        let req = match data.as_ref() {
+            RequestData::GetProfile(req) => req,
+            _ => panic!("invalid request"),
+        };
+        let player = match self.player_service.get_player(context.client_id) {
+            Some(player) => player,
+            None => return Err(ServiceError::PlayerNotFound),
+        };
+        let account_id = match req.account_id.or(player.get_account_id()) {
+            Some(account_id) => account_id,
+            None => return Err(ServiceError::NoAccount),
+        };
+        let stats_service = self.stats_service.clone();
+        let stats = run_blocking(move || stats_service.get_player_stats(account_id)).await?;
+        Ok(ResponseData::GetProfile(ProfileResponse {
+            success: true,
+            account_id,
+            stats: Some(PlayerStats::from(&stats)),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        account::account::Account,
+        game::game::Game,
+        model::stats::profile::ProfileRequest,
+        service::{game_service::GameService, lobby_service::LobbyService},
+        stats::{match_record::MatchRecord, match_repository::MatchRepository},
+    };
+    use std::{collections::HashSet, error::Error};
+
+    fn profile_controller() -> Result<ProfileController, Box<dyn Error + Send + Sync>> {
+        Ok(ProfileController::new(
+            Arc::new(PlayerService::new(
+                Arc::new(LobbyService::new()),
+                Arc::new(GameService::new(HashSet::new())),
+            )),
+            Arc::new(StatsService::new(MatchRepository::open_in_memory()?)),
+        ))
+    }
+
+    fn profile_request(account_id: Option<u32>) -> Request {
+        Request::new(
+            0,
+            Arc::new(RequestData::GetProfile(ProfileRequest { account_id })),
+        )
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_registered_player_should_return_own_stats(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let controller = profile_controller()?;
+        let player = controller
+            .player_service
+            .add_player(0, String::from("test"));
+        player.set_account(Some(Account::new(1, String::from("test"))));
+        StatsService::record_result(
+            controller.stats_service.clone(),
+            MatchRecord::from_game(&Game::new(0, vec![player])),
+            Vec::new(),
+        )
+        .await?;
+        let res = match controller
+            .handle_request(profile_request(None), RequestContext { client_id: 0 })
+            .await?
+        {
+            ResponseData::GetProfile(res) => res,
+            _ => panic!("invalid response"),
+        };
+        assert_eq!(res.account_id, 1);
+        assert_eq!(res.stats.unwrap().games_played, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_request_with_guest_without_account_id_should_return_error(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let controller = profile_controller()?;
+        controller
+            .player_service
+            .add_player(0, String::from("test"));
+        assert!(controller
+            .handle_request(profile_request(None), RequestContext { client_id: 0 })
+            .await
+            .is_err());
+        assert!(controller
+            .handle_request(profile_request(Some(1)), RequestContext { client_id: 0 })
+            .await
+            .is_ok());
+        Ok(())
+    }
+}
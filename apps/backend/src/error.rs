@@ -0,0 +1,188 @@
+use std::{error::Error, fmt::Display};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServiceError {
+    Internal(String),
+    UnsupportedProtocolVersion(u32),
+    PlayerNotFound,
+    AlreadyConnected,
+    AccountAlreadyConnected,
+    InvalidSessionToken,
+    UnknownPing,
+    NotInLobby,
+    AlreadyInLobby,
+    LobbyNotFound,
+    LobbyFull,
+    InvalidMaxPlayers,
+    NotLeader,
+    LeaderCannotBeKicked,
+    BannedFromLobby,
+    WrongLobbyPassword,
+    NotAllReady,
+    RematchInProgress,
+    NoRematchInProgress,
+    InvalidLobbyName,
+    InvalidLobbyPassword,
+    InvalidLobbyLanguage,
+    InvalidLobbyDictionary,
+    InvalidSpectatorDelay,
+    InvalidAutoStartMinPlayers,
+    InvalidAfkRemovalTurns,
+    GameNotFound,
+    GameNotStarted,
+    GameAlreadyStarted,
+    NotInGame,
+    AlreadyInGame,
+    NotYourTurn,
+    AlreadyShuffled,
+    TileOutOfBoard,
+    CardAlreadyUsed,
+    CardNotOnBoard,
+    CardNotPlacedThisTurn,
+    InvalidWord,
+    SpectatorsNotAllowed,
+    AlreadySpectating,
+    NotSpectating,
+    CannotVoteKickSelf,
+    TargetNotInGame,
+    TargetNotAfk,
+    EmptyMessage,
+    MessageTooLong,
+    SendingTooFast,
+    NotInLobbyOrGame,
+    AlreadyInQueue,
+    NotInQueue,
+    EmptyMatchmakingGroup,
+    NoAccount,
+    AccountNotFound,
+    InvalidUsername,
+    InvalidPassword,
+    UsernameTaken,
+    InvalidCredentials,
+    PasswordRequired,
+    CannotFriendSelf,
+    AlreadyFriends,
+    FriendRequestAlreadySent,
+    FriendRequestNotFound,
+    FriendNotFound,
+    NotFriends,
+    FriendOffline,
+    FriendAlreadyInLobby,
+}
+
+impl Display for ServiceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let reason = match self {
+            ServiceError::Internal(reason) => return write!(f, "Internal error: {}", reason),
+            ServiceError::UnsupportedProtocolVersion(version) => {
+                return write!(
+                    f,
+                    "Protocol version {} is not supported, this server accepts versions {} to {}",
+                    version,
+                    crate::protocol::MIN_PROTOCOL_VERSION,
+                    crate::protocol::PROTOCOL_VERSION
+                )
+            }
+            ServiceError::PlayerNotFound => "Player not found",
+            ServiceError::AlreadyConnected => "Client already connected",
+            ServiceError::AccountAlreadyConnected => "Account already connected",
+            ServiceError::InvalidSessionToken => "Invalid session token",
+            ServiceError::UnknownPing => "Unknown ping",
+            ServiceError::NotInLobby => "Player is not in a lobby",
+            ServiceError::AlreadyInLobby => "Player is already in a lobby",
+            ServiceError::LobbyNotFound => "Lobby not found",
+            ServiceError::LobbyFull => "Lobby is full",
+            ServiceError::InvalidMaxPlayers => "Invalid max players",
+            ServiceError::NotLeader => "Only the leader can do this",
+            ServiceError::LeaderCannotBeKicked => "Leader can't be kicked",
+            ServiceError::BannedFromLobby => "Player is banned from the lobby",
+            ServiceError::WrongLobbyPassword => "Wrong lobby password",
+            ServiceError::NotAllReady => "Not all players are ready",
+            ServiceError::RematchInProgress => "Rematch vote in progress",
+            ServiceError::NoRematchInProgress => "No rematch vote in progress",
+            ServiceError::InvalidLobbyName => "Invalid lobby name",
+            ServiceError::InvalidLobbyPassword => "Invalid lobby password",
+            ServiceError::InvalidLobbyLanguage => "Invalid lobby language",
+            ServiceError::InvalidLobbyDictionary => "Invalid lobby dictionary",
+            ServiceError::InvalidSpectatorDelay => "Invalid spectator delay",
+            ServiceError::InvalidAutoStartMinPlayers => "Invalid auto start minimum players",
+            ServiceError::InvalidAfkRemovalTurns => "Invalid AFK removal turns",
+            ServiceError::GameNotFound => "Game not found",
+            ServiceError::GameNotStarted => "Game not started",
+            ServiceError::GameAlreadyStarted => "Game already started",
+            ServiceError::NotInGame => "Player is not in a game",
+            ServiceError::AlreadyInGame => "Player is already in a game",
+            ServiceError::NotYourTurn => "It is not the player's turn",
+            ServiceError::AlreadyShuffled => "Player has shuffled in this turn",
+            ServiceError::TileOutOfBoard => "Tile out of board",
+            ServiceError::CardAlreadyUsed => "Card has been used",
+            ServiceError::CardNotOnBoard => "Card is not on the board",
+            ServiceError::CardNotPlacedThisTurn => "Card was not placed in this turn",
+            ServiceError::InvalidWord => "Invalid word",
+            ServiceError::SpectatorsNotAllowed => "Spectators are not allowed",
+            ServiceError::AlreadySpectating => "Player is already spectating",
+            ServiceError::NotSpectating => "Player is not spectating",
+            ServiceError::CannotVoteKickSelf => "Cannot vote to kick yourself",
+            ServiceError::TargetNotInGame => "Target is not in the game",
+            ServiceError::TargetNotAfk => "Target is not AFK",
+            ServiceError::EmptyMessage => "Message is empty",
+            ServiceError::MessageTooLong => "Message is too long",
+            ServiceError::SendingTooFast => "Player is sending messages too fast",
+            ServiceError::NotInLobbyOrGame => "Player is not in a lobby or game",
+            ServiceError::AlreadyInQueue => "Player already in matchmaking queue",
+            ServiceError::NotInQueue => "Player is not in matchmaking queue",
+            ServiceError::EmptyMatchmakingGroup => "Matchmaking group is empty",
+            ServiceError::NoAccount => "Player has no account",
+            ServiceError::AccountNotFound => "Account not found",
+            ServiceError::InvalidUsername => "Invalid username",
+            ServiceError::InvalidPassword => "Invalid password",
+            ServiceError::UsernameTaken => "Username already taken",
+            ServiceError::InvalidCredentials => "Invalid username or password",
+            ServiceError::PasswordRequired => "Password required",
+            ServiceError::CannotFriendSelf => "Cannot add yourself as a friend",
+            ServiceError::AlreadyFriends => "Already friends",
+            ServiceError::FriendRequestAlreadySent => "Friend request already sent",
+            ServiceError::FriendRequestNotFound => "Friend request not found",
+            ServiceError::FriendNotFound => "Friend not found",
+            ServiceError::NotFriends => "Not friends",
+            ServiceError::FriendOffline => "Friend is offline",
+            ServiceError::FriendAlreadyInLobby => "Friend already in lobby",
+        };
+        write!(f, "{}", reason)
+    }
+}
+
+impl Error for ServiceError {}
+
+/**
+ * Storage and other infrastructure failures have no code of their own. A service error that was
+ * boxed on the way keeps its code.
+ */
+impl From<Box<dyn Error + Send + Sync>> for ServiceError {
+    fn from(err: Box<dyn Error + Send + Sync>) -> Self {
+        match err.downcast::<ServiceError>() {
+            Ok(err) => *err,
+            Err(err) => ServiceError::Internal(err.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_with_boxed_service_error_should_keep_it() {
+        let err: Box<dyn Error + Send + Sync> = Box::new(ServiceError::LobbyFull);
+        assert_eq!(ServiceError::from(err), ServiceError::LobbyFull);
+    }
+
+    #[test]
+    fn from_with_other_error_should_return_internal() {
+        let err: Box<dyn Error + Send + Sync> = "disk full".into();
+        assert_eq!(
+            ServiceError::from(err),
+            ServiceError::Internal(String::from("disk full"))
+        );
+    }
+}
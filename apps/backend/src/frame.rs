@@ -4,16 +4,39 @@ use bytes::Buf;
 use prost::Message;
 
 use crate::{
+    model::chat::send::SendRequest,
+    model::chat::send::SendResponse,
     model::control::connect::ConnectRequest,
     model::control::connect::ConnectResponse,
     model::control::disconnect::DisconnectResponse,
     model::control::heartbeat::HeartbeatResponse,
+    model::control::ping::PingBroadcast,
+    model::control::ping::PongRequest,
+    model::control::ping::PongResponse,
+    model::control::reconnect::ReconnectRequest,
+    model::control::reconnect::ReconnectResponse,
+    model::friend::accept::AcceptFriendRequest,
+    model::friend::accept::AcceptFriendResponse,
+    model::friend::add::AddFriendRequest,
+    model::friend::add::AddFriendResponse,
+    model::friend::broadcast::FriendBroadcast,
+    model::friend::invite::InviteRequest,
+    model::friend::invite::InviteResponse,
+    model::friend::list::ListFriendsResponse,
+    model::friend::remove::RemoveFriendRequest,
+    model::friend::remove::RemoveFriendResponse,
     model::game::broadcast::GameBroadcast,
     model::game::exit::ExitResponse,
     model::game::finish_turn::FinishTurnResponse,
+    model::game::get_board::GetBoardResponse,
     model::game::get_new_card::GetNewCardResponse,
     model::game::set_tile::SetTileRequest,
     model::game::set_tile::SetTileResponse,
+    model::game::spectate::SpectateRequest,
+    model::game::spectate::SpectateResponse,
+    model::game::stop_spectating::StopSpectatingResponse,
+    model::game::vote_kick::VoteKickRequest,
+    model::game::vote_kick::VoteKickResponse,
     model::game::{
         cancel::{CancelRequest, CancelResponse},
         start::StartResponse,
@@ -23,17 +46,38 @@ use crate::{
     model::lobby::create::CreateResponse,
     model::lobby::join::JoinRequest,
     model::lobby::join::JoinResponse,
+    model::lobby::kick::KickRequest,
+    model::lobby::kick::KickResponse,
+    model::lobby::list::ListRequest,
     model::lobby::list::ListResponse,
+    model::lobby::list::LobbyListBroadcast,
     model::lobby::quit::QuitResponse,
     model::lobby::ready::ReadyResponse,
-    operation::Operation,
+    model::lobby::rematch::RematchRequest,
+    model::lobby::rematch::RematchResponse,
+    model::lobby::update::UpdateRequest,
+    model::lobby::update::UpdateResponse,
+    model::matchmaking::cancel::CancelQuickPlayResponse,
+    model::matchmaking::quick_play::QuickPlayRequest,
+    model::matchmaking::quick_play::QuickPlayResponse,
+    model::stats::history::HistoryRequest,
+    model::stats::history::HistoryResponse,
+    model::stats::leaderboard::LeaderboardRequest,
+    model::stats::leaderboard::LeaderboardResponse,
+    model::stats::profile::ProfileRequest,
+    model::stats::profile::ProfileResponse,
+    operation::{Operation, MAX_PAYLOAD_LEN},
 };
 use std::hash::{Hash, Hasher};
 
+pub const HEADER_LEN: usize = 12;
+
 #[derive(Debug)]
 pub enum Frame {
     Request(Request),
     Response(Response),
+    Invalid { state: u32, reason: String },
+    TooLarge { state: u32, reason: String },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -64,7 +108,7 @@ pub enum RequestData {
     CreateLobby(CreateRequest),
     JoinLobby(JoinRequest),
     QuitLobby,
-    ListLobby,
+    ListLobby(ListRequest),
     Ready,
     StartGame,
     SetTile(SetTileRequest),
@@ -72,6 +116,26 @@ pub enum RequestData {
     GetNewCard,
     Cancel(CancelRequest),
     Exit,
+    UpdateLobby(UpdateRequest),
+    Kick(KickRequest),
+    Chat(SendRequest),
+    QuickPlay(QuickPlayRequest),
+    CancelQuickPlay,
+    Rematch(RematchRequest),
+    Spectate(SpectateRequest),
+    StopSpectating,
+    Reconnect(ReconnectRequest),
+    GetProfile(ProfileRequest),
+    GetMatchHistory(HistoryRequest),
+    GetLeaderboard(LeaderboardRequest),
+    AddFriend(AddFriendRequest),
+    AcceptFriend(AcceptFriendRequest),
+    RemoveFriend(RemoveFriendRequest),
+    ListFriends,
+    Invite(InviteRequest),
+    VoteKick(VoteKickRequest),
+    Pong(PongRequest),
+    GetBoard,
 }
 
 impl Hash for RequestData {
@@ -83,7 +147,7 @@ impl Hash for RequestData {
             RequestData::CreateLobby(_) => 3.hash(state),
             RequestData::JoinLobby(_) => 4.hash(state),
             RequestData::QuitLobby => 5.hash(state),
-            RequestData::ListLobby => 6.hash(state),
+            RequestData::ListLobby(_) => 6.hash(state),
             RequestData::Ready => 7.hash(state),
             RequestData::StartGame => 8.hash(state),
             RequestData::SetTile(_) => 9.hash(state),
@@ -91,6 +155,26 @@ impl Hash for RequestData {
             RequestData::GetNewCard => 11.hash(state),
             RequestData::Cancel(_) => 12.hash(state),
             RequestData::Exit => 13.hash(state),
+            RequestData::UpdateLobby(_) => 14.hash(state),
+            RequestData::Kick(_) => 15.hash(state),
+            RequestData::Chat(_) => 16.hash(state),
+            RequestData::QuickPlay(_) => 17.hash(state),
+            RequestData::CancelQuickPlay => 18.hash(state),
+            RequestData::Rematch(_) => 19.hash(state),
+            RequestData::Spectate(_) => 20.hash(state),
+            RequestData::StopSpectating => 21.hash(state),
+            RequestData::Reconnect(_) => 22.hash(state),
+            RequestData::GetProfile(_) => 23.hash(state),
+            RequestData::GetMatchHistory(_) => 24.hash(state),
+            RequestData::GetLeaderboard(_) => 25.hash(state),
+            RequestData::AddFriend(_) => 26.hash(state),
+            RequestData::AcceptFriend(_) => 27.hash(state),
+            RequestData::RemoveFriend(_) => 28.hash(state),
+            RequestData::ListFriends => 29.hash(state),
+            RequestData::Invite(_) => 30.hash(state),
+            RequestData::VoteKick(_) => 31.hash(state),
+            RequestData::Pong(_) => 32.hash(state),
+            RequestData::GetBoard => 33.hash(state),
         }
     }
 }
@@ -134,50 +218,129 @@ pub enum ResponseData {
     GetNewCard(GetNewCardResponse),
     GameBroadcast(GameBroadcast),
     Exit(ExitResponse),
+    UpdateLobby(UpdateResponse),
+    Kick(KickResponse),
+    Chat(SendResponse),
+    LobbyListBroadcast(LobbyListBroadcast),
+    FriendBroadcast(FriendBroadcast),
+    Ping(PingBroadcast),
+    QuickPlay(QuickPlayResponse),
+    CancelQuickPlay(CancelQuickPlayResponse),
+    Rematch(RematchResponse),
+    Spectate(SpectateResponse),
+    StopSpectating(StopSpectatingResponse),
+    Reconnect(ReconnectResponse),
+    GetProfile(ProfileResponse),
+    GetMatchHistory(HistoryResponse),
+    GetLeaderboard(LeaderboardResponse),
+    AddFriend(AddFriendResponse),
+    AcceptFriend(AcceptFriendResponse),
+    RemoveFriend(RemoveFriendResponse),
+    ListFriends(ListFriendsResponse),
+    Invite(InviteResponse),
+    VoteKick(VoteKickResponse),
+    Pong(PongResponse),
+    GetBoard(GetBoardResponse),
 }
 
 #[derive(Debug)]
 pub enum Error {
     Incomplete,
-    ProtobufDecodeFailed(prost::DecodeError),
+    Malformed {
+        state: u32,
+        len: usize,
+        reason: Box<dyn std::error::Error + Send + Sync>,
+    },
+    TooLarge {
+        state: u32,
+        len: usize,
+        max: usize,
+    },
     Other(Box<dyn std::error::Error + Send + Sync>),
 }
 
 impl Frame {
     pub fn check(src: &mut Cursor<&[u8]>) -> Result<(), Error> {
-        let op = match Operation::try_from(get_u8(src)?) {
+        let op = get_u8(src)?;
+        let version = get_u8(src)?;
+        src.set_position(4);
+        let state = get_u32(src)?;
+        let payload_len = get_u32(src)? as usize;
+        let op = Operation::decode(op, version);
+        // reject oversized frames before waiting for their content
+        let max = match &op {
+            Ok(op) => op.max_payload_len(),
+            Err(_) => MAX_PAYLOAD_LEN,
+        };
+        if payload_len > max {
+            return Err(Error::TooLarge {
+                state,
+                len: payload_len,
+                max,
+            });
+        }
+        // only judge the content once the whole frame is buffered, so it can be skipped
+        if src.remaining() < payload_len {
+            return Err(Error::Incomplete);
+        }
+        let len = HEADER_LEN + payload_len;
+        let op = match op {
             Ok(op) => op,
-            Err(e) => {
-                return Err(Error::Other(e));
-            }
+            Err(reason) => return Err(Error::Malformed { state, len, reason }),
         };
-        src.set_position(8);
-        let payload_len = get_u32(src)?;
-        let payload = src.take(payload_len as usize);
+        let data: &[u8] = src.get_ref();
+        let payload = &data[HEADER_LEN..len];
+        src.set_position(len as u64);
         let e = match op {
             Operation::Connect => ConnectRequest::decode(payload).err(),
-            Operation::Disconnect => return Ok(()),
-            Operation::Heartbeat => return Ok(()),
+            Operation::Disconnect => None,
+            Operation::Heartbeat => None,
             Operation::CreateLobby => CreateRequest::decode(payload).err(),
             Operation::JoinLobby => JoinRequest::decode(payload).err(),
-            Operation::QuitLobby => return Ok(()),
-            Operation::ListLobby => return Ok(()),
-            Operation::Ready => return Ok(()),
-            Operation::StartGame => return Ok(()),
+            Operation::QuitLobby => None,
+            Operation::ListLobby => ListRequest::decode(payload).err(),
+            Operation::Ready => None,
+            Operation::StartGame => None,
             Operation::SetTile => SetTileRequest::decode(payload).err(),
-            Operation::FinishTurn => return Ok(()),
-            Operation::Exit => return Ok(()),
-            Operation::GetNewCard => return Ok(()),
+            Operation::FinishTurn => None,
+            Operation::Exit => None,
+            Operation::GetNewCard => None,
             Operation::Cancel => CancelRequest::decode(payload).err(),
+            Operation::UpdateLobby => UpdateRequest::decode(payload).err(),
+            Operation::Kick => KickRequest::decode(payload).err(),
+            Operation::Chat => SendRequest::decode(payload).err(),
+            Operation::QuickPlay => QuickPlayRequest::decode(payload).err(),
+            Operation::CancelQuickPlay => None,
+            Operation::Rematch => RematchRequest::decode(payload).err(),
+            Operation::Spectate => SpectateRequest::decode(payload).err(),
+            Operation::StopSpectating => None,
+            Operation::Reconnect => ReconnectRequest::decode(payload).err(),
+            Operation::GetProfile => ProfileRequest::decode(payload).err(),
+            Operation::GetMatchHistory => HistoryRequest::decode(payload).err(),
+            Operation::GetLeaderboard => LeaderboardRequest::decode(payload).err(),
+            Operation::AddFriend => AddFriendRequest::decode(payload).err(),
+            Operation::AcceptFriend => AcceptFriendRequest::decode(payload).err(),
+            Operation::RemoveFriend => RemoveFriendRequest::decode(payload).err(),
+            Operation::ListFriends => None,
+            Operation::Invite => InviteRequest::decode(payload).err(),
+            Operation::VoteKick => VoteKickRequest::decode(payload).err(),
+            Operation::Pong => PongRequest::decode(payload).err(),
+            Operation::GetBoard => None,
         };
-        if e.is_some() {
-            return Err(Error::ProtobufDecodeFailed(e.unwrap()));
+        match e {
+            Some(e) => Err(Error::Malformed {
+                state,
+                len,
+                reason: e.into(),
+            }),
+            None => Ok(()),
         }
-        Ok(())
     }
 
     pub fn parse(src: &mut Cursor<&[u8]>) -> Result<Frame, Error> {
-        let op = match Operation::try_from(get_u8(src)?) {
+        let op = get_u8(src)?;
+        let version = get_u8(src)?;
+        let op = match Operation::decode(op, version) {
             Ok(op) => op,
             Err(e) => {
                 return Err(Error::Other(e));
@@ -193,14 +356,14 @@ impl Frame {
                     state,
                     data: Arc::new(RequestData::Connect(req)),
                 })),
-                Err(e) => Err(Error::ProtobufDecodeFailed(e)),
+                Err(e) => Err(Error::Other(e.into())),
             },
             Operation::Cancel => match CancelRequest::decode(payload) {
                 Ok(req) => Ok(Frame::Request(Request {
                     state,
                     data: Arc::new(RequestData::Cancel(req)),
                 })),
-                Err(e) => Err(Error::ProtobufDecodeFailed(e)),
+                Err(e) => Err(Error::Other(e.into())),
             },
             Operation::Disconnect => Ok(Frame::Request(Request {
                 state,
@@ -215,23 +378,26 @@ impl Frame {
                     state,
                     data: Arc::new(RequestData::CreateLobby(req)),
                 })),
-                Err(e) => Err(Error::ProtobufDecodeFailed(e)),
+                Err(e) => Err(Error::Other(e.into())),
             },
             Operation::JoinLobby => match JoinRequest::decode(payload) {
                 Ok(req) => Ok(Frame::Request(Request {
                     state,
                     data: Arc::new(RequestData::JoinLobby(req)),
                 })),
-                Err(e) => Err(Error::ProtobufDecodeFailed(e)),
+                Err(e) => Err(Error::Other(e.into())),
             },
             Operation::QuitLobby => Ok(Frame::Request(Request {
                 state,
                 data: Arc::new(RequestData::QuitLobby),
             })),
-            Operation::ListLobby => Ok(Frame::Request(Request {
-                state,
-                data: Arc::new(RequestData::ListLobby),
-            })),
+            Operation::ListLobby => match ListRequest::decode(payload) {
+                Ok(req) => Ok(Frame::Request(Request {
+                    state,
+                    data: Arc::new(RequestData::ListLobby(req)),
+                })),
+                Err(e) => Err(Error::Other(e.into())),
+            },
             Operation::Ready => Ok(Frame::Request(Request {
                 state,
                 data: Arc::new(RequestData::Ready),
@@ -245,7 +411,7 @@ impl Frame {
                     state,
                     data: Arc::new(RequestData::SetTile(req)),
                 })),
-                Err(e) => Err(Error::ProtobufDecodeFailed(e)),
+                Err(e) => Err(Error::Other(e.into())),
             },
             Operation::FinishTurn => Ok(Frame::Request(Request {
                 state,
@@ -259,6 +425,134 @@ impl Frame {
                 state,
                 data: Arc::new(RequestData::Exit),
             })),
+            Operation::UpdateLobby => match UpdateRequest::decode(payload) {
+                Ok(req) => Ok(Frame::Request(Request {
+                    state,
+                    data: Arc::new(RequestData::UpdateLobby(req)),
+                })),
+                Err(e) => Err(Error::Other(e.into())),
+            },
+            Operation::Kick => match KickRequest::decode(payload) {
+                Ok(req) => Ok(Frame::Request(Request {
+                    state,
+                    data: Arc::new(RequestData::Kick(req)),
+                })),
+                Err(e) => Err(Error::Other(e.into())),
+            },
+            Operation::Chat => match SendRequest::decode(payload) {
+                Ok(req) => Ok(Frame::Request(Request {
+                    state,
+                    data: Arc::new(RequestData::Chat(req)),
+                })),
+                Err(e) => Err(Error::Other(e.into())),
+            },
+            Operation::QuickPlay => match QuickPlayRequest::decode(payload) {
+                Ok(req) => Ok(Frame::Request(Request {
+                    state,
+                    data: Arc::new(RequestData::QuickPlay(req)),
+                })),
+                Err(e) => Err(Error::Other(e.into())),
+            },
+            Operation::CancelQuickPlay => Ok(Frame::Request(Request {
+                state,
+                data: Arc::new(RequestData::CancelQuickPlay),
+            })),
+            Operation::Rematch => match RematchRequest::decode(payload) {
+                Ok(req) => Ok(Frame::Request(Request {
+                    state,
+                    data: Arc::new(RequestData::Rematch(req)),
+                })),
+                Err(e) => Err(Error::Other(e.into())),
+            },
+            Operation::Spectate => match SpectateRequest::decode(payload) {
+                Ok(req) => Ok(Frame::Request(Request {
+                    state,
+                    data: Arc::new(RequestData::Spectate(req)),
+                })),
+                Err(e) => Err(Error::Other(e.into())),
+            },
+            Operation::StopSpectating => Ok(Frame::Request(Request {
+                state,
+                data: Arc::new(RequestData::StopSpectating),
+            })),
+            Operation::Reconnect => match ReconnectRequest::decode(payload) {
+                Ok(req) => Ok(Frame::Request(Request {
+                    state,
+                    data: Arc::new(RequestData::Reconnect(req)),
+                })),
+                Err(e) => Err(Error::Other(e.into())),
+            },
+            Operation::GetProfile => match ProfileRequest::decode(payload) {
+                Ok(req) => Ok(Frame::Request(Request {
+                    state,
+                    data: Arc::new(RequestData::GetProfile(req)),
+                })),
+                Err(e) => Err(Error::Other(e.into())),
+            },
+            Operation::GetMatchHistory => match HistoryRequest::decode(payload) {
+                Ok(req) => Ok(Frame::Request(Request {
+                    state,
+                    data: Arc::new(RequestData::GetMatchHistory(req)),
+                })),
+                Err(e) => Err(Error::Other(e.into())),
+            },
+            Operation::GetLeaderboard => match LeaderboardRequest::decode(payload) {
+                Ok(req) => Ok(Frame::Request(Request {
+                    state,
+                    data: Arc::new(RequestData::GetLeaderboard(req)),
+                })),
+                Err(e) => Err(Error::Other(e.into())),
+            },
+            Operation::AddFriend => match AddFriendRequest::decode(payload) {
+                Ok(req) => Ok(Frame::Request(Request {
+                    state,
+                    data: Arc::new(RequestData::AddFriend(req)),
+                })),
+                Err(e) => Err(Error::Other(e.into())),
+            },
+            Operation::AcceptFriend => match AcceptFriendRequest::decode(payload) {
+                Ok(req) => Ok(Frame::Request(Request {
+                    state,
+                    data: Arc::new(RequestData::AcceptFriend(req)),
+                })),
+                Err(e) => Err(Error::Other(e.into())),
+            },
+            Operation::RemoveFriend => match RemoveFriendRequest::decode(payload) {
+                Ok(req) => Ok(Frame::Request(Request {
+                    state,
+                    data: Arc::new(RequestData::RemoveFriend(req)),
+                })),
+                Err(e) => Err(Error::Other(e.into())),
+            },
+            Operation::ListFriends => Ok(Frame::Request(Request {
+                state,
+                data: Arc::new(RequestData::ListFriends),
+            })),
+            Operation::Invite => match InviteRequest::decode(payload) {
+                Ok(req) => Ok(Frame::Request(Request {
+                    state,
+                    data: Arc::new(RequestData::Invite(req)),
+                })),
+                Err(e) => Err(Error::Other(e.into())),
+            },
+            Operation::VoteKick => match VoteKickRequest::decode(payload) {
+                Ok(req) => Ok(Frame::Request(Request {
+                    state,
+                    data: Arc::new(RequestData::VoteKick(req)),
+                })),
+                Err(e) => Err(Error::Other(e.into())),
+            },
+            Operation::Pong => match PongRequest::decode(payload) {
+                Ok(req) => Ok(Frame::Request(Request {
+                    state,
+                    data: Arc::new(RequestData::Pong(req)),
+                })),
+                Err(e) => Err(Error::Other(e.into())),
+            },
+            Operation::GetBoard => Ok(Frame::Request(Request {
+                state,
+                data: Arc::new(RequestData::GetBoard),
+            })),
         }
     }
 }
@@ -278,3 +572,74 @@ fn get_u32(src: &mut Cursor<&[u8]>) -> Result<u32, Error> {
 
     Ok(src.get_u32())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::BufMut;
+
+    fn request(op: u8, state: u32, payload: &[u8]) -> Vec<u8> {
+        let mut buf = vec![op, 0, 0, 0];
+        buf.put_u32(state);
+        buf.put_u32(payload.len() as u32);
+        buf.put_slice(payload);
+        buf
+    }
+
+    #[test]
+    fn check_with_partial_payload_should_return_incomplete() {
+        let buf = request(6, 2, &[8, 1]);
+        assert!(matches!(
+            Frame::check(&mut Cursor::new(&buf[..buf.len() - 1])),
+            Err(Error::Incomplete)
+        ));
+    }
+
+    #[test]
+    fn check_with_unknown_operation_should_return_malformed_with_frame_length() {
+        let buf = request(255, 2, &[1, 2, 3]);
+        match Frame::check(&mut Cursor::new(&buf[..])) {
+            Err(Error::Malformed { state, len, .. }) => {
+                assert_eq!(state, 2);
+                assert_eq!(len, buf.len());
+            }
+            _ => panic!("frame should be malformed"),
+        }
+    }
+
+    #[test]
+    fn check_with_undecodable_payload_should_return_malformed() {
+        let buf = request(0, 2, &[0xff, 0xff, 0xff]);
+        assert!(matches!(
+            Frame::check(&mut Cursor::new(&buf[..])),
+            Err(Error::Malformed { state: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn check_with_oversized_payload_should_return_too_large_before_content_arrives() {
+        let mut buf = request(16, 2, &[]);
+        buf[8..12].copy_from_slice(&(MAX_PAYLOAD_LEN as u32 + 1).to_be_bytes());
+        assert!(matches!(
+            Frame::check(&mut Cursor::new(&buf[..])),
+            Err(Error::TooLarge { state: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn check_with_payload_on_bodyless_operation_should_return_too_large() {
+        let buf = request(1, 2, &[1]);
+        assert!(matches!(
+            Frame::check(&mut Cursor::new(&buf[..])),
+            Err(Error::TooLarge { max: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn check_with_empty_request_should_consume_whole_frame() {
+        let buf = request(1, 2, &[]);
+        let mut cursor = Cursor::new(&buf[..]);
+        assert!(Frame::check(&mut cursor).is_ok());
+        assert_eq!(cursor.position() as usize, buf.len());
+    }
+}
@@ -19,6 +19,34 @@ impl Board {
         }
     }
 
+    /**
+     * The tile at the given client coordinates. Clients count rows from the bottom.
+     */
+    pub fn get_tile(&self, x: usize, y: usize) -> Option<&Tile> {
+        self.tiles[BOARD_SIZE - y - 1][x].as_ref()
+    }
+
+    pub fn set_tile(&mut self, x: usize, y: usize, tile: Option<Tile>) {
+        self.tiles[BOARD_SIZE - y - 1][x] = tile;
+    }
+
+    pub fn diff(&self, other: &Board) -> Vec<(usize, usize)> {
+        let mut cells = Vec::new();
+        for y in 0..BOARD_SIZE {
+            for x in 0..BOARD_SIZE {
+                let same = match (self.get_tile(x, y), other.get_tile(x, y)) {
+                    (Some(a), Some(b)) => a.char == b.char && a.owner == b.owner,
+                    (None, None) => true,
+                    _ => false,
+                };
+                if !same {
+                    cells.push((x, y));
+                }
+            }
+        }
+        cells
+    }
+
     pub fn validate(&self, dict: &HashSet<String>, game: Arc<Game>) -> Option<Vec<String>> {
         let mut current_word: Option<String> = None;
         let mut is_horizontal_word_arr = [[false; BOARD_SIZE]; BOARD_SIZE];
@@ -326,4 +354,17 @@ mod tests {
         assert!(list.unwrap().len() == 0);
         Ok(())
     }
+
+    #[test]
+    fn diff_with_changed_cells_should_return_their_coordinates() {
+        let player = Arc::new(Player::new(0, String::from("test")));
+        let mut board = Board::new();
+        board.set_tile(0, 0, Some(Tile::new('a', player.clone(), 1)));
+        board.set_tile(3, 5, Some(Tile::new('b', player.clone(), 1)));
+        let mut other = board.clone();
+        other.set_tile(3, 5, None);
+        other.set_tile(7, 2, Some(Tile::new('c', player, 1)));
+        assert_eq!(board.diff(&other), vec![(7, 2), (3, 5)]);
+        assert!(board.diff(&board.clone()).is_empty());
+    }
 }
@@ -1,12 +1,18 @@
 use std::{
     collections::{linked_list::LinkedList, HashMap},
     sync::{Arc, Mutex},
+    time::{Duration, SystemTime},
 };
 
 use super::{board::Board, game_player::GamePlayer};
+use crate::chat::{chat_history::ChatHistory, chat_message::ChatMessage};
+use crate::frame::Response;
+use crate::lobby::lobby_settings::LobbySettings;
 use crate::player::Player;
 pub const END_GAME_TURN: u32 = 16;
+use tokio::sync::{mpsc::UnboundedSender, MutexGuard};
 use tokio::task::JoinHandle;
+use tokio::time::Instant;
 #[derive(Debug)]
 pub struct Game {
     pub id: u32,
@@ -15,7 +21,16 @@ pub struct Game {
     turn_queue: Mutex<LinkedList<Arc<GamePlayer>>>,
     board: Arc<Mutex<Board>>,
     board_backup: Mutex<Board>,
+    board_sequence: Mutex<u32>,
     timeout: Mutex<Option<Arc<JoinHandle<()>>>>,
+    requests: tokio::sync::Mutex<()>,
+    chat_history: Mutex<ChatHistory>,
+    spectators: Mutex<HashMap<u32, Arc<Player>>>,
+    spectator_queues: Mutex<HashMap<u32, UnboundedSender<(Instant, Response)>>>,
+    allow_spectators: Mutex<bool>,
+    spectator_delay: Mutex<Duration>,
+    settings: Mutex<Option<LobbySettings>>,
+    started_at: SystemTime,
 }
 
 impl PartialEq for Game {
@@ -42,7 +57,16 @@ impl Game {
             turn_queue: Mutex::new(queue),
             board: Arc::new(Mutex::new(Board::new())),
             board_backup: Mutex::new(Board::new()),
+            board_sequence: Mutex::new(0),
             timeout: Mutex::new(None),
+            requests: tokio::sync::Mutex::new(()),
+            chat_history: Mutex::new(ChatHistory::default()),
+            spectators: Mutex::new(HashMap::new()),
+            spectator_queues: Mutex::new(HashMap::new()),
+            allow_spectators: Mutex::new(true),
+            spectator_delay: Mutex::new(Duration::ZERO),
+            settings: Mutex::new(None),
+            started_at: SystemTime::now(),
         }
     }
 
@@ -50,6 +74,22 @@ impl Game {
         *self.timeout.lock().unwrap() = Some(task);
     }
 
+    /**
+     * Forget the timeout task without aborting it. The task calls this before finishing the turn
+     * so it doesn't abort itself.
+     */
+    pub fn clear_timeout_task(&self) {
+        *self.timeout.lock().unwrap() = None;
+    }
+
+    /**
+     * Requests and timeouts of the game are handled one at a time while the guard is held, in the
+     * order they asked for it.
+     */
+    pub async fn lock_requests(&self) -> MutexGuard<'_, ()> {
+        self.requests.lock().await
+    }
+
     pub fn cancel_timeout_task(&self) -> bool {
         let mut task_mutex = self.timeout.lock().unwrap();
         match task_mutex.as_ref() {
@@ -62,11 +102,94 @@ impl Game {
         }
     }
 
+    pub fn add_chat_message(&self, message: ChatMessage) {
+        self.chat_history.lock().unwrap().push(message);
+    }
+
+    pub fn get_chat_history(&self) -> Vec<ChatMessage> {
+        self.chat_history.lock().unwrap().get_messages()
+    }
+
+    pub fn set_spectator_settings(&self, allow_spectators: bool, spectator_delay: Duration) {
+        *self.allow_spectators.lock().unwrap() = allow_spectators;
+        *self.spectator_delay.lock().unwrap() = spectator_delay;
+    }
+
+    pub fn get_settings(&self) -> Option<LobbySettings> {
+        self.settings.lock().unwrap().clone()
+    }
+
+    pub fn set_settings(&self, settings: LobbySettings) {
+        *self.settings.lock().unwrap() = Some(settings);
+    }
+
+    pub fn get_started_at(&self) -> SystemTime {
+        self.started_at
+    }
+
+    pub fn allows_spectators(&self) -> bool {
+        *self.allow_spectators.lock().unwrap()
+    }
+
+    pub fn get_spectator_delay(&self) -> Duration {
+        *self.spectator_delay.lock().unwrap()
+    }
+
+    pub fn add_spectator(&self, player: Arc<Player>) -> bool {
+        self.spectators
+            .lock()
+            .unwrap()
+            .insert(player.id, player)
+            .is_none()
+    }
+
+    pub fn remove_spectator(&self, player: &Player) -> Option<Arc<Player>> {
+        // the queue task sends what is left and stops once the sender is dropped
+        self.spectator_queues.lock().unwrap().remove(&player.id);
+        self.spectators.lock().unwrap().remove(&player.id)
+    }
+
+    /**
+     * Send the response to the spectator after the spectator delay. Responses of a spectator go
+     * through a single queue, so they arrive in the order they were sent.
+     */
+    #[cfg(not(test))]
+    pub fn send_to_spectator(&self, spectator: Arc<Player>, res: Response) {
+        let due = Instant::now() + self.get_spectator_delay();
+        let id = spectator.id;
+        let mut spectator_queues = self.spectator_queues.lock().unwrap();
+        let queue = spectator_queues.entry(id).or_insert_with(|| {
+            let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<(Instant, Response)>();
+            tokio::spawn(async move {
+                while let Some((due, res)) = rx.recv().await {
+                    tokio::time::sleep_until(due).await;
+                    if let Err(e) = spectator.send_message(res) {
+                        eprintln!("Error sending spectator broadcast: {}", e);
+                    }
+                }
+            });
+            tx
+        });
+        if queue.send((due, res)).is_err() {
+            eprintln!("spectator queue of player {} is closed", id);
+        }
+    }
+
+    pub fn get_spectators(&self) -> Vec<Arc<Player>> {
+        self.spectators.lock().unwrap().values().cloned().collect()
+    }
+
     pub fn get_board(&self) -> Arc<Mutex<Board>> {
         self.board.clone()
     }
 
     pub fn remove_player(&self, player: Arc<Player>) -> Option<Arc<GamePlayer>> {
+        let mut turn_queue = self.turn_queue.lock().unwrap();
+        *turn_queue = std::mem::take(&mut *turn_queue)
+            .into_iter()
+            .filter(|game_player| game_player.player != player)
+            .collect();
+        drop(turn_queue);
         self.players.lock().unwrap().remove(&player.id)
     }
 
@@ -78,8 +201,22 @@ impl Game {
         *self.board_backup.lock().unwrap() = self.board.lock().unwrap().clone();
     }
 
-    pub fn restore_board(&self) {
-        *self.board.lock().unwrap() = self.board_backup.lock().unwrap().clone();
+    pub fn restore_board(&self) -> Vec<(usize, usize)> {
+        let mut board = self.board.lock().unwrap();
+        let backup = self.board_backup.lock().unwrap();
+        let cells = board.diff(&backup);
+        *board = backup.clone();
+        cells
+    }
+
+    pub fn get_board_sequence(&self) -> u32 {
+        *self.board_sequence.lock().unwrap()
+    }
+
+    pub fn next_board_sequence(&self) -> u32 {
+        let mut sequence = self.board_sequence.lock().unwrap();
+        *sequence = sequence.wrapping_add(1);
+        *sequence
     }
 
     #[cfg(test)]
@@ -254,4 +391,35 @@ mod tests {
         assert!(!game.cancel_timeout_task());
         Ok(())
     }
+
+    #[tokio::test]
+    async fn lock_requests_with_held_guard_should_wait_for_it() {
+        let player = Arc::new(Player::new(0, String::from("test")));
+        let game = Arc::new(Game::new(0, vec![player]));
+        let guard = game.lock_requests().await;
+        let task = {
+            let game = game.clone();
+            tokio::spawn(async move {
+                let _guard = game.lock_requests().await;
+            })
+        };
+        sleep(Duration::from_millis(10)).await;
+        assert!(!task.is_finished());
+        drop(guard);
+        sleep(Duration::from_millis(10)).await;
+        assert!(task.is_finished());
+    }
+
+    #[test]
+    fn add_spectator_with_test_player_should_not_be_game_player() {
+        let player = Arc::new(Player::new(0, String::from("test1")));
+        let spectator = Arc::new(Player::new(1, String::from("test2")));
+        let game = Game::new(0, vec![player]);
+        assert!(game.add_spectator(spectator.clone()));
+        assert!(!game.add_spectator(spectator.clone()));
+        assert_eq!(game.get_spectators().len(), 1);
+        assert!(game.get_player(spectator.id).is_none());
+        assert!(game.remove_spectator(&spectator).is_some());
+        assert!(game.get_spectators().is_empty());
+    }
 }
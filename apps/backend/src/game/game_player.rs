@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use std::sync::Mutex;
@@ -10,6 +11,11 @@ use super::card::Card;
 pub struct GamePlayer {
     cards: Mutex<Vec<Card>>,
     has_shuffled: Mutex<bool>,
+    score: Mutex<u32>,
+    words: Mutex<Vec<String>>,
+    missed_turns: Mutex<u32>,
+    afk: Mutex<bool>,
+    kick_votes: Mutex<HashSet<u32>>,
     pub player: Arc<Player>,
 }
 
@@ -25,6 +31,11 @@ impl GamePlayer {
         Self {
             cards: Mutex::new(cards),
             has_shuffled: Mutex::new(false),
+            score: Mutex::new(0),
+            words: Mutex::new(Vec::new()),
+            missed_turns: Mutex::new(0),
+            afk: Mutex::new(false),
+            kick_votes: Mutex::new(HashSet::new()),
             player,
         }
     }
@@ -37,6 +48,55 @@ impl GamePlayer {
         *self.has_shuffled.lock().unwrap()
     }
 
+    pub fn get_score(&self) -> u32 {
+        *self.score.lock().unwrap()
+    }
+
+    pub fn get_words(&self) -> Vec<String> {
+        self.words.lock().unwrap().clone()
+    }
+
+    /**
+     * Credit the words formed in the player's turn. The board reports every word touching the
+     * player's tiles, so words already credited are skipped. Each new word scores its length.
+     */
+    pub fn record_words(&self, words: &[String]) {
+        let mut credited_words = self.words.lock().unwrap();
+        for word in words {
+            if credited_words.contains(word) {
+                continue;
+            }
+            *self.score.lock().unwrap() += word.chars().count() as u32;
+            credited_words.push(word.clone());
+        }
+    }
+
+    pub fn record_missed_turn(&self) -> u32 {
+        let mut missed_turns = self.missed_turns.lock().unwrap();
+        *missed_turns += 1;
+        *missed_turns
+    }
+
+    pub fn record_active_turn(&self) -> bool {
+        *self.missed_turns.lock().unwrap() = 0;
+        self.kick_votes.lock().unwrap().clear();
+        std::mem::replace(&mut *self.afk.lock().unwrap(), false)
+    }
+
+    pub fn is_afk(&self) -> bool {
+        *self.afk.lock().unwrap()
+    }
+
+    pub fn set_afk(&self, afk: bool) {
+        *self.afk.lock().unwrap() = afk;
+    }
+
+    pub fn add_kick_vote(&self, voter_id: u32) -> u32 {
+        let mut kick_votes = self.kick_votes.lock().unwrap();
+        kick_votes.insert(voter_id);
+        kick_votes.len() as u32
+    }
+
     pub fn generate_new_card() -> Vec<Card> {
         let mut consonant = (b'a'..=b'z') // Start as u8
             .map(|c| c as char) // Convert all to chars
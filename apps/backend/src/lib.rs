@@ -1,11 +1,21 @@
+pub mod account;
+pub mod chat;
 pub mod connection;
+pub mod connection_settings;
 pub mod controller;
+pub mod error;
 pub mod frame;
 pub mod game;
 pub mod lobby;
+pub mod matchmaking;
 pub mod model;
+pub mod naming;
 pub mod operation;
 pub mod player;
+pub mod protocol;
 pub mod router;
 pub mod server;
 pub mod service;
+pub mod stats;
+pub mod tls;
+pub mod transport;
@@ -1,3 +1,5 @@
 pub mod lobby;
+pub mod lobby_filter;
 pub mod lobby_info;
 pub mod lobby_player;
+pub mod lobby_settings;
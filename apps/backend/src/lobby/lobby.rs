@@ -1,15 +1,42 @@
+use crate::chat::{chat_history::ChatHistory, chat_message::ChatMessage};
+use crate::error::ServiceError;
+use crate::game::game::Game;
 use crate::player::Player;
-use std::error::Error;
+use rand::{distributions::Alphanumeric, Rng};
+use std::net::IpAddr;
 use std::sync::Mutex;
-use std::{collections::HashMap, sync::Arc};
+use std::time::{Duration, Instant};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+use tokio::sync::MutexGuard;
+use tokio::task::JoinHandle;
 
-use super::lobby_player::LobbyPlayer;
+use super::{lobby_player::LobbyPlayer, lobby_settings::LobbySettings};
+
+pub const INVITE_CODE_LENGTH: usize = 8;
+
+#[derive(Debug)]
+struct AutoStartCountdown {
+    task: Arc<JoinHandle<()>>,
+    deadline: Instant,
+}
 
 #[derive(Debug, Clone)]
 pub struct Lobby {
     id: u32,
     max_players: u32,
     players: Arc<Mutex<HashMap<u32, Arc<LobbyPlayer>>>>,
+    settings: Arc<Mutex<LobbySettings>>,
+    invite_code: String,
+    banned_player_ids: Arc<Mutex<HashSet<u32>>>,
+    banned_addresses: Arc<Mutex<HashSet<IpAddr>>>,
+    invited_player_ids: Arc<Mutex<HashSet<u32>>>,
+    chat_history: Arc<Mutex<ChatHistory>>,
+    rematch_vote_open: Arc<Mutex<bool>>,
+    auto_start_countdown: Arc<Mutex<Option<AutoStartCountdown>>>,
+    start: Arc<tokio::sync::Mutex<()>>,
     pub leader: Arc<Player>,
 }
 
@@ -30,23 +57,48 @@ impl Lobby {
                 leader.id,
                 Arc::new(LobbyPlayer::new(leader.clone())),
             )]))),
+            settings: Arc::new(Mutex::new(LobbySettings::new(
+                Lobby::default_name(&leader),
+                false,
+                None,
+            ))),
+            invite_code: Lobby::generate_invite_code(),
+            banned_player_ids: Arc::new(Mutex::new(HashSet::new())),
+            banned_addresses: Arc::new(Mutex::new(HashSet::new())),
+            invited_player_ids: Arc::new(Mutex::new(HashSet::new())),
+            chat_history: Arc::new(Mutex::new(ChatHistory::default())),
+            rematch_vote_open: Arc::new(Mutex::new(false)),
+            auto_start_countdown: Arc::new(Mutex::new(None)),
+            start: Arc::new(tokio::sync::Mutex::new(())),
             leader,
         }
     }
 
-    pub fn add_player(
-        &self,
-        player: Arc<Player>,
-    ) -> Result<Arc<LobbyPlayer>, Box<dyn Error + Send + Sync>> {
-        if self.players.lock().unwrap().contains_key(&player.id) {
-            return Err("Player already in lobby".into());
+    pub fn default_name(leader: &Player) -> String {
+        format!("{}'s lobby", leader.name)
+    }
+
+    fn generate_invite_code() -> String {
+        rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(INVITE_CODE_LENGTH)
+            .map(|c| char::from(c).to_ascii_uppercase())
+            .collect()
+    }
+
+    pub fn add_player(&self, player: Arc<Player>) -> Result<Arc<LobbyPlayer>, ServiceError> {
+        // checked under the same lock as the insert, so concurrent joins can't overfill the lobby
+        let mut players = self.players.lock().unwrap();
+        if players.contains_key(&player.id) {
+            return Err(ServiceError::AlreadyInLobby);
+        }
+        if players.len() as u32 >= self.max_players {
+            return Err(ServiceError::LobbyFull);
         }
 
         let lobby_player = Arc::new(LobbyPlayer::new(player.clone()));
-        self.players
-            .lock()
-            .unwrap()
-            .insert(player.id, lobby_player.clone());
+        self.invited_player_ids.lock().unwrap().remove(&player.id);
+        players.insert(player.id, lobby_player.clone());
         Ok(lobby_player)
     }
 
@@ -58,12 +110,9 @@ impl Lobby {
         self.players.lock().unwrap().values().cloned().collect()
     }
 
-    pub fn remove_player(
-        &self,
-        player: Arc<Player>,
-    ) -> Result<Arc<LobbyPlayer>, Box<dyn Error + Send + Sync>> {
+    pub fn remove_player(&self, player: Arc<Player>) -> Result<Arc<LobbyPlayer>, ServiceError> {
         if !self.players.lock().unwrap().contains_key(&player.id) {
-            return Err("Player is not in the lobby".into());
+            return Err(ServiceError::NotInLobby);
         }
         Ok(self.players.lock().unwrap().remove(&player.id).unwrap())
     }
@@ -75,6 +124,173 @@ impl Lobby {
     pub fn get_max_players(&self) -> u32 {
         self.max_players
     }
+
+    pub fn get_settings(&self) -> LobbySettings {
+        self.settings.lock().unwrap().clone()
+    }
+
+    pub fn set_settings(&self, settings: LobbySettings) {
+        *self.settings.lock().unwrap() = settings;
+    }
+
+    pub fn get_name(&self) -> String {
+        self.settings.lock().unwrap().name.clone()
+    }
+
+    pub fn is_private(&self) -> bool {
+        self.settings.lock().unwrap().private
+    }
+
+    pub fn get_language(&self) -> String {
+        self.settings.lock().unwrap().language.clone()
+    }
+
+    pub fn get_dictionary(&self) -> String {
+        self.settings.lock().unwrap().dictionary.clone()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.players.lock().unwrap().len() as u32 >= self.max_players
+    }
+
+    pub fn get_game(&self) -> Option<Arc<Game>> {
+        self.get_players()
+            .iter()
+            .find_map(|lobby_player| lobby_player.player.get_game())
+    }
+
+    pub fn is_started(&self) -> bool {
+        self.get_game().is_some()
+    }
+
+    pub fn has_password(&self) -> bool {
+        self.settings.lock().unwrap().password.is_some()
+    }
+
+    pub fn check_password(&self, password: Option<&str>) -> bool {
+        match &self.settings.lock().unwrap().password {
+            Some(lobby_password) => password == Some(lobby_password.as_str()),
+            None => true,
+        }
+    }
+
+    pub fn get_invite_code(&self) -> &str {
+        &self.invite_code
+    }
+
+    pub fn ban_player(&self, player: &Player) {
+        self.banned_player_ids.lock().unwrap().insert(player.id);
+        if let Some(address) = player.get_address() {
+            self.banned_addresses.lock().unwrap().insert(address);
+        }
+    }
+
+    /**
+     * Invite the player. An invited player can join with the invite code without the password,
+     * once.
+     */
+    pub fn invite_player(&self, player: &Player) {
+        self.invited_player_ids.lock().unwrap().insert(player.id);
+    }
+
+    pub fn is_invited(&self, player: &Player) -> bool {
+        self.invited_player_ids.lock().unwrap().contains(&player.id)
+    }
+
+    pub fn add_chat_message(&self, message: ChatMessage) {
+        self.chat_history.lock().unwrap().push(message);
+    }
+
+    pub fn get_chat_history(&self) -> Vec<ChatMessage> {
+        self.chat_history.lock().unwrap().get_messages()
+    }
+
+    pub fn open_rematch_vote(&self) {
+        *self.rematch_vote_open.lock().unwrap() = true;
+        for lobby_player in self.get_players() {
+            lobby_player.set_rematch_vote(None);
+        }
+    }
+
+    pub fn is_rematch_vote_open(&self) -> bool {
+        *self.rematch_vote_open.lock().unwrap()
+    }
+
+    pub fn is_rematch_accepted(&self) -> bool {
+        self.get_players()
+            .iter()
+            .all(|lobby_player| lobby_player.get_rematch_vote() == Some(true))
+    }
+
+    pub fn close_rematch_vote(&self) {
+        *self.rematch_vote_open.lock().unwrap() = false;
+        for lobby_player in self.get_players() {
+            lobby_player.set_rematch_vote(None);
+            lobby_player.set_ready(false);
+        }
+    }
+
+    pub fn can_auto_start(&self) -> bool {
+        let settings = self.get_settings();
+        let players = self.get_players();
+        settings.auto_start
+            && !self.is_started()
+            && !self.is_rematch_vote_open()
+            && players.len() as u32 >= settings.auto_start_min_players
+            && players.iter().all(|lobby_player| lobby_player.get_ready())
+    }
+
+    pub fn set_auto_start_task(&self, task: Arc<JoinHandle<()>>, deadline: Instant) {
+        *self.auto_start_countdown.lock().unwrap() = Some(AutoStartCountdown { task, deadline });
+    }
+
+    pub fn has_auto_start_task(&self) -> bool {
+        self.auto_start_countdown.lock().unwrap().is_some()
+    }
+
+    pub fn get_auto_start_remaining(&self) -> Option<Duration> {
+        self.auto_start_countdown
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|countdown| countdown.deadline.saturating_duration_since(Instant::now()))
+    }
+
+    pub fn cancel_auto_start_task(&self) -> bool {
+        match self.auto_start_countdown.lock().unwrap().take() {
+            Some(countdown) => {
+                countdown.task.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /**
+     * Forget the auto-start countdown without aborting it, used by the countdown task itself once
+     * it fires.
+     */
+    pub fn clear_auto_start_task(&self) {
+        *self.auto_start_countdown.lock().unwrap() = None;
+    }
+
+    /**
+     * Starting the game of the lobby, by the leader or the auto-start countdown, happens while
+     * the guard is held, so only one of them can start it.
+     */
+    pub async fn lock_start(&self) -> MutexGuard<'_, ()> {
+        self.start.lock().await
+    }
+
+    pub fn is_banned(&self, player: &Player) -> bool {
+        if self.banned_player_ids.lock().unwrap().contains(&player.id) {
+            return true;
+        }
+        match player.get_address() {
+            Some(address) => self.banned_addresses.lock().unwrap().contains(&address),
+            None => false,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -105,6 +321,22 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn add_player_with_full_lobby_should_return_error() -> Result<(), Box<dyn std::error::Error>> {
+        let lobby = Lobby::new(0, 4, Arc::new(Player::new(0, "test1".to_string())));
+        for id in 1..4 {
+            lobby.add_player(Arc::new(Player::new(id, format!("test{}", id + 1))))?;
+        }
+        assert_eq!(
+            lobby
+                .add_player(Arc::new(Player::new(4, "test5".to_string())))
+                .err(),
+            Some(ServiceError::LobbyFull)
+        );
+        assert_eq!(lobby.get_players().len(), 4);
+        Ok(())
+    }
+
     #[test]
     fn add_player_with_test_player_already_in_lobby_should_return_error(
     ) -> Result<(), Box<dyn std::error::Error>> {
@@ -171,4 +403,124 @@ mod tests {
         assert_eq!(lobby.players.lock().unwrap().len(), 0);
         Ok(())
     }
+
+    #[test]
+    fn new_with_test_leader_should_generate_invite_code() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let lobby = Lobby::new(0, 4, Arc::new(Player::new(0, "test".to_string())));
+        assert_eq!(lobby.get_invite_code().len(), INVITE_CODE_LENGTH);
+        assert!(lobby
+            .get_invite_code()
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit()));
+        Ok(())
+    }
+
+    #[test]
+    fn check_password_without_lobby_password_should_return_true(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let lobby = Lobby::new(0, 4, Arc::new(Player::new(0, "test".to_string())));
+        assert!(lobby.check_password(None));
+        assert!(lobby.check_password(Some("1234")));
+        Ok(())
+    }
+
+    #[test]
+    fn check_password_with_lobby_password_should_only_accept_same_password(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let lobby = Lobby::new(0, 4, Arc::new(Player::new(0, "test".to_string())));
+        lobby.set_settings(LobbySettings::new(
+            String::from("test"),
+            false,
+            Some(String::from("1234")),
+        ));
+        assert!(lobby.has_password());
+        assert!(lobby.check_password(Some("1234")));
+        assert!(!lobby.check_password(Some("4321")));
+        assert!(!lobby.check_password(None));
+        Ok(())
+    }
+
+    #[test]
+    fn add_player_with_invited_player_should_use_up_invitation(
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let leader = Arc::new(Player::new(0, String::from("test1")));
+        let player = Arc::new(Player::new(1, String::from("test2")));
+        let lobby = Lobby::new(0, 4, leader);
+        lobby.invite_player(&player);
+        assert!(lobby.is_invited(&player));
+        lobby.add_player(player.clone())?;
+        assert!(!lobby.is_invited(&player));
+        Ok(())
+    }
+
+    #[test]
+    fn ban_player_with_test_player_should_ban_player() -> Result<(), Box<dyn std::error::Error>> {
+        let lobby = Lobby::new(0, 4, Arc::new(Player::new(0, "test1".to_string())));
+        let player = Player::new(1, "test2".to_string());
+        assert!(!lobby.is_banned(&player));
+        lobby.ban_player(&player);
+        assert!(lobby.is_banned(&player));
+        Ok(())
+    }
+
+    #[test]
+    fn ban_player_with_test_player_should_ban_address() -> Result<(), Box<dyn std::error::Error>> {
+        let lobby = Lobby::new(0, 4, Arc::new(Player::new(0, "test1".to_string())));
+        let player = Player::new(1, "test2".to_string());
+        player.set_address(Some(IpAddr::from([127, 0, 0, 1])));
+        lobby.ban_player(&player);
+        let other_player = Player::new(2, "test3".to_string());
+        assert!(!lobby.is_banned(&other_player));
+        other_player.set_address(Some(IpAddr::from([127, 0, 0, 1])));
+        assert!(lobby.is_banned(&other_player));
+        Ok(())
+    }
+
+    #[test]
+    fn close_rematch_vote_with_ready_players_should_reset_ready(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let leader = Arc::new(Player::new(0, String::from("test")));
+        let lobby = Lobby::new(0, 4, leader);
+        lobby.get_player(0).unwrap().set_ready(true);
+        lobby.open_rematch_vote();
+        assert!(!lobby.is_rematch_accepted());
+        lobby.get_player(0).unwrap().set_rematch_vote(Some(true));
+        assert!(lobby.is_rematch_accepted());
+        lobby.close_rematch_vote();
+        assert!(!lobby.is_rematch_vote_open());
+        assert!(!lobby.get_player(0).unwrap().get_ready());
+        Ok(())
+    }
+
+    #[test]
+    fn can_auto_start_with_all_players_ready_should_return_true(
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let lobby = Lobby::new(0, 4, Arc::new(Player::new(0, String::from("test1"))));
+        lobby.add_player(Arc::new(Player::new(1, String::from("test2"))))?;
+        lobby.set_settings(LobbySettings {
+            auto_start: true,
+            ..lobby.get_settings()
+        });
+        for lobby_player in lobby.get_players() {
+            lobby_player.set_ready(true);
+        }
+        assert!(lobby.can_auto_start());
+        lobby.get_player(1).unwrap().set_ready(false);
+        assert!(!lobby.can_auto_start());
+        Ok(())
+    }
+
+    #[test]
+    fn can_auto_start_below_minimum_players_should_return_false(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let lobby = Lobby::new(0, 4, Arc::new(Player::new(0, String::from("test"))));
+        lobby.set_settings(LobbySettings {
+            auto_start: true,
+            ..lobby.get_settings()
+        });
+        lobby.get_player(0).unwrap().set_ready(true);
+        assert!(!lobby.can_auto_start());
+        Ok(())
+    }
 }
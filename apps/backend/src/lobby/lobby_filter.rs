@@ -0,0 +1,94 @@
+use super::lobby::Lobby;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LobbyFilter {
+    pub has_space: bool,
+    pub not_started: bool,
+    pub dictionary: Option<String>,
+    pub language: Option<String>,
+}
+
+impl LobbyFilter {
+    pub fn matches(&self, lobby: &Lobby) -> bool {
+        if self.has_space && lobby.is_full() {
+            return false;
+        }
+        if self.not_started && lobby.is_started() {
+            return false;
+        }
+        if let Some(dictionary) = &self.dictionary {
+            if !lobby.get_dictionary().eq_ignore_ascii_case(dictionary) {
+                return false;
+            }
+        }
+        if let Some(language) = &self.language {
+            if !lobby.get_language().eq_ignore_ascii_case(language) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{game::game::Game, lobby::lobby_settings::LobbySettings, player::Player};
+
+    use super::*;
+
+    #[test]
+    fn matches_with_default_filter_should_return_true() {
+        let lobby = Lobby::new(0, 4, Arc::new(Player::new(0, String::from("test"))));
+        assert!(LobbyFilter::default().matches(&lobby));
+    }
+
+    #[test]
+    fn matches_with_has_space_and_full_lobby_should_return_false() {
+        let lobby = Lobby::new(0, 4, Arc::new(Player::new(0, String::from("test"))));
+        for id in 1..4 {
+            lobby
+                .add_player(Arc::new(Player::new(id, String::from("test"))))
+                .unwrap();
+        }
+        let filter = LobbyFilter {
+            has_space: true,
+            ..Default::default()
+        };
+        assert!(!filter.matches(&lobby));
+    }
+
+    #[test]
+    fn matches_with_not_started_and_started_lobby_should_return_false() {
+        let leader = Arc::new(Player::new(0, String::from("test")));
+        let lobby = Lobby::new(0, 4, leader.clone());
+        leader.set_game(Some(Arc::new(Game::new(0, vec![leader.clone()]))));
+        let filter = LobbyFilter {
+            not_started: true,
+            ..Default::default()
+        };
+        assert!(!filter.matches(&lobby));
+    }
+
+    #[test]
+    fn matches_with_language_and_dictionary_should_compare_lobby_settings() {
+        let lobby = Lobby::new(0, 4, Arc::new(Player::new(0, String::from("test"))));
+        lobby.set_settings(LobbySettings {
+            language: String::from("zh"),
+            dictionary: String::from("basic"),
+            ..LobbySettings::new(String::from("test"), false, None)
+        });
+        let filter = LobbyFilter {
+            language: Some(String::from("ZH")),
+            dictionary: Some(String::from("basic")),
+            ..Default::default()
+        };
+        assert!(filter.matches(&lobby));
+        let filter = LobbyFilter {
+            language: Some(String::from("en")),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&lobby));
+    }
+}
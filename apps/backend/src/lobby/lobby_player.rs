@@ -7,6 +7,7 @@ use crate::player::Player;
 #[derive(Debug)]
 pub struct LobbyPlayer {
     ready: Mutex<bool>,
+    rematch_vote: Mutex<Option<bool>>,
     pub player: Arc<Player>,
 }
 
@@ -20,6 +21,7 @@ impl LobbyPlayer {
     pub fn new(player: Arc<Player>) -> Self {
         Self {
             ready: Mutex::new(false),
+            rematch_vote: Mutex::new(None),
             player,
         }
     }
@@ -31,6 +33,14 @@ impl LobbyPlayer {
     pub fn set_ready(&self, ready: bool) {
         *self.ready.lock().unwrap() = ready;
     }
+
+    pub fn get_rematch_vote(&self) -> Option<bool> {
+        *self.rematch_vote.lock().unwrap()
+    }
+
+    pub fn set_rematch_vote(&self, rematch_vote: Option<bool>) {
+        *self.rematch_vote.lock().unwrap() = rematch_vote;
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,154 @@
+use crate::error::ServiceError;
+use std::time::Duration;
+
+pub const MAX_LOBBY_NAME_LENGTH: usize = 32;
+pub const MAX_LOBBY_PASSWORD_LENGTH: usize = 32;
+pub const MAX_LOBBY_LANGUAGE_LENGTH: usize = 16;
+pub const MAX_LOBBY_DICTIONARY_LENGTH: usize = 32;
+pub const DEFAULT_LOBBY_LANGUAGE: &str = "en";
+pub const DEFAULT_LOBBY_DICTIONARY: &str = "default";
+pub const MAX_SPECTATOR_DELAY: Duration = Duration::from_secs(120);
+pub const DEFAULT_AUTO_START_MIN_PLAYERS: u32 = 2;
+pub const AUTO_START_COUNTDOWN: Duration = Duration::from_secs(10);
+pub const DEFAULT_AFK_TURNS: u32 = 2;
+pub const DEFAULT_AFK_REMOVAL_TURNS: u32 = 4;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LobbySettings {
+    pub name: String,
+    pub private: bool,
+    pub password: Option<String>,
+    pub language: String,
+    pub dictionary: String,
+    pub allow_spectators: bool,
+    pub spectator_delay: Duration,
+    pub auto_start: bool,
+    pub auto_start_min_players: u32,
+    /** Consecutive timed-out turns before a player is marked AFK. 0 disables it. */
+    pub afk_turns: u32,
+    /** Consecutive timed-out turns before a player is removed from the game. 0 disables it. */
+    pub afk_removal_turns: u32,
+}
+
+impl LobbySettings {
+    pub fn new(name: String, private: bool, password: Option<String>) -> Self {
+        Self {
+            name,
+            private,
+            password,
+            language: String::from(DEFAULT_LOBBY_LANGUAGE),
+            dictionary: String::from(DEFAULT_LOBBY_DICTIONARY),
+            allow_spectators: true,
+            spectator_delay: Duration::ZERO,
+            auto_start: false,
+            auto_start_min_players: DEFAULT_AUTO_START_MIN_PLAYERS,
+            afk_turns: DEFAULT_AFK_TURNS,
+            afk_removal_turns: DEFAULT_AFK_REMOVAL_TURNS,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), ServiceError> {
+        if self.name.trim().is_empty() || self.name.chars().count() > MAX_LOBBY_NAME_LENGTH {
+            return Err(ServiceError::InvalidLobbyName);
+        }
+        if let Some(password) = &self.password {
+            if password.is_empty() || password.chars().count() > MAX_LOBBY_PASSWORD_LENGTH {
+                return Err(ServiceError::InvalidLobbyPassword);
+            }
+        }
+        if self.language.trim().is_empty()
+            || self.language.chars().count() > MAX_LOBBY_LANGUAGE_LENGTH
+        {
+            return Err(ServiceError::InvalidLobbyLanguage);
+        }
+        if self.dictionary.trim().is_empty()
+            || self.dictionary.chars().count() > MAX_LOBBY_DICTIONARY_LENGTH
+        {
+            return Err(ServiceError::InvalidLobbyDictionary);
+        }
+        if self.spectator_delay > MAX_SPECTATOR_DELAY {
+            return Err(ServiceError::InvalidSpectatorDelay);
+        }
+        if self.auto_start_min_players == 0 {
+            return Err(ServiceError::InvalidAutoStartMinPlayers);
+        }
+        if self.afk_turns != 0
+            && self.afk_removal_turns != 0
+            && self.afk_removal_turns < self.afk_turns
+        {
+            return Err(ServiceError::InvalidAfkRemovalTurns);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_with_valid_settings_should_return_ok() {
+        let settings = LobbySettings::new(String::from("test"), true, Some(String::from("1234")));
+        assert!(settings.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_with_blank_name_should_return_error() {
+        let settings = LobbySettings::new(String::from("   "), false, None);
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn validate_with_too_long_name_should_return_error() {
+        let settings = LobbySettings::new("a".repeat(MAX_LOBBY_NAME_LENGTH + 1), false, None);
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn validate_with_empty_password_should_return_error() {
+        let settings = LobbySettings::new(String::from("test"), false, Some(String::new()));
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn validate_with_blank_language_should_return_error() {
+        let settings = LobbySettings {
+            language: String::from(" "),
+            ..LobbySettings::new(String::from("test"), false, None)
+        };
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn validate_with_too_long_spectator_delay_should_return_error() {
+        let settings = LobbySettings {
+            spectator_delay: MAX_SPECTATOR_DELAY + Duration::from_secs(1),
+            ..LobbySettings::new(String::from("test"), false, None)
+        };
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn validate_with_zero_auto_start_min_players_should_return_error() {
+        let settings = LobbySettings {
+            auto_start_min_players: 0,
+            ..LobbySettings::new(String::from("test"), false, None)
+        };
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn validate_with_afk_removal_before_afk_should_return_error() {
+        let settings = LobbySettings {
+            afk_turns: 3,
+            afk_removal_turns: 2,
+            ..LobbySettings::new(String::from("test"), false, None)
+        };
+        assert!(settings.validate().is_err());
+        let settings = LobbySettings {
+            afk_turns: 0,
+            ..settings
+        };
+        assert!(settings.validate().is_ok());
+    }
+}
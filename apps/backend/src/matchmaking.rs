@@ -0,0 +1,3 @@
+pub mod matchmaking_key;
+pub mod matchmaking_result;
+pub mod wait_estimator;
@@ -0,0 +1,43 @@
+pub const RATING_BAND_WIDTH: u32 = 200;
+
+pub fn rating_band(rating: u32) -> u32 {
+    rating / RATING_BAND_WIDTH
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MatchmakingKey {
+    pub max_players: u32,
+    pub language: String,
+    pub rating_band: u32,
+}
+
+impl MatchmakingKey {
+    pub fn new(max_players: u32, language: String, rating: u32) -> Self {
+        Self {
+            max_players,
+            language: language.to_lowercase(),
+            rating_band: rating_band(rating),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_with_close_ratings_should_share_rating_band() {
+        assert_eq!(
+            MatchmakingKey::new(4, String::from("en"), 1500),
+            MatchmakingKey::new(4, String::from("EN"), 1550)
+        );
+    }
+
+    #[test]
+    fn new_with_distant_ratings_should_not_share_rating_band() {
+        assert_ne!(
+            MatchmakingKey::new(4, String::from("en"), 1000),
+            MatchmakingKey::new(4, String::from("en"), 1500)
+        );
+    }
+}
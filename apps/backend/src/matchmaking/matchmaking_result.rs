@@ -0,0 +1,12 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::lobby::lobby::Lobby;
+
+#[derive(Debug, Clone)]
+pub enum MatchmakingResult {
+    Queued {
+        estimated_wait: Duration,
+        queue_size: u32,
+    },
+    Matched(Arc<Lobby>),
+}
@@ -0,0 +1,53 @@
+use std::{collections::VecDeque, time::Duration};
+
+pub const WAIT_HISTORY_SIZE: usize = 20;
+pub const DEFAULT_ESTIMATED_WAIT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Default)]
+pub struct WaitEstimator {
+    waits: VecDeque<Duration>,
+}
+
+impl WaitEstimator {
+    pub fn record(&mut self, wait: Duration) {
+        if self.waits.len() == WAIT_HISTORY_SIZE {
+            self.waits.pop_front();
+        }
+        self.waits.push_back(wait);
+    }
+
+    pub fn estimate(&self) -> Duration {
+        if self.waits.is_empty() {
+            return DEFAULT_ESTIMATED_WAIT;
+        }
+        self.waits.iter().sum::<Duration>() / self.waits.len() as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimate_without_history_should_return_default() {
+        assert_eq!(WaitEstimator::default().estimate(), DEFAULT_ESTIMATED_WAIT);
+    }
+
+    #[test]
+    fn estimate_with_history_should_return_average() {
+        let mut estimator = WaitEstimator::default();
+        estimator.record(Duration::from_secs(10));
+        estimator.record(Duration::from_secs(20));
+        assert_eq!(estimator.estimate(), Duration::from_secs(15));
+    }
+
+    #[test]
+    fn record_with_full_history_should_drop_oldest_wait() {
+        let mut estimator = WaitEstimator::default();
+        estimator.record(Duration::from_secs(1000));
+        for _ in 0..WAIT_HISTORY_SIZE {
+            estimator.record(Duration::from_secs(10));
+        }
+        assert_eq!(estimator.estimate(), Duration::from_secs(10));
+    }
+}
@@ -1,6 +1,10 @@
+pub mod chat;
 pub mod control;
 pub mod error;
+pub mod friend;
 pub mod game;
 pub mod lobby;
+pub mod matchmaking;
 pub mod player;
 pub mod state;
+pub mod stats;
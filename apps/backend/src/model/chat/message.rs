@@ -0,0 +1,40 @@
+include!(concat!(env!("OUT_DIR"), "/chat.message.rs"));
+
+impl From<&crate::chat::chat_message::ChatMessage> for ChatMessage {
+    fn from(message: &crate::chat::chat_message::ChatMessage) -> Self {
+        Self {
+            sender: Some(crate::model::player::player::Player::from(
+                message.sender.clone(),
+            )),
+            content: message.content.clone(),
+            timestamp: message.timestamp,
+        }
+    }
+}
+
+impl From<&Vec<crate::chat::chat_message::ChatMessage>> for ChatMessages {
+    fn from(messages: &Vec<crate::chat::chat_message::ChatMessage>) -> Self {
+        Self {
+            messages: messages.iter().map(ChatMessage::from).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::player::Player;
+
+    use super::*;
+
+    #[test]
+    fn from_chat_message_return_chat_message() {
+        let player = Arc::new(Player::new(0, String::from("test")));
+        let message = crate::chat::chat_message::ChatMessage::new(player, String::from("hello"));
+        let proto = ChatMessage::from(&message);
+        assert_eq!(proto.sender.unwrap().id, 0);
+        assert_eq!(proto.content, "hello");
+        assert_eq!(proto.timestamp, message.timestamp);
+    }
+}
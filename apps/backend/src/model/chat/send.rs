@@ -0,0 +1,3 @@
+include!(concat!(env!("OUT_DIR"), "/chat.send.rs"));
+
+impl Eq for SendRequest {}
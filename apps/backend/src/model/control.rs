@@ -1,3 +1,5 @@
 pub mod connect;
 pub mod disconnect;
 pub mod heartbeat;
+pub mod ping;
+pub mod reconnect;
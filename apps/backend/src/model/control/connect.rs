@@ -1,3 +1,18 @@
 include!(concat!(env!("OUT_DIR"), "/control.connect.rs"));
 
 impl Eq for ConnectRequest {}
+
+impl From<crate::naming::name_rejection::NameRejection> for NameRejection {
+    fn from(name_rejection: crate::naming::name_rejection::NameRejection) -> Self {
+        match name_rejection {
+            crate::naming::name_rejection::NameRejection::Empty => NameRejection::Empty,
+            crate::naming::name_rejection::NameRejection::TooLong => NameRejection::TooLong,
+            crate::naming::name_rejection::NameRejection::InvalidCharacters => {
+                NameRejection::InvalidCharacters
+            }
+            crate::naming::name_rejection::NameRejection::Reserved => NameRejection::Reserved,
+            crate::naming::name_rejection::NameRejection::Profanity => NameRejection::Profanity,
+            crate::naming::name_rejection::NameRejection::Taken => NameRejection::Taken,
+        }
+    }
+}
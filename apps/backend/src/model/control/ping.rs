@@ -0,0 +1,3 @@
+include!(concat!(env!("OUT_DIR"), "/control.ping.rs"));
+
+impl Eq for PongRequest {}
@@ -0,0 +1,3 @@
+include!(concat!(env!("OUT_DIR"), "/control.reconnect.rs"));
+
+impl Eq for ReconnectRequest {}
@@ -1 +1,97 @@
 include!(concat!(env!("OUT_DIR"), "/error.error.rs"));
+
+use crate::error::ServiceError;
+
+impl From<&ServiceError> for ErrorCode {
+    fn from(err: &ServiceError) -> Self {
+        match err {
+            ServiceError::Internal(_) => ErrorCode::Internal,
+            ServiceError::UnsupportedProtocolVersion(_) => ErrorCode::UnsupportedProtocolVersion,
+            ServiceError::PlayerNotFound => ErrorCode::PlayerNotFound,
+            ServiceError::AlreadyConnected => ErrorCode::AlreadyConnected,
+            ServiceError::AccountAlreadyConnected => ErrorCode::AccountAlreadyConnected,
+            ServiceError::InvalidSessionToken => ErrorCode::InvalidSessionToken,
+            ServiceError::UnknownPing => ErrorCode::UnknownPing,
+            ServiceError::NotInLobby => ErrorCode::NotInLobby,
+            ServiceError::AlreadyInLobby => ErrorCode::AlreadyInLobby,
+            ServiceError::LobbyNotFound => ErrorCode::LobbyNotFound,
+            ServiceError::LobbyFull => ErrorCode::LobbyFull,
+            ServiceError::InvalidMaxPlayers => ErrorCode::InvalidMaxPlayers,
+            ServiceError::NotLeader => ErrorCode::NotLeader,
+            ServiceError::LeaderCannotBeKicked => ErrorCode::LeaderCannotBeKicked,
+            ServiceError::BannedFromLobby => ErrorCode::BannedFromLobby,
+            ServiceError::WrongLobbyPassword => ErrorCode::WrongLobbyPassword,
+            ServiceError::NotAllReady => ErrorCode::NotAllReady,
+            ServiceError::RematchInProgress => ErrorCode::RematchInProgress,
+            ServiceError::NoRematchInProgress => ErrorCode::NoRematchInProgress,
+            ServiceError::InvalidLobbyName => ErrorCode::InvalidLobbyName,
+            ServiceError::InvalidLobbyPassword => ErrorCode::InvalidLobbyPassword,
+            ServiceError::InvalidLobbyLanguage => ErrorCode::InvalidLobbyLanguage,
+            ServiceError::InvalidLobbyDictionary => ErrorCode::InvalidLobbyDictionary,
+            ServiceError::InvalidSpectatorDelay => ErrorCode::InvalidSpectatorDelay,
+            ServiceError::InvalidAutoStartMinPlayers => ErrorCode::InvalidAutoStartMinPlayers,
+            ServiceError::InvalidAfkRemovalTurns => ErrorCode::InvalidAfkRemovalTurns,
+            ServiceError::GameNotFound => ErrorCode::GameNotFound,
+            ServiceError::GameNotStarted => ErrorCode::GameNotStarted,
+            ServiceError::GameAlreadyStarted => ErrorCode::GameAlreadyStarted,
+            ServiceError::NotInGame => ErrorCode::NotInGame,
+            ServiceError::AlreadyInGame => ErrorCode::AlreadyInGame,
+            ServiceError::NotYourTurn => ErrorCode::NotYourTurn,
+            ServiceError::AlreadyShuffled => ErrorCode::AlreadyShuffled,
+            ServiceError::TileOutOfBoard => ErrorCode::TileOutOfBoard,
+            ServiceError::CardAlreadyUsed => ErrorCode::CardAlreadyUsed,
+            ServiceError::CardNotOnBoard => ErrorCode::CardNotOnBoard,
+            ServiceError::CardNotPlacedThisTurn => ErrorCode::CardNotPlacedThisTurn,
+            ServiceError::InvalidWord => ErrorCode::InvalidWord,
+            ServiceError::SpectatorsNotAllowed => ErrorCode::SpectatorsNotAllowed,
+            ServiceError::AlreadySpectating => ErrorCode::AlreadySpectating,
+            ServiceError::NotSpectating => ErrorCode::NotSpectating,
+            ServiceError::CannotVoteKickSelf => ErrorCode::CannotVoteKickSelf,
+            ServiceError::TargetNotInGame => ErrorCode::TargetNotInGame,
+            ServiceError::TargetNotAfk => ErrorCode::TargetNotAfk,
+            ServiceError::EmptyMessage => ErrorCode::EmptyMessage,
+            ServiceError::MessageTooLong => ErrorCode::MessageTooLong,
+            ServiceError::SendingTooFast => ErrorCode::SendingTooFast,
+            ServiceError::NotInLobbyOrGame => ErrorCode::NotInLobbyOrGame,
+            ServiceError::AlreadyInQueue => ErrorCode::AlreadyInQueue,
+            ServiceError::NotInQueue => ErrorCode::NotInQueue,
+            ServiceError::EmptyMatchmakingGroup => ErrorCode::EmptyMatchmakingGroup,
+            ServiceError::NoAccount => ErrorCode::NoAccount,
+            ServiceError::AccountNotFound => ErrorCode::AccountNotFound,
+            ServiceError::InvalidUsername => ErrorCode::InvalidUsername,
+            ServiceError::InvalidPassword => ErrorCode::InvalidPassword,
+            ServiceError::UsernameTaken => ErrorCode::UsernameTaken,
+            ServiceError::InvalidCredentials => ErrorCode::InvalidCredentials,
+            ServiceError::PasswordRequired => ErrorCode::PasswordRequired,
+            ServiceError::CannotFriendSelf => ErrorCode::CannotFriendSelf,
+            ServiceError::AlreadyFriends => ErrorCode::AlreadyFriends,
+            ServiceError::FriendRequestAlreadySent => ErrorCode::FriendRequestAlreadySent,
+            ServiceError::FriendRequestNotFound => ErrorCode::FriendRequestNotFound,
+            ServiceError::FriendNotFound => ErrorCode::FriendNotFound,
+            ServiceError::NotFriends => ErrorCode::NotFriends,
+            ServiceError::FriendOffline => ErrorCode::FriendOffline,
+            ServiceError::FriendAlreadyInLobby => ErrorCode::FriendAlreadyInLobby,
+        }
+    }
+}
+
+impl From<ServiceError> for Error {
+    fn from(err: ServiceError) -> Self {
+        Self {
+            message: err.to_string(),
+            code: ErrorCode::from(&err) as i32,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_service_error_should_set_code_and_message() {
+        let err = Error::from(ServiceError::NotYourTurn);
+        assert_eq!(err.code, ErrorCode::NotYourTurn as i32);
+        assert_eq!(err.message, ServiceError::NotYourTurn.to_string());
+    }
+}
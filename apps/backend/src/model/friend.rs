@@ -0,0 +1,8 @@
+pub mod accept;
+pub mod add;
+pub mod broadcast;
+#[allow(clippy::module_inception)]
+pub mod friend;
+pub mod invite;
+pub mod list;
+pub mod remove;
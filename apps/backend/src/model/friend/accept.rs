@@ -0,0 +1,3 @@
+include!(concat!(env!("OUT_DIR"), "/friend.accept.rs"));
+
+impl Eq for AcceptFriendRequest {}
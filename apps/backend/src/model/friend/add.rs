@@ -0,0 +1,3 @@
+include!(concat!(env!("OUT_DIR"), "/friend.add.rs"));
+
+impl Eq for AddFriendRequest {}
@@ -0,0 +1,44 @@
+use crate::account::friendship::FriendStatus as AccountFriendStatus;
+
+include!(concat!(env!("OUT_DIR"), "/friend.friend.rs"));
+
+impl From<AccountFriendStatus> for FriendStatus {
+    fn from(status: AccountFriendStatus) -> Self {
+        match status {
+            AccountFriendStatus::Accepted => FriendStatus::Accepted,
+            AccountFriendStatus::Incoming => FriendStatus::Incoming,
+            AccountFriendStatus::Outgoing => FriendStatus::Outgoing,
+        }
+    }
+}
+
+impl From<crate::player::Presence> for Presence {
+    fn from(presence: crate::player::Presence) -> Self {
+        match presence {
+            crate::player::Presence::Offline => Presence::Offline,
+            crate::player::Presence::Online => Presence::Online,
+            crate::player::Presence::InLobby => Presence::InLobby,
+            crate::player::Presence::InGame => Presence::InGame,
+        }
+    }
+}
+
+impl From<&crate::account::friend::Friend> for Friend {
+    fn from(friend: &crate::account::friend::Friend) -> Self {
+        Self {
+            account_id: friend.account.id,
+            username: friend.account.username.clone(),
+            status: FriendStatus::from(friend.status) as i32,
+            presence: Presence::from(friend.presence) as i32,
+            lobby_id: friend.lobby_id,
+        }
+    }
+}
+
+impl From<&Vec<crate::account::friend::Friend>> for Friends {
+    fn from(friends: &Vec<crate::account::friend::Friend>) -> Self {
+        Self {
+            friends: friends.iter().map(Friend::from).collect(),
+        }
+    }
+}
@@ -0,0 +1,3 @@
+include!(concat!(env!("OUT_DIR"), "/friend.invite.rs"));
+
+impl Eq for InviteRequest {}
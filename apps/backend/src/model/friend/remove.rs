@@ -0,0 +1,3 @@
+include!(concat!(env!("OUT_DIR"), "/friend.remove.rs"));
+
+impl Eq for RemoveFriendRequest {}
@@ -5,9 +5,13 @@ pub mod card;
 pub mod cards;
 pub mod exit;
 pub mod finish_turn;
+pub mod get_board;
 pub mod get_new_card;
 pub mod hand_card;
 pub mod set_tile;
+pub mod spectate;
 pub mod start;
+pub mod stop_spectating;
 pub mod tile;
+pub mod vote_kick;
 pub mod words;
@@ -22,6 +22,22 @@ impl From<&crate::game::board::Board> for Board {
     }
 }
 
+impl BoardDelta {
+    pub fn new(sequence: u32, board: &crate::game::board::Board, cells: &[(usize, usize)]) -> Self {
+        Self {
+            sequence,
+            cells: cells
+                .iter()
+                .map(|&(x, y)| Cell {
+                    x: x as u32,
+                    y: y as u32,
+                    tile: board.get_tile(x, y).map(Tile::from),
+                })
+                .collect(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
@@ -62,4 +78,17 @@ mod tests {
             assert_eq!(board.rows[i].columns[25].tile, None);
         }
     }
+
+    #[test]
+    fn new_board_delta_should_contain_given_cells() {
+        let mut board = crate::game::board::Board::new();
+        let player = Arc::new(Player::new(0, String::from("test")));
+        board.set_tile(1, 2, Some(crate::game::tile::Tile::new('a', player, 0)));
+        let delta = BoardDelta::new(3, &board, &[(1, 2), (3, 4)]);
+        assert_eq!(delta.sequence, 3);
+        assert_eq!(delta.cells.len(), 2);
+        assert_eq!(delta.cells[0].tile.clone().unwrap().char, String::from("a"));
+        assert_eq!((delta.cells[1].x, delta.cells[1].y), (3, 4));
+        assert_eq!(delta.cells[1].tile, None);
+    }
 }
@@ -0,0 +1 @@
+include!(concat!(env!("OUT_DIR"), "/game.get_board.rs"));
@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use crate::game::game::Game;
+
+include!(concat!(env!("OUT_DIR"), "/game.spectate.rs"));
+
+impl Eq for SpectateRequest {}
+
+impl From<&Arc<Game>> for SpectateResponse {
+    fn from(game: &Arc<Game>) -> Self {
+        Self {
+            success: true,
+            board: Some(crate::model::game::board::Board::from(
+                &*game.get_board().lock().unwrap(),
+            )),
+            players: Some(crate::model::player::players::Players::from(
+                &game.get_players(),
+            )),
+            spectators: Some(crate::model::player::players::Players::from(
+                &game.get_spectators(),
+            )),
+            current_player: Some(crate::model::player::player::Player::from(
+                game.get_player_in_this_turn(),
+            )),
+            next_player: game
+                .get_next_turn_player()
+                .map(crate::model::player::player::Player::from),
+            board_sequence: game.get_board_sequence(),
+        }
+    }
+}
@@ -0,0 +1,3 @@
+include!(concat!(env!("OUT_DIR"), "/game.vote_kick.rs"));
+
+impl Eq for VoteKickRequest {}
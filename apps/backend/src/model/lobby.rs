@@ -1,7 +1,10 @@
 pub mod broadcast;
 pub mod create;
 pub mod join;
+pub mod kick;
 pub mod list;
 pub mod lobby;
 pub mod quit;
 pub mod ready;
+pub mod rematch;
+pub mod update;
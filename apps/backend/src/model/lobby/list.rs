@@ -1,9 +1,11 @@
 use std::sync::Arc;
 
-use crate::lobby::lobby::Lobby;
+use crate::lobby::{lobby::Lobby, lobby_filter::LobbyFilter};
 
 include!(concat!(env!("OUT_DIR"), "/lobby.list.rs"));
 
+impl Eq for ListRequest {}
+
 impl From<Vec<Arc<Lobby>>> for LobbyInfos {
     fn from(lobbies: Vec<Arc<Lobby>>) -> Self {
         let mut lobby_infos = Vec::new();
@@ -16,10 +18,38 @@ impl From<Vec<Arc<Lobby>>> for LobbyInfos {
 
 impl From<Arc<Lobby>> for LobbyInfo {
     fn from(lobby: Arc<Lobby>) -> Self {
+        LobbyInfo::from(lobby.as_ref())
+    }
+}
+
+impl From<&Lobby> for LobbyInfo {
+    fn from(lobby: &Lobby) -> Self {
         Self {
             id: lobby.get_id(),
             max_players: lobby.get_max_players(),
             current_players: lobby.get_players().len() as u32,
+            name: lobby.get_name(),
+            has_password: lobby.has_password(),
+            language: lobby.get_language(),
+            dictionary: lobby.get_dictionary(),
+            started: lobby.is_started(),
+            allow_spectators: lobby.get_settings().allow_spectators,
+            rating_bands: lobby
+                .get_players()
+                .iter()
+                .map(|lobby_player| lobby_player.player.get_rating_band())
+                .collect(),
+        }
+    }
+}
+
+impl From<&ListRequest> for LobbyFilter {
+    fn from(req: &ListRequest) -> Self {
+        Self {
+            has_space: req.has_space,
+            not_started: req.not_started,
+            dictionary: req.dictionary.clone().filter(|x| !x.is_empty()),
+            language: req.language.clone().filter(|x| !x.is_empty()),
         }
     }
 }
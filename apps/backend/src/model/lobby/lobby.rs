@@ -12,13 +12,38 @@ impl From<&crate::lobby::lobby::Lobby> for Lobby {
     fn from(lobby: &crate::lobby::lobby::Lobby) -> Self {
         let mut players = Vec::new();
         for player in lobby.get_players() {
-            players.push(crate::model::player::player::Player::from(
-                player.player.clone(),
-            ));
+            players.push(LobbyPlayer {
+                id: player.player.id,
+                name: player.player.name.clone(),
+                ready: player.get_ready(),
+                leader: player.player == lobby.leader,
+                rematch_vote: player.get_rematch_vote(),
+                rating: player.player.get_rating(),
+                rating_band: player.player.get_rating_band(),
+                rtt: player.player.get_rtt().map(|rtt| rtt.as_millis() as u32),
+                idle: player.player.is_idle(),
+            });
         }
+        let settings = lobby.get_settings();
         Self {
             id: lobby.get_id(),
             players,
+            name: lobby.get_name(),
+            is_private: lobby.is_private(),
+            has_password: lobby.has_password(),
+            invite_code: lobby.get_invite_code().to_string(),
+            language: lobby.get_language(),
+            dictionary: lobby.get_dictionary(),
+            rematch_vote_open: lobby.is_rematch_vote_open(),
+            allow_spectators: settings.allow_spectators,
+            spectator_delay: settings.spectator_delay.as_secs() as u32,
+            auto_start: settings.auto_start,
+            auto_start_min_players: settings.auto_start_min_players,
+            afk_turns: settings.afk_turns,
+            afk_removal_turns: settings.afk_removal_turns,
+            auto_start_countdown: lobby
+                .get_auto_start_remaining()
+                .map(|remaining| remaining.as_secs() as u32),
         }
     }
 }
@@ -0,0 +1,3 @@
+include!(concat!(env!("OUT_DIR"), "/lobby.rematch.rs"));
+
+impl Eq for RematchRequest {}
@@ -0,0 +1,3 @@
+include!(concat!(env!("OUT_DIR"), "/lobby.update.rs"));
+
+impl Eq for UpdateRequest {}
@@ -0,0 +1,3 @@
+include!(concat!(env!("OUT_DIR"), "/matchmaking.quick_play.rs"));
+
+impl Eq for QuickPlayRequest {}
@@ -7,6 +7,11 @@ impl From<Arc<crate::player::Player>> for Player {
         Self {
             id: player.id,
             name: player.name.clone(),
+            account_id: player.get_account_id(),
+            rating: player.get_rating(),
+            rating_band: player.get_rating_band(),
+            rtt: player.get_rtt().map(|rtt| rtt.as_millis() as u32),
+            idle: player.is_idle(),
         }
     }
 }
@@ -16,6 +21,11 @@ impl From<Arc<crate::game::game_player::GamePlayer>> for Player {
         Self {
             id: player.player.id,
             name: player.player.name.clone(),
+            account_id: player.player.get_account_id(),
+            rating: player.player.get_rating(),
+            rating_band: player.player.get_rating_band(),
+            rtt: player.player.get_rtt().map(|rtt| rtt.as_millis() as u32),
+            idle: player.player.is_idle(),
         }
     }
 }
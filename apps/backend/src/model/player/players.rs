@@ -14,3 +14,14 @@ impl From<&Vec<Arc<GamePlayer>>> for Players {
         }
     }
 }
+
+impl From<&Vec<Arc<crate::player::Player>>> for Players {
+    fn from(players: &Vec<Arc<crate::player::Player>>) -> Self {
+        Self {
+            players: players
+                .iter()
+                .map(|x| super::player::Player::from(x.clone()))
+                .collect(),
+        }
+    }
+}
@@ -1,4 +1,7 @@
 pub enum State {
     LobbyBroadcast = 0,
     GameBroadcast = 1,
+    LobbyListBroadcast = 2,
+    FriendBroadcast = 3,
+    Ping = 4,
 }
@@ -0,0 +1,5 @@
+pub mod history;
+pub mod leaderboard;
+pub mod match_record;
+pub mod player_stats;
+pub mod profile;
@@ -0,0 +1,3 @@
+include!(concat!(env!("OUT_DIR"), "/stats.history.rs"));
+
+impl Eq for HistoryRequest {}
@@ -0,0 +1,18 @@
+use crate::matchmaking::matchmaking_key::rating_band;
+
+include!(concat!(env!("OUT_DIR"), "/stats.leaderboard.rs"));
+
+impl Eq for LeaderboardRequest {}
+
+impl From<&crate::stats::rating_repository::LeaderboardEntry> for LeaderboardEntry {
+    fn from(entry: &crate::stats::rating_repository::LeaderboardEntry) -> Self {
+        Self {
+            rank: entry.rank,
+            account_id: entry.account_id,
+            username: entry.username.clone(),
+            rating: entry.rating,
+            rating_band: rating_band(entry.rating),
+            games_played: entry.games_played,
+        }
+    }
+}
@@ -0,0 +1,26 @@
+include!(concat!(env!("OUT_DIR"), "/stats.match_record.rs"));
+
+impl From<&crate::stats::match_record::MatchPlayerRecord> for MatchPlayer {
+    fn from(player: &crate::stats::match_record::MatchPlayerRecord) -> Self {
+        Self {
+            account_id: player.account_id,
+            name: player.name.clone(),
+            score: player.score,
+            words: player.words.clone(),
+            winner: player.winner,
+        }
+    }
+}
+
+impl From<&crate::stats::match_record::MatchRecord> for MatchRecord {
+    fn from(record: &crate::stats::match_record::MatchRecord) -> Self {
+        Self {
+            id: record.id,
+            started_at: record.started_at,
+            duration: record.duration.as_secs(),
+            language: record.language.clone(),
+            dictionary: record.dictionary.clone(),
+            players: record.players.iter().map(MatchPlayer::from).collect(),
+        }
+    }
+}
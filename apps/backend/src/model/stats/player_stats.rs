@@ -0,0 +1,12 @@
+include!(concat!(env!("OUT_DIR"), "/stats.player_stats.rs"));
+
+impl From<&crate::stats::player_stats::PlayerStats> for PlayerStats {
+    fn from(stats: &crate::stats::player_stats::PlayerStats) -> Self {
+        Self {
+            games_played: stats.games_played,
+            wins: stats.wins,
+            average_score: stats.average_score,
+            longest_word: stats.longest_word.clone(),
+        }
+    }
+}
@@ -0,0 +1,3 @@
+include!(concat!(env!("OUT_DIR"), "/stats.profile.rs"));
+
+impl Eq for ProfileRequest {}
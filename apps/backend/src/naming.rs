@@ -0,0 +1,2 @@
+pub mod name_policy;
+pub mod name_rejection;
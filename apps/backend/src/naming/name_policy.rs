@@ -0,0 +1,143 @@
+use std::collections::HashSet;
+#[cfg(not(test))]
+use std::error::Error;
+
+#[cfg(not(test))]
+use tokio::{
+    fs::File,
+    io::{AsyncBufReadExt, BufReader},
+};
+
+use super::name_rejection::NameRejection;
+
+pub const MAX_NAME_LENGTH: usize = 16;
+pub const NAME_SPECIAL_CHARACTERS: [char; 3] = [' ', '_', '-'];
+#[cfg(not(test))]
+pub const RESERVED_NAMES_PATH: &str = "assets/reserved_names.txt";
+#[cfg(not(test))]
+pub const PROFANITY_PATH: &str = "assets/profanity.txt";
+
+#[derive(Debug, Default)]
+pub struct NamePolicy {
+    reserved_names: HashSet<String>,
+    profanity: HashSet<String>,
+}
+
+impl NamePolicy {
+    pub fn new(reserved_names: HashSet<String>, profanity: HashSet<String>) -> Self {
+        Self {
+            reserved_names: reserved_names
+                .iter()
+                .map(|name| name.to_lowercase())
+                .collect(),
+            profanity: profanity.iter().map(|word| word.to_lowercase()).collect(),
+        }
+    }
+
+    #[cfg(not(test))]
+    pub async fn load() -> Result<Self, Box<dyn Error + Send + Sync>> {
+        Ok(NamePolicy::new(
+            NamePolicy::read_list(RESERVED_NAMES_PATH).await?,
+            NamePolicy::read_list(PROFANITY_PATH).await?,
+        ))
+    }
+
+    #[cfg(not(test))]
+    async fn read_list(path: &str) -> Result<HashSet<String>, Box<dyn Error + Send + Sync>> {
+        let mut list = HashSet::new();
+        let mut reader = BufReader::new(File::open(path).await?).lines();
+        while let Some(line) = reader.next_line().await? {
+            let line = line.trim();
+            if !line.is_empty() && !line.starts_with('#') {
+                list.insert(String::from(line));
+            }
+        }
+        Ok(list)
+    }
+
+    pub fn validate(&self, name: &str) -> Result<String, NameRejection> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(NameRejection::Empty);
+        }
+        if name.chars().count() > MAX_NAME_LENGTH {
+            return Err(NameRejection::TooLong);
+        }
+        if !name
+            .chars()
+            .all(|c| c.is_alphanumeric() || NAME_SPECIAL_CHARACTERS.contains(&c))
+        {
+            return Err(NameRejection::InvalidCharacters);
+        }
+        let lowercase_name = name.to_lowercase();
+        if self.reserved_names.contains(&lowercase_name) {
+            return Err(NameRejection::Reserved);
+        }
+        if self
+            .profanity
+            .iter()
+            .any(|word| lowercase_name.contains(word.as_str()))
+        {
+            return Err(NameRejection::Profanity);
+        }
+        Ok(String::from(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name_policy() -> NamePolicy {
+        NamePolicy::new(
+            HashSet::from([String::from("Admin")]),
+            HashSet::from([String::from("darn")]),
+        )
+    }
+
+    #[test]
+    fn validate_with_valid_name_should_return_trimmed_name() {
+        assert_eq!(
+            name_policy().validate("  test_1 "),
+            Ok(String::from("test_1"))
+        );
+    }
+
+    #[test]
+    fn validate_with_blank_name_should_reject_empty() {
+        assert_eq!(name_policy().validate("   "), Err(NameRejection::Empty));
+    }
+
+    #[test]
+    fn validate_with_too_long_name_should_reject_too_long() {
+        assert_eq!(
+            name_policy().validate(&"a".repeat(MAX_NAME_LENGTH + 1)),
+            Err(NameRejection::TooLong)
+        );
+    }
+
+    #[test]
+    fn validate_with_symbols_should_reject_invalid_characters() {
+        assert_eq!(
+            name_policy().validate("<test>"),
+            Err(NameRejection::InvalidCharacters)
+        );
+    }
+
+    #[test]
+    fn validate_with_reserved_name_should_reject_reserved() {
+        assert_eq!(
+            name_policy().validate("ADMIN"),
+            Err(NameRejection::Reserved)
+        );
+        assert!(name_policy().validate("admin2").is_ok());
+    }
+
+    #[test]
+    fn validate_with_profane_word_inside_name_should_reject_profanity() {
+        assert_eq!(
+            name_policy().validate("xDarnx"),
+            Err(NameRejection::Profanity)
+        );
+    }
+}
@@ -0,0 +1,27 @@
+use std::{error::Error, fmt::Display};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameRejection {
+    Empty,
+    TooLong,
+    InvalidCharacters,
+    Reserved,
+    Profanity,
+    Taken,
+}
+
+impl Display for NameRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let reason = match self {
+            NameRejection::Empty => "Name is empty",
+            NameRejection::TooLong => "Name is too long",
+            NameRejection::InvalidCharacters => "Name contains invalid characters",
+            NameRejection::Reserved => "Name is reserved",
+            NameRejection::Profanity => "Name contains profanity",
+            NameRejection::Taken => "Name is already taken",
+        };
+        write!(f, "{}", reason)
+    }
+}
+
+impl Error for NameRejection {}
@@ -1,6 +1,7 @@
 use std::error::Error;
 
 use crate::frame::RequestData;
+use crate::protocol::{MIN_PROTOCOL_VERSION, PROTOCOL_VERSION};
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub enum Operation {
@@ -18,6 +19,65 @@ pub enum Operation {
     GetNewCard,
     Cancel,
     Exit,
+    UpdateLobby,
+    Kick,
+    Chat,
+    QuickPlay,
+    CancelQuickPlay,
+    Rematch,
+    Spectate,
+    StopSpectating,
+    Reconnect,
+    GetProfile,
+    GetMatchHistory,
+    GetLeaderboard,
+    AddFriend,
+    AcceptFriend,
+    RemoveFriend,
+    ListFriends,
+    Invite,
+    VoteKick,
+    Pong,
+    GetBoard,
+}
+
+pub const MAX_PAYLOAD_LEN: usize = 1024;
+
+impl Operation {
+    pub fn max_payload_len(&self) -> usize {
+        match self {
+            Operation::Disconnect
+            | Operation::Heartbeat
+            | Operation::QuitLobby
+            | Operation::Ready
+            | Operation::StartGame
+            | Operation::FinishTurn
+            | Operation::GetNewCard
+            | Operation::Exit
+            | Operation::CancelQuickPlay
+            | Operation::StopSpectating
+            | Operation::ListFriends
+            | Operation::GetBoard => 0,
+            Operation::Chat => MAX_PAYLOAD_LEN,
+            Operation::Connect | Operation::CreateLobby | Operation::UpdateLobby => 512,
+            _ => 256,
+        }
+    }
+
+    /**
+     * Decode an operation code sent by a client speaking the given protocol version.
+     * Connect keeps code 0 in every version so any client can reach the version check,
+     * renumbering the others only takes another arm here.
+     */
+    pub fn decode(value: u8, version: u8) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        if value == 0 {
+            return Ok(Operation::Connect);
+        }
+        match version {
+            MIN_PROTOCOL_VERSION..=PROTOCOL_VERSION => Operation::try_from(value),
+            _ => Err(format!("unsupported protocol version {version}").into()),
+        }
+    }
 }
 
 impl TryFrom<u8> for Operation {
@@ -38,6 +98,26 @@ impl TryFrom<u8> for Operation {
             11 => Ok(Operation::GetNewCard),
             12 => Ok(Operation::Cancel),
             13 => Ok(Operation::Exit),
+            14 => Ok(Operation::UpdateLobby),
+            15 => Ok(Operation::Kick),
+            16 => Ok(Operation::Chat),
+            17 => Ok(Operation::QuickPlay),
+            18 => Ok(Operation::CancelQuickPlay),
+            19 => Ok(Operation::Rematch),
+            20 => Ok(Operation::Spectate),
+            21 => Ok(Operation::StopSpectating),
+            22 => Ok(Operation::Reconnect),
+            23 => Ok(Operation::GetProfile),
+            24 => Ok(Operation::GetMatchHistory),
+            25 => Ok(Operation::GetLeaderboard),
+            26 => Ok(Operation::AddFriend),
+            27 => Ok(Operation::AcceptFriend),
+            28 => Ok(Operation::RemoveFriend),
+            29 => Ok(Operation::ListFriends),
+            30 => Ok(Operation::Invite),
+            31 => Ok(Operation::VoteKick),
+            32 => Ok(Operation::Pong),
+            33 => Ok(Operation::GetBoard),
             _ => Err("invalid operation".into()),
         }
     }
@@ -53,7 +133,7 @@ impl TryFrom<&RequestData> for Operation {
             RequestData::CreateLobby(_) => Ok(Operation::CreateLobby),
             RequestData::JoinLobby(_) => Ok(Operation::JoinLobby),
             RequestData::QuitLobby => Ok(Operation::QuitLobby),
-            RequestData::ListLobby => Ok(Operation::ListLobby),
+            RequestData::ListLobby(_) => Ok(Operation::ListLobby),
             RequestData::Ready => Ok(Operation::Ready),
             RequestData::StartGame => Ok(Operation::StartGame),
             RequestData::SetTile(_) => Ok(Operation::SetTile),
@@ -61,6 +141,26 @@ impl TryFrom<&RequestData> for Operation {
             RequestData::GetNewCard => Ok(Operation::GetNewCard),
             RequestData::Cancel(_) => Ok(Operation::Cancel),
             RequestData::Exit => Ok(Operation::Exit),
+            RequestData::UpdateLobby(_) => Ok(Operation::UpdateLobby),
+            RequestData::Kick(_) => Ok(Operation::Kick),
+            RequestData::Chat(_) => Ok(Operation::Chat),
+            RequestData::QuickPlay(_) => Ok(Operation::QuickPlay),
+            RequestData::CancelQuickPlay => Ok(Operation::CancelQuickPlay),
+            RequestData::Rematch(_) => Ok(Operation::Rematch),
+            RequestData::Spectate(_) => Ok(Operation::Spectate),
+            RequestData::StopSpectating => Ok(Operation::StopSpectating),
+            RequestData::Reconnect(_) => Ok(Operation::Reconnect),
+            RequestData::GetProfile(_) => Ok(Operation::GetProfile),
+            RequestData::GetMatchHistory(_) => Ok(Operation::GetMatchHistory),
+            RequestData::GetLeaderboard(_) => Ok(Operation::GetLeaderboard),
+            RequestData::AddFriend(_) => Ok(Operation::AddFriend),
+            RequestData::AcceptFriend(_) => Ok(Operation::AcceptFriend),
+            RequestData::RemoveFriend(_) => Ok(Operation::RemoveFriend),
+            RequestData::ListFriends => Ok(Operation::ListFriends),
+            RequestData::Invite(_) => Ok(Operation::Invite),
+            RequestData::VoteKick(_) => Ok(Operation::VoteKick),
+            RequestData::Pong(_) => Ok(Operation::Pong),
+            RequestData::GetBoard => Ok(Operation::GetBoard),
             // _ => Err("invalid request".into()),
         }
     }
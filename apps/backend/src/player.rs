@@ -1,21 +1,52 @@
 #[cfg(not(test))]
-use crate::frame::Frame;
+use crate::connection::ClientSender;
 #[cfg(not(test))]
-use crate::frame::Response;
-use crate::{game::game::Game, lobby::lobby::Lobby};
+use crate::frame::{Frame, Response};
+use crate::{
+    account::account::Account, chat::rate_limiter::RateLimiter, game::game::Game,
+    lobby::lobby::Lobby, matchmaking::matchmaking_key::rating_band, protocol::Feature,
+};
 use core::hash::{Hash, Hasher};
+use rand::{distributions::Alphanumeric, Rng};
+use std::net::IpAddr;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 #[cfg(not(test))]
-use tokio::sync::mpsc::Sender;
+use tokio::sync::mpsc::error::TrySendError;
+
+pub const CHAT_RATE_LIMIT: usize = 5;
+pub const CHAT_RATE_LIMIT_WINDOW: Duration = Duration::from_secs(10);
+pub const DEFAULT_RATING: u32 = 1500;
+pub const SESSION_TOKEN_LENGTH: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Presence {
+    Offline,
+    Online,
+    InLobby,
+    InGame,
+}
 
 #[derive(Debug)]
 pub struct Player {
     pub id: u32,
     pub name: String,
     #[cfg(not(test))]
-    sender: Sender<Frame>,
+    sender: Mutex<ClientSender>,
+    session_token: Mutex<String>,
+    disconnected_at: Mutex<Option<Instant>>,
+    address: Mutex<Option<IpAddr>>,
+    account: Mutex<Option<Account>>,
     lobby: Mutex<Option<Arc<Lobby>>>,
     game: Mutex<Option<Arc<Game>>>,
+    spectating: Mutex<Option<Arc<Game>>>,
+    chat_rate_limiter: Mutex<RateLimiter>,
+    rating: Mutex<u32>,
+    ping_sequence: Mutex<u32>,
+    pending_ping: Mutex<Option<(u32, Instant)>>,
+    rtt: Mutex<Option<Duration>>,
+    idle: Mutex<bool>,
+    features: Mutex<u32>,
 }
 
 impl PartialEq for Player {
@@ -33,14 +64,154 @@ impl Hash for Player {
 }
 
 impl Player {
-    pub fn new(id: u32, name: String, #[cfg(not(test))] sender: Sender<Frame>) -> Self {
+    pub fn new(id: u32, name: String, #[cfg(not(test))] sender: ClientSender) -> Self {
         Player {
             id,
             name,
             #[cfg(not(test))]
-            sender,
+            sender: Mutex::new(sender),
+            session_token: Mutex::new(Player::generate_session_token()),
+            disconnected_at: Mutex::new(None),
+            address: Mutex::new(None),
+            account: Mutex::new(None),
             lobby: Mutex::new(None),
             game: Mutex::new(None),
+            spectating: Mutex::new(None),
+            chat_rate_limiter: Mutex::new(RateLimiter::new(
+                CHAT_RATE_LIMIT,
+                CHAT_RATE_LIMIT_WINDOW,
+            )),
+            rating: Mutex::new(DEFAULT_RATING),
+            ping_sequence: Mutex::new(0),
+            pending_ping: Mutex::new(None),
+            rtt: Mutex::new(None),
+            idle: Mutex::new(false),
+            features: Mutex::new(0),
+        }
+    }
+
+    fn generate_session_token() -> String {
+        rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(SESSION_TOKEN_LENGTH)
+            .map(char::from)
+            .collect()
+    }
+
+    pub fn get_session_token(&self) -> String {
+        self.session_token.lock().unwrap().clone()
+    }
+
+    /**
+     * Replace the session token with a fresh one, so a token seen on an old connection can not be
+     * used to take over the player again.
+     */
+    pub fn renew_session_token(&self) -> String {
+        let token = Player::generate_session_token();
+        *self.session_token.lock().unwrap() = token.clone();
+        token
+    }
+
+    #[cfg(not(test))]
+    pub fn set_sender(&self, sender: ClientSender) {
+        *self.sender.lock().unwrap() = sender;
+    }
+
+    pub fn get_disconnected_at(&self) -> Option<Instant> {
+        *self.disconnected_at.lock().unwrap()
+    }
+
+    pub fn set_disconnected_at(&self, disconnected_at: Option<Instant>) {
+        *self.disconnected_at.lock().unwrap() = disconnected_at;
+    }
+
+    pub fn get_address(&self) -> Option<IpAddr> {
+        *self.address.lock().unwrap()
+    }
+
+    pub fn set_address(&self, address: Option<IpAddr>) {
+        *self.address.lock().unwrap() = address;
+    }
+
+    pub fn get_account(&self) -> Option<Account> {
+        self.account.lock().unwrap().clone()
+    }
+
+    pub fn set_account(&self, account: Option<Account>) {
+        *self.account.lock().unwrap() = account;
+    }
+
+    pub fn get_account_id(&self) -> Option<u32> {
+        self.account
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|account| account.id)
+    }
+
+    pub fn get_rating(&self) -> u32 {
+        *self.rating.lock().unwrap()
+    }
+
+    pub fn set_rating(&self, rating: u32) {
+        *self.rating.lock().unwrap() = rating;
+    }
+
+    pub fn get_rating_band(&self) -> u32 {
+        rating_band(self.get_rating())
+    }
+
+    pub fn start_ping(&self, sent_at: Instant) -> u32 {
+        let mut sequence = self.ping_sequence.lock().unwrap();
+        *sequence = sequence.wrapping_add(1);
+        *self.pending_ping.lock().unwrap() = Some((*sequence, sent_at));
+        *sequence
+    }
+
+    pub fn finish_ping(&self, sequence: u32, received_at: Instant) -> Option<Duration> {
+        let mut pending_ping = self.pending_ping.lock().unwrap();
+        match *pending_ping {
+            Some((pending_sequence, sent_at)) if pending_sequence == sequence => {
+                *pending_ping = None;
+                let rtt = received_at.saturating_duration_since(sent_at);
+                *self.rtt.lock().unwrap() = Some(rtt);
+                Some(rtt)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn get_rtt(&self) -> Option<Duration> {
+        *self.rtt.lock().unwrap()
+    }
+
+    pub fn is_idle(&self) -> bool {
+        *self.idle.lock().unwrap()
+    }
+
+    pub fn set_idle(&self, idle: bool) {
+        *self.idle.lock().unwrap() = idle;
+    }
+
+    pub fn get_features(&self) -> u32 {
+        *self.features.lock().unwrap()
+    }
+
+    pub fn set_features(&self, features: u32) {
+        *self.features.lock().unwrap() = features;
+    }
+
+    pub fn has_feature(&self, feature: Feature) -> bool {
+        self.get_features() & feature as u32 != 0
+    }
+
+    pub fn get_presence(&self) -> Presence {
+        if self.get_game().is_some() {
+            return Presence::InGame;
+        }
+        match self.get_lobby() {
+            Some(_) => Presence::InLobby,
+            None => Presence::Online,
         }
     }
 
@@ -60,14 +231,52 @@ impl Player {
         *self.game.lock().unwrap() = game;
     }
 
+    pub fn get_spectating(&self) -> Option<Arc<Game>> {
+        self.spectating.lock().unwrap().clone()
+    }
+
+    pub fn set_spectating(&self, game: Option<Arc<Game>>) {
+        *self.spectating.lock().unwrap() = game;
+    }
+
+    pub fn try_chat(&self) -> bool {
+        self.chat_rate_limiter
+            .lock()
+            .unwrap()
+            .try_acquire(Instant::now())
+    }
+
     #[cfg(not(test))]
-    pub async fn send_message(
-        &self,
-        res: Response,
-    ) -> Result<(), tokio::sync::mpsc::error::SendError<Frame>> {
-        match self.sender.send(Frame::Response(res)).await {
-            Ok(_) => Ok(()),
-            Err(e) => Err(e),
-        }
+    pub fn send_message(&self, res: Response) -> Result<(), TrySendError<Frame>> {
+        let sender = self.sender.lock().unwrap().clone();
+        sender.send(Frame::Response(res))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finish_ping_with_pending_sequence_should_update_rtt() {
+        let player = Player::new(0, String::from("test"));
+        let sent_at = Instant::now();
+        let sequence = player.start_ping(sent_at);
+        let rtt = player.finish_ping(sequence, sent_at + Duration::from_millis(120));
+        assert_eq!(rtt, Some(Duration::from_millis(120)));
+        assert_eq!(player.get_rtt(), Some(Duration::from_millis(120)));
+    }
+
+    #[test]
+    fn finish_ping_with_stale_sequence_should_return_none() {
+        let player = Player::new(0, String::from("test"));
+        let sent_at = Instant::now();
+        let stale = player.start_ping(sent_at);
+        let sequence = player.start_ping(sent_at);
+        assert_ne!(stale, sequence);
+        assert!(player.finish_ping(stale, sent_at).is_none());
+        assert!(player.get_rtt().is_none());
+        assert!(player.finish_ping(sequence, sent_at).is_some());
+        assert!(player.finish_ping(sequence, sent_at).is_none());
     }
 }
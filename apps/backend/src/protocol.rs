@@ -0,0 +1,73 @@
+use crate::error::ServiceError;
+
+/**
+ * The protocol version this server speaks. Clients send theirs in the second header byte of
+ * every request and in `ConnectRequest`, 0 stands for clients that predate versioning.
+ */
+pub const PROTOCOL_VERSION: u8 = 1;
+pub const MIN_PROTOCOL_VERSION: u8 = 0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Feature {
+    Accounts = 1 << 0,
+    Friends = 1 << 1,
+    Matchmaking = 1 << 2,
+    Spectate = 1 << 3,
+    Ping = 1 << 4,
+    BoardDelta = 1 << 5,
+}
+
+pub const SERVER_FEATURES: u32 = Feature::Accounts as u32
+    | Feature::Friends as u32
+    | Feature::Matchmaking as u32
+    | Feature::Spectate as u32
+    | Feature::Ping as u32
+    | Feature::BoardDelta as u32;
+
+pub fn is_supported(version: u32) -> bool {
+    (MIN_PROTOCOL_VERSION as u32..=PROTOCOL_VERSION as u32).contains(&version)
+}
+
+pub fn check_version(version: u32) -> Result<(), ServiceError> {
+    if is_supported(version) {
+        return Ok(());
+    }
+    Err(ServiceError::UnsupportedProtocolVersion(version))
+}
+
+pub fn negotiate_features(client_features: u32) -> u32 {
+    client_features & SERVER_FEATURES
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operation::Operation;
+
+    #[test]
+    fn check_version_with_legacy_client_should_return_ok() {
+        assert!(check_version(0).is_ok());
+    }
+
+    #[test]
+    fn check_version_with_newer_client_should_return_error() {
+        assert!(check_version(PROTOCOL_VERSION as u32 + 1).is_err());
+    }
+
+    #[test]
+    fn decode_operation_with_unsupported_version_should_only_accept_connect() {
+        assert_eq!(
+            Operation::decode(0, PROTOCOL_VERSION + 1).unwrap(),
+            Operation::Connect
+        );
+        assert!(Operation::decode(1, PROTOCOL_VERSION + 1).is_err());
+    }
+
+    #[test]
+    fn negotiate_features_with_unknown_bits_should_drop_them() {
+        assert_eq!(
+            negotiate_features(Feature::Friends as u32 | 1 << 31),
+            Feature::Friends as u32
+        );
+    }
+}
@@ -7,20 +7,22 @@ use std::fmt::Debug;
 use std::sync::{Arc, RwLock};
 
 #[cfg(not(test))]
-use crate::frame::Frame;
+use crate::connection::ClientSender;
 #[cfg(not(test))]
-use tokio::sync::mpsc::Sender;
+use std::net::SocketAddr;
 
 #[derive(Debug)]
 pub struct Router {
-    controllers: Arc<RwLock<HashMap<Operation, Box<dyn PrintableController>>>>,
+    controllers: Arc<RwLock<HashMap<Operation, Arc<dyn PrintableController>>>>,
 }
 
 #[derive(Debug)]
 pub struct RequestContext {
     pub client_id: u32,
     #[cfg(not(test))]
-    pub sender: Sender<Frame>,
+    pub sender: ClientSender,
+    #[cfg(not(test))]
+    pub addr: SocketAddr,
 }
 
 impl Router {
@@ -38,26 +40,25 @@ impl Router {
         self.controllers
             .write()
             .unwrap()
-            .insert(operation, controller);
+            .insert(operation, Arc::from(controller));
         self
     }
 
-    pub fn route(
+    pub async fn route(
         &self,
         request: Request,
         context: RequestContext,
     ) -> Result<ResponseData, Box<dyn Error + Sync + Send>> {
-        match self
+        let controller = self
             .controllers
             .read()
             .unwrap()
             .get(&Operation::try_from(request.get_data().as_ref())?)
-        {
-            Some(controller) => match controller.handle_request(request, context) {
+            .cloned();
+        match controller {
+            Some(controller) => match controller.handle_request(request, context).await {
                 Ok(response) => Ok(response),
-                Err(err) => Ok(ResponseData::Error(crate::model::error::error::Error {
-                    message: err.to_string(),
-                })),
+                Err(err) => Ok(ResponseData::Error(err.into())),
             },
             None => Err(format!("no controller for request {:?}", request).into()),
         }
@@ -69,10 +70,16 @@ mod tests {
     use std::collections::HashSet;
 
     use crate::{
-        controller::control::connect::ConnectController,
+        account::account_repository::AccountRepository,
+        controller::{control::connect::ConnectController, game::get_board::GetBoardController},
+        model::error::error::ErrorCode,
+        naming::name_policy::NamePolicy,
         service::{
-            game_service::GameService, lobby_service::LobbyService, player_service::PlayerService,
+            account_service::AccountService, game_service::GameService,
+            lobby_service::LobbyService, player_service::PlayerService,
+            rating_service::RatingService,
         },
+        stats::rating_repository::RatingRepository,
     };
 
     use super::*;
@@ -82,10 +89,19 @@ mod tests {
         let router = Router::new();
         router.register_controller(
             Operation::Connect,
-            Box::new(ConnectController::new(Arc::new(PlayerService::new(
-                Arc::new(LobbyService::new()),
-                Arc::new(GameService::new(HashSet::new())),
-            )))),
+            Box::new(ConnectController::new(
+                Arc::new(PlayerService::new(
+                    Arc::new(LobbyService::new()),
+                    Arc::new(GameService::new(HashSet::new())),
+                )),
+                Arc::new(AccountService::new(
+                    AccountRepository::open_in_memory().unwrap(),
+                )),
+                Arc::new(NamePolicy::default()),
+                Arc::new(RatingService::new(
+                    RatingRepository::open_in_memory().unwrap(),
+                )),
+            )),
         );
         assert!(router
             .controllers
@@ -93,4 +109,31 @@ mod tests {
             .unwrap()
             .contains_key(&Operation::Connect));
     }
+
+    #[tokio::test]
+    async fn route_with_failing_controller_should_return_error_code(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let router = Router::new();
+        router.register_controller(
+            Operation::GetBoard,
+            Box::new(GetBoardController::new(Arc::new(PlayerService::new(
+                Arc::new(LobbyService::new()),
+                Arc::new(GameService::new(HashSet::new())),
+            )))),
+        );
+        match router
+            .route(
+                Request::new(0, Arc::new(crate::frame::RequestData::GetBoard)),
+                RequestContext { client_id: 0 },
+            )
+            .await?
+        {
+            ResponseData::Error(err) => {
+                assert_eq!(err.code, ErrorCode::PlayerNotFound as i32);
+                assert_eq!(err.message, "Player not found");
+            }
+            _ => panic!("invalid response"),
+        }
+        Ok(())
+    }
 }
@@ -1,39 +1,78 @@
 use std::error::Error;
+use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
-use crate::connection::Connection;
+#[cfg(not(test))]
+use crate::account::account_repository::AccountRepository;
+use crate::connection::{ClientSender, Connection};
+use crate::connection_settings::ConnectionSettings;
 #[cfg(not(test))]
 use crate::controller::{
+    chat::send::SendController,
     control::{
         connect::ConnectController, disconnect::DisconnectController,
-        heartbeat::HeartbeatController,
+        heartbeat::HeartbeatController, pong::PongController, reconnect::ReconnectController,
+    },
+    friend::{
+        accept::AcceptFriendController, add::AddFriendController, invite::InviteController,
+        list::ListFriendsController, remove::RemoveFriendController,
     },
     game::{
         cancel::CancelController, exit::ExitController, finish_turn::FinishTurnController,
-        get_new_card::GetNewCardController, set_tile::SetTileController, start::StartController,
+        get_board::GetBoardController, get_new_card::GetNewCardController,
+        set_tile::SetTileController, spectate::SpectateController, start::StartController,
+        stop_spectating::StopSpectatingController, vote_kick::VoteKickController,
     },
     lobby::{
-        create::CreateController, join::JoinController, list::ListController, quit::QuitController,
-        ready::ReadyController,
+        create::CreateController, join::JoinController, kick::KickController, list::ListController,
+        quit::QuitController, ready::ReadyController, rematch::RematchController,
+        update::UpdateController,
+    },
+    matchmaking::{cancel::CancelQuickPlayController, quick_play::QuickPlayController},
+    stats::{
+        history::HistoryController, leaderboard::LeaderboardController, profile::ProfileController,
     },
 };
-use crate::frame::{Frame, Response};
+use crate::frame::{Frame, Response, ResponseData};
+use crate::game::game::Game;
+use crate::model::{control::ping::PingBroadcast, state::State};
+#[cfg(not(test))]
+use crate::naming::name_policy::NamePolicy;
 #[cfg(not(test))]
 use crate::operation::Operation;
 use crate::router::{RequestContext, Router};
-use crate::service::player_service::PlayerService;
 #[cfg(not(test))]
-use crate::service::{game_service::GameService, lobby_service::LobbyService};
-use tokio::net::TcpListener;
+use crate::service::{
+    account_service::AccountService, chat_service::ChatService, friend_service::FriendService,
+    game_service::GameService, lobby_service::LobbyService, rating_service::RatingService,
+    stats_service::StatsService,
+};
+use crate::service::{matchmaking_service::MatchmakingService, player_service::PlayerService};
+#[cfg(not(test))]
+use crate::stats::{match_repository::MatchRepository, rating_repository::RatingRepository};
+use crate::tls::Tls;
+#[cfg(not(test))]
+use crate::tls::TlsSettings;
+use crate::transport::BoxedStream;
+use tokio::net::{TcpListener, TcpStream};
 use tokio::sync::mpsc::{channel, Receiver, Sender};
 use tokio::sync::Mutex;
+use tokio::time::timeout;
+
+pub const DEFAULT_DATABASE_PATH: &str = "letterlegend.db";
+pub const DEFAULT_WEBSOCKET_PORT: u32 = 45679;
 
 #[derive(Debug, Clone)]
 pub struct Server {
     host: String,
     port: u32,
+    websocket_port: u32,
     player_service: Arc<PlayerService>,
+    matchmaking_service: Arc<MatchmakingService>,
     router: Arc<Router>,
+    connection_settings: ConnectionSettings,
+    tls: Option<Tls>,
 }
 
 pub struct Context {
@@ -47,132 +86,328 @@ unsafe impl Sync for Server {}
 impl Server {
     pub async fn run(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
         let listener = TcpListener::bind(format!("{}:{}", self.host, self.port)).await?;
+        let websocket_listener =
+            TcpListener::bind(format!("{}:{}", self.host, self.websocket_port)).await?;
 
         let mut next_client_id = 0;
 
         loop {
-            let (socket, _) = listener.accept().await?;
-            let (tx, rx): (Sender<Frame>, Receiver<Frame>) = channel(128);
-            let shared_rx_bak = Arc::new(Mutex::new(rx));
+            let (accepted, websocket) = tokio::select! {
+                accepted = listener.accept() => (accepted, false),
+                accepted = websocket_listener.accept() => (accepted, true),
+            };
+            let (socket, addr) = accepted?;
             let client_id = next_client_id;
             next_client_id += 1;
-
-            let connection_bak = Arc::new(Connection::new(socket));
-            // clone the map
-            let connection = connection_bak.clone();
             let server = self.clone();
-            let shared_rx = shared_rx_bak.clone();
-
+            // do the handshakes off the accept loop so a slow client can't stall it
             tokio::spawn(async move {
-                loop {
-                    let frame = match connection.read_frame().await {
-                        Ok(Some(frame)) => frame,
-                        Ok(None) => {
-                            println!("connection closed by remote peer");
-                            if let Some(player) = server.player_service.get_player(client_id) {
-                                match server.player_service.remove_player(player) {
-                                    Ok(player) => println!(
-                                        "clean up player's resource success. player id: {}, player name: {}", player.id, player.name
-                                    ),
-                                    Err(e) => {
-                                        eprintln!("failed to clean up player's resource, err: {e}")
-                                    }
-                                }
-                            };
-                            shared_rx.lock().await.close();
-                            break;
-                        }
-                        Err(e) => {
-                            eprintln!("failed to read frame; err = {:?}", e);
-                            if let Some(player) = server.player_service.get_player(client_id) {
-                                match server.player_service.remove_player(player) {
-                                    Ok(player) => println!(
-                                        "clean up player's resource success. player id: {}, player name: {}", player.id, player.name
-                                    ),
-                                    Err(e) => {
-                                        eprintln!("failed to clean up player's resource, err: {e}")
-                                    }
-                                }
-                            };
-                            shared_rx.lock().await.close();
-                            break;
-                        }
-                    };
-                    match frame {
-                        Frame::Request(req) => {
-                            println!("received request; request = {:?}", req);
-                            let state = req.get_state();
-                            match server.router.route(
+                match server.accept(socket, websocket).await {
+                    Ok(connection) => server.serve(connection, addr, client_id),
+                    Err(e) => eprintln!("handshake failed; err = {:?}", e),
+                }
+            });
+        }
+    }
+
+    async fn accept(
+        &self,
+        socket: TcpStream,
+        websocket: bool,
+    ) -> Result<Connection, Box<dyn Error + Send + Sync>> {
+        let stream: BoxedStream = match &self.tls {
+            Some(tls) => Box::new(tls.accept(socket).await?),
+            None => Box::new(socket),
+        };
+        if websocket {
+            Connection::accept_websocket(stream).await
+        } else {
+            Ok(Connection::new(stream))
+        }
+    }
+
+    fn serve(&self, connection: Connection, _addr: SocketAddr, client_id: u32) {
+        let (tx, rx): (Sender<Frame>, Receiver<Frame>) = channel(128);
+        let shared_rx_bak = Arc::new(Mutex::new(rx));
+        let connection_bak = Arc::new(connection);
+        // clone the map
+        let connection = connection_bak.clone();
+        let shared_rx = shared_rx_bak.clone();
+
+        let server = self.clone();
+        let ping_tx = tx.clone();
+        let ping_task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(server.connection_settings.ping_interval);
+            loop {
+                interval.tick().await;
+                // only ping clients that have connected as a player
+                let player = match server.player_service.get_player(client_id) {
+                    Some(player) => player,
+                    None => continue,
+                };
+                let sequence = player.start_ping(Instant::now());
+                let timestamp = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_millis() as u64;
+                if ping_tx
+                    .send(Frame::Response(Response::new(
+                        State::Ping as u32,
+                        Arc::new(ResponseData::Ping(PingBroadcast {
+                            sequence,
+                            timestamp,
+                        })),
+                    )))
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+
+        let sender = ClientSender::new(tx.clone());
+        let server = self.clone();
+        tokio::spawn(async move {
+            let mut idle = false;
+            let mut bad_frames = 0;
+            loop {
+                // a silent connection is marked idle first and dropped once it stays silent
+                let wait = if idle {
+                    server.connection_settings.dead_timeout
+                        - server.connection_settings.idle_timeout
+                } else {
+                    server.connection_settings.idle_timeout
+                };
+                let read = tokio::select! {
+                    read = timeout(wait, connection.read_frame()) => read,
+                    _ = sender.overflowed() => {
+                        eprintln!("client can't keep up with its frames, closing connection");
+                        server.disconnect_player(client_id);
+                        shared_rx.lock().await.close();
+                        break;
+                    }
+                };
+                let result = match read {
+                    Ok(result) => result,
+                    Err(_) if !idle => {
+                        idle = true;
+                        server.set_player_idle(client_id, true);
+                        continue;
+                    }
+                    Err(_) => Err("read timeout, connection is dead".into()),
+                };
+                if idle {
+                    idle = false;
+                    server.set_player_idle(client_id, false);
+                }
+                let frame = match result {
+                    Ok(Some(frame)) => frame,
+                    Ok(None) => {
+                        println!("connection closed by remote peer");
+                        server.disconnect_player(client_id);
+                        shared_rx.lock().await.close();
+                        break;
+                    }
+                    Err(e) => {
+                        eprintln!("failed to read frame; err = {:?}", e);
+                        server.disconnect_player(client_id);
+                        shared_rx.lock().await.close();
+                        break;
+                    }
+                };
+                match frame {
+                    Frame::Request(req) => {
+                        println!("received request; request = {:?}", req);
+                        let state = req.get_state();
+                        // the response is queued before the next request of the game is handled
+                        let game = server.get_game(client_id);
+                        let _guard = match &game {
+                            Some(game) => Some(game.lock_requests().await),
+                            None => None,
+                        };
+                        match server
+                            .router
+                            .route(
                                 req,
                                 RequestContext {
                                     client_id,
                                     #[cfg(not(test))]
-                                    sender: tx.clone(),
+                                    sender: sender.clone(),
+                                    #[cfg(not(test))]
+                                    addr: _addr,
                                 },
-                            ) {
-                                Ok(res) => {
-                                    if tx
-                                        .send(Frame::Response(Response::new(state, Arc::new(res))))
-                                        .await
-                                        .is_err()
-                                    {
-                                        eprintln!("failed to send frame to writer thread");
-                                        break;
-                                    }
-                                }
-                                Err(e) => {
-                                    eprintln!("failed to handle request; err = {:?}", e);
+                            )
+                            .await
+                        {
+                            Ok(res) => {
+                                if let Err(e) = sender
+                                    .send(Frame::Response(Response::new(state, Arc::new(res))))
+                                {
+                                    eprintln!("failed to send frame to writer thread; err = {e}");
                                 }
-                            };
-                        }
-                        _ => {
-                            eprintln!("invalid frame; frame = {:?}", frame)
-                        }
-                    };
-                }
-            });
-
-            let connection = connection_bak.clone();
-            let shared_rx = shared_rx_bak.clone();
-
-            tokio::spawn(async move {
-                loop {
-                    while let Some(frame) = shared_rx.lock().await.recv().await {
-                        match connection.write_frame(&frame).await {
-                            Ok(_) => {
-                                println!("sent frame; frame = {:?}", frame);
-                                continue;
                             }
                             Err(e) => {
-                                eprintln!("failed to write frame; err = {:?}", e);
-                                break;
+                                eprintln!("failed to handle request; err = {:?}", e);
                             }
                         };
                     }
+                    Frame::Invalid { state, reason } => {
+                        eprintln!("invalid frame; reason = {}", reason);
+                        bad_frames += 1;
+                        if tx
+                            .send(Frame::Response(Response::new(
+                                state,
+                                Arc::new(ResponseData::Error(crate::model::error::error::Error {
+                                    message: format!("Malformed request: {reason}"),
+                                    code: crate::model::error::error::ErrorCode::MalformedRequest
+                                        as i32,
+                                })),
+                            )))
+                            .await
+                            .is_err()
+                        {
+                            eprintln!("failed to send frame to writer thread");
+                            break;
+                        }
+                        if bad_frames >= server.connection_settings.max_bad_frames {
+                            eprintln!("too many invalid frames, closing connection");
+                            server.disconnect_player(client_id);
+                            shared_rx.lock().await.close();
+                            break;
+                        }
+                    }
+                    Frame::TooLarge { state, reason } => {
+                        eprintln!("frame too large, closing connection; reason = {}", reason);
+                        if tx
+                            .send(Frame::Response(Response::new(
+                                state,
+                                Arc::new(ResponseData::Error(crate::model::error::error::Error {
+                                    message: format!("Request too large: {reason}"),
+                                    code: crate::model::error::error::ErrorCode::RequestTooLarge
+                                        as i32,
+                                })),
+                            )))
+                            .await
+                            .is_err()
+                        {
+                            eprintln!("failed to send frame to writer thread");
+                        }
+                        server.disconnect_player(client_id);
+                        shared_rx.lock().await.close();
+                        break;
+                    }
+                    _ => {
+                        eprintln!("invalid frame; frame = {:?}", frame)
+                    }
+                };
+            }
+            ping_task.abort();
+        });
+
+        let connection = connection_bak.clone();
+        let shared_rx = shared_rx_bak.clone();
+
+        tokio::spawn(async move {
+            loop {
+                // release the queue while writing, so the reader can close it
+                let frame = match shared_rx.lock().await.recv().await {
+                    Some(frame) => frame,
+                    None => break,
+                };
+                match connection.write_frame(&frame).await {
+                    Ok(_) => println!("sent frame; frame = {:?}", frame),
+                    Err(e) => {
+                        eprintln!("failed to write frame; err = {:?}", e);
+                        break;
+                    }
+                };
+            }
+        });
+    }
+
+    fn disconnect_player(&self, client_id: u32) {
+        if let Some(player) = self.player_service.get_player(client_id) {
+            // a dropped player must not be matched
+            let _ = self.matchmaking_service.leave_queue(&player);
+            match self.player_service.disconnect_player(client_id) {
+                Ok(player) => println!(
+                    "player disconnected, waiting for reconnect. player id: {}, player name: {}",
+                    player.id, player.name
+                ),
+                Err(e) => {
+                    eprintln!("failed to disconnect player, err: {e}")
                 }
-            });
+            }
+        };
+    }
+
+    /**
+     * The game the client plays or spectates, whose requests must be handled in order.
+     */
+    fn get_game(&self, client_id: u32) -> Option<Arc<Game>> {
+        let player = self.player_service.get_player(client_id)?;
+        player.get_game().or_else(|| player.get_spectating())
+    }
+
+    fn set_player_idle(&self, client_id: u32, idle: bool) {
+        if let Some(player) = self.player_service.get_player(client_id) {
+            player.set_idle(idle);
         }
     }
 
     #[cfg(not(test))]
     pub async fn new() -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let database_path =
+            std::env::var("DATABASE_PATH").unwrap_or(String::from(DEFAULT_DATABASE_PATH));
+        let stats_service = Arc::new(StatsService::new(MatchRepository::open(&database_path)?));
+        let rating_service = Arc::new(RatingService::new(RatingRepository::open(&database_path)?));
         let lobby_service = Arc::new(LobbyService::new());
-        let game_service = Arc::new(GameService::new().await?);
+        let game_service =
+            Arc::new(GameService::new(stats_service.clone(), lobby_service.clone()).await?);
         let player_service = Arc::new(PlayerService::new(
             lobby_service.clone(),
             game_service.clone(),
         ));
+        let chat_service = Arc::new(ChatService::new());
+        let account_service = Arc::new(AccountService::new(AccountRepository::open(
+            &database_path,
+        )?));
+        let friend_service = Arc::new(FriendService::new(
+            player_service.clone(),
+            account_service.clone(),
+        ));
+        let matchmaking_service = Arc::new(MatchmakingService::new(
+            player_service.clone(),
+            lobby_service.clone(),
+        ));
         let router = Arc::new(Router::new());
         router
             .register_controller(
                 Operation::Connect,
-                Box::new(ConnectController::new(player_service.clone())),
+                Box::new(ConnectController::new(
+                    player_service.clone(),
+                    account_service.clone(),
+                    Arc::new(NamePolicy::load().await?),
+                    rating_service.clone(),
+                )),
             )
             .register_controller(
                 Operation::Disconnect,
-                Box::new(DisconnectController::new(player_service.clone())),
+                Box::new(DisconnectController::new(
+                    player_service.clone(),
+                    matchmaking_service.clone(),
+                )),
+            )
+            .register_controller(
+                Operation::Reconnect,
+                Box::new(ReconnectController::new(player_service.clone())),
             )
             .register_controller(Operation::Heartbeat, Box::new(HeartbeatController::new()))
+            .register_controller(
+                Operation::Pong,
+                Box::new(PongController::new(player_service.clone())),
+            )
             .register_controller(
                 Operation::CreateLobby,
                 Box::new(CreateController::new(
@@ -189,18 +424,42 @@ impl Server {
             )
             .register_controller(
                 Operation::ListLobby,
-                Box::new(ListController::new(lobby_service.clone())),
+                Box::new(ListController::new(
+                    player_service.clone(),
+                    lobby_service.clone(),
+                )),
             )
             .register_controller(
                 Operation::QuitLobby,
                 Box::new(QuitController::new(
                     player_service.clone(),
                     lobby_service.clone(),
+                    game_service.clone(),
+                )),
+            )
+            .register_controller(
+                Operation::UpdateLobby,
+                Box::new(UpdateController::new(
+                    player_service.clone(),
+                    lobby_service.clone(),
+                    game_service.clone(),
+                )),
+            )
+            .register_controller(
+                Operation::Kick,
+                Box::new(KickController::new(
+                    player_service.clone(),
+                    lobby_service.clone(),
+                    game_service.clone(),
                 )),
             )
             .register_controller(
                 Operation::Ready,
-                Box::new(ReadyController::new(player_service.clone())),
+                Box::new(ReadyController::new(
+                    player_service.clone(),
+                    lobby_service.clone(),
+                    game_service.clone(),
+                )),
             )
             .register_controller(
                 Operation::StartGame,
@@ -243,12 +502,130 @@ impl Server {
                     player_service.clone(),
                     game_service.clone(),
                 )),
+            )
+            .register_controller(
+                Operation::Chat,
+                Box::new(SendController::new(player_service.clone(), chat_service)),
+            )
+            .register_controller(
+                Operation::Rematch,
+                Box::new(RematchController::new(
+                    player_service.clone(),
+                    lobby_service.clone(),
+                    game_service.clone(),
+                )),
+            )
+            .register_controller(
+                Operation::QuickPlay,
+                Box::new(QuickPlayController::new(
+                    player_service.clone(),
+                    matchmaking_service.clone(),
+                )),
+            )
+            .register_controller(
+                Operation::CancelQuickPlay,
+                Box::new(CancelQuickPlayController::new(
+                    player_service.clone(),
+                    matchmaking_service.clone(),
+                )),
+            )
+            .register_controller(
+                Operation::Spectate,
+                Box::new(SpectateController::new(
+                    player_service.clone(),
+                    lobby_service.clone(),
+                    game_service.clone(),
+                )),
+            )
+            .register_controller(
+                Operation::GetBoard,
+                Box::new(GetBoardController::new(player_service.clone())),
+            )
+            .register_controller(
+                Operation::VoteKick,
+                Box::new(VoteKickController::new(
+                    player_service.clone(),
+                    game_service.clone(),
+                )),
+            )
+            .register_controller(
+                Operation::StopSpectating,
+                Box::new(StopSpectatingController::new(
+                    player_service.clone(),
+                    game_service,
+                )),
+            )
+            .register_controller(
+                Operation::GetProfile,
+                Box::new(ProfileController::new(
+                    player_service.clone(),
+                    stats_service.clone(),
+                )),
+            )
+            .register_controller(
+                Operation::GetMatchHistory,
+                Box::new(HistoryController::new(
+                    player_service.clone(),
+                    stats_service,
+                )),
+            )
+            .register_controller(
+                Operation::GetLeaderboard,
+                Box::new(LeaderboardController::new(
+                    player_service.clone(),
+                    rating_service,
+                )),
+            )
+            .register_controller(
+                Operation::AddFriend,
+                Box::new(AddFriendController::new(
+                    player_service.clone(),
+                    friend_service.clone(),
+                )),
+            )
+            .register_controller(
+                Operation::AcceptFriend,
+                Box::new(AcceptFriendController::new(
+                    player_service.clone(),
+                    friend_service.clone(),
+                )),
+            )
+            .register_controller(
+                Operation::RemoveFriend,
+                Box::new(RemoveFriendController::new(
+                    player_service.clone(),
+                    friend_service.clone(),
+                )),
+            )
+            .register_controller(
+                Operation::ListFriends,
+                Box::new(ListFriendsController::new(
+                    player_service.clone(),
+                    friend_service.clone(),
+                )),
+            )
+            .register_controller(
+                Operation::Invite,
+                Box::new(InviteController::new(
+                    player_service.clone(),
+                    friend_service,
+                )),
             );
         Ok(Self {
             host: String::from("0.0.0.0"),
             port: 45678,
+            websocket_port: match std::env::var("WEBSOCKET_PORT") {
+                Ok(port) => port.trim().parse().map_err(|_| "Invalid WEBSOCKET_PORT")?,
+                Err(_) => DEFAULT_WEBSOCKET_PORT,
+            },
             player_service,
+            matchmaking_service,
             router,
+            connection_settings: ConnectionSettings::from_env()?,
+            tls: match TlsSettings::from_env()? {
+                Some(settings) => Some(settings.load()?),
+                None => None,
+            },
         })
     }
 }
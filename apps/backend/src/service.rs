@@ -1,3 +1,10 @@
+pub mod account_service;
+pub mod blocking;
+pub mod chat_service;
+pub mod friend_service;
 pub mod game_service;
 pub mod lobby_service;
+pub mod matchmaking_service;
 pub mod player_service;
+pub mod rating_service;
+pub mod stats_service;
@@ -0,0 +1,229 @@
+use crate::account::{
+    account::Account,
+    account_repository::AccountRepository,
+    friendship::{FriendStatus, Friendship},
+    password::{hash_password, verify_password},
+};
+use crate::error::ServiceError;
+
+pub const MIN_USERNAME_LENGTH: usize = 3;
+pub const MAX_USERNAME_LENGTH: usize = 16;
+pub const MIN_PASSWORD_LENGTH: usize = 8;
+pub const MAX_PASSWORD_LENGTH: usize = 64;
+
+#[derive(Debug)]
+pub struct AccountService {
+    repository: AccountRepository,
+}
+
+impl AccountService {
+    pub fn new(repository: AccountRepository) -> Self {
+        Self { repository }
+    }
+
+    pub fn register(&self, username: &str, password: &str) -> Result<Account, ServiceError> {
+        let username_length = username.chars().count();
+        if !(MIN_USERNAME_LENGTH..=MAX_USERNAME_LENGTH).contains(&username_length)
+            || !username
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            return Err(ServiceError::InvalidUsername);
+        }
+        if !(MIN_PASSWORD_LENGTH..=MAX_PASSWORD_LENGTH).contains(&password.chars().count()) {
+            return Err(ServiceError::InvalidPassword);
+        }
+        if self.repository.find_by_username(username)?.is_some() {
+            return Err(ServiceError::UsernameTaken);
+        }
+        Ok(self
+            .repository
+            .create_account(username, &hash_password(password)?)?)
+    }
+
+    pub fn is_username_registered(&self, username: &str) -> Result<bool, ServiceError> {
+        Ok(self.repository.find_by_username(username)?.is_some())
+    }
+
+    pub fn authenticate(&self, username: &str, password: &str) -> Result<Account, ServiceError> {
+        match self.repository.find_by_username(username)? {
+            Some((account, password_hash)) if verify_password(password, &password_hash) => {
+                Ok(account)
+            }
+            _ => Err(ServiceError::InvalidCredentials),
+        }
+    }
+
+    pub fn request_friend(
+        &self,
+        account_id: u32,
+        username: &str,
+    ) -> Result<(Account, Friendship), ServiceError> {
+        let other = match self.repository.find_by_username(username)? {
+            Some((other, _)) => other,
+            None => return Err(ServiceError::AccountNotFound),
+        };
+        if other.id == account_id {
+            return Err(ServiceError::CannotFriendSelf);
+        }
+        match self.repository.find_friendship(account_id, other.id)? {
+            Some(friendship) => match friendship.get_status(account_id) {
+                FriendStatus::Accepted => Err(ServiceError::AlreadyFriends),
+                FriendStatus::Outgoing => Err(ServiceError::FriendRequestAlreadySent),
+                FriendStatus::Incoming => {
+                    self.repository
+                        .accept_friend_request(other.id, account_id)?;
+                    Ok((other.clone(), Friendship::new(other.id, account_id, true)))
+                }
+            },
+            None => {
+                let friendship = self
+                    .repository
+                    .create_friend_request(account_id, other.id)?;
+                Ok((other, friendship))
+            }
+        }
+    }
+
+    pub fn accept_friend(
+        &self,
+        account_id: u32,
+        requester_id: u32,
+    ) -> Result<Account, ServiceError> {
+        if !self
+            .repository
+            .accept_friend_request(requester_id, account_id)?
+        {
+            return Err(ServiceError::FriendRequestNotFound);
+        }
+        match self.repository.find_by_id(requester_id)? {
+            Some(requester) => Ok(requester),
+            None => Err(ServiceError::AccountNotFound),
+        }
+    }
+
+    pub fn remove_friend(&self, account_id: u32, other_id: u32) -> Result<(), ServiceError> {
+        match self.repository.delete_friendship(account_id, other_id)? {
+            true => Ok(()),
+            false => Err(ServiceError::FriendNotFound),
+        }
+    }
+
+    pub fn are_friends(&self, account_id: u32, other_id: u32) -> Result<bool, ServiceError> {
+        Ok(self
+            .repository
+            .find_friendship(account_id, other_id)?
+            .is_some_and(|friendship| friendship.accepted))
+    }
+
+    pub fn get_friendships(
+        &self,
+        account_id: u32,
+    ) -> Result<Vec<(Account, Friendship)>, ServiceError> {
+        Ok(self.repository.get_friendships(account_id)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    fn account_service() -> Result<AccountService, Box<dyn Error + Send + Sync>> {
+        Ok(AccountService::new(AccountRepository::open_in_memory()?))
+    }
+
+    #[test]
+    fn register_with_valid_credentials_should_authenticate(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let service = account_service()?;
+        let account = service.register("test_user", "password")?;
+        assert_eq!(service.authenticate("test_user", "password")?, account);
+        Ok(())
+    }
+
+    #[test]
+    fn register_with_invalid_username_should_return_error(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let service = account_service()?;
+        assert!(service.register("te", "password").is_err());
+        assert!(service.register("test user", "password").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn register_with_short_password_should_return_error() -> Result<(), Box<dyn Error + Send + Sync>>
+    {
+        let service = account_service()?;
+        assert!(service.register("test", "1234").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn register_with_taken_username_should_return_error() -> Result<(), Box<dyn Error + Send + Sync>>
+    {
+        let service = account_service()?;
+        service.register("test", "password")?;
+        assert!(service.register("TEST", "password").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn authenticate_with_wrong_password_should_return_error(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let service = account_service()?;
+        service.register("test", "password")?;
+        assert!(service.authenticate("test", "wrong password").is_err());
+        assert!(service.authenticate("unknown", "password").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn request_friend_with_incoming_request_should_accept_it(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let account_service = account_service()?;
+        let account1 = account_service.register("test1", "password")?;
+        let account2 = account_service.register("test2", "password")?;
+        let (_, friendship) = account_service.request_friend(account1.id, "test2")?;
+        assert!(!friendship.accepted);
+        assert!(account_service
+            .request_friend(account1.id, "test2")
+            .is_err());
+        let (other, friendship) = account_service.request_friend(account2.id, "TEST1")?;
+        assert_eq!(other, account1);
+        assert!(friendship.accepted);
+        assert!(account_service.are_friends(account1.id, account2.id)?);
+        Ok(())
+    }
+
+    #[test]
+    fn request_friend_with_own_username_should_return_error(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let account_service = account_service()?;
+        let account = account_service.register("test1", "password")?;
+        assert!(account_service.request_friend(account.id, "test1").is_err());
+        assert!(account_service
+            .request_friend(account.id, "unknown")
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn accept_friend_without_request_should_return_error(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let account_service = account_service()?;
+        let account1 = account_service.register("test1", "password")?;
+        let account2 = account_service.register("test2", "password")?;
+        assert!(account_service
+            .accept_friend(account1.id, account2.id)
+            .is_err());
+        account_service.request_friend(account2.id, "test1")?;
+        assert_eq!(
+            account_service.accept_friend(account1.id, account2.id)?,
+            account2
+        );
+        account_service.remove_friend(account2.id, account1.id)?;
+        assert!(!account_service.are_friends(account1.id, account2.id)?);
+        Ok(())
+    }
+}
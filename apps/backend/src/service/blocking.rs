@@ -0,0 +1,31 @@
+use crate::error::ServiceError;
+
+/**
+ * Run blocking work, such as password hashing or a database query, on the blocking thread pool.
+ * Calling it directly from a request would stall the runtime worker and every request of the
+ * game waiting behind it.
+ */
+pub async fn run_blocking<T, F>(f: F) -> Result<T, ServiceError>
+where
+    F: FnOnce() -> Result<T, ServiceError> + Send + 'static,
+    T: Send + 'static,
+{
+    match tokio::task::spawn_blocking(f).await {
+        Ok(result) => result,
+        Err(e) => Err(ServiceError::Internal(e.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn run_blocking_with_error_should_return_it() {
+        assert_eq!(run_blocking(|| Ok(1)).await, Ok(1));
+        assert_eq!(
+            run_blocking(|| Err::<(), _>(ServiceError::AccountNotFound)).await,
+            Err(ServiceError::AccountNotFound)
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use std::sync::Arc;
+
+use crate::{chat::chat_message::ChatMessage, error::ServiceError, player::Player};
+
+#[cfg(not(test))]
+use crate::frame::{Response, ResponseData};
+#[cfg(not(test))]
+use crate::model::game::broadcast::{GameBroadcast, GameEvent};
+#[cfg(not(test))]
+use crate::model::lobby::broadcast::{LobbyBroadcast, LobbyEvent};
+#[cfg(not(test))]
+use crate::model::state::State;
+
+pub const MAX_CHAT_MESSAGE_LENGTH: usize = 200;
+
+#[derive(Debug, Default)]
+pub struct ChatService {}
+
+impl ChatService {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub async fn send_message(
+        &self,
+        player: Arc<Player>,
+        content: &str,
+    ) -> Result<ChatMessage, ServiceError> {
+        let content = content.trim();
+        if content.is_empty() {
+            return Err(ServiceError::EmptyMessage);
+        }
+        if content.chars().count() > MAX_CHAT_MESSAGE_LENGTH {
+            return Err(ServiceError::MessageTooLong);
+        }
+        let game = player.get_game();
+        let lobby = player.get_lobby();
+        if game.is_none() && lobby.is_none() {
+            return Err(ServiceError::NotInLobbyOrGame);
+        }
+        if !player.try_chat() {
+            return Err(ServiceError::SendingTooFast);
+        }
+        let message = ChatMessage::new(player.clone(), content.to_string());
+        if let Some(game) = game {
+            game.add_chat_message(message.clone());
+            #[cfg(not(test))]
+            for game_player in game.get_players() {
+                if game_player.player == player {
+                    continue;
+                }
+                let chat_message = Some(crate::model::chat::message::ChatMessage::from(&message));
+                if let Err(e) = game_player.player.send_message(Response::new(
+                    State::GameBroadcast as u32,
+                    Arc::new(ResponseData::GameBroadcast(GameBroadcast {
+                        event: GameEvent::Chat as i32,
+                        board: None,
+                        players: None,
+                        current_player: None,
+                        next_player: None,
+                        words: None,
+                        cards: None,
+                        chat_message,
+                        spectators: None,
+                        afk_player: None,
+                        kick_votes: 0,
+                        board_delta: None,
+                        board_sequence: 0,
+                    })),
+                )) {
+                    eprintln!("Error sending game broadcast: {}", e);
+                }
+            }
+        } else if let Some(lobby) = lobby {
+            lobby.add_chat_message(message.clone());
+            #[cfg(not(test))]
+            for lobby_player in lobby.get_players() {
+                if lobby_player.player == player {
+                    continue;
+                }
+                let chat_message = Some(crate::model::chat::message::ChatMessage::from(&message));
+                let lobby = Some(crate::model::lobby::lobby::Lobby::from(lobby.as_ref()));
+                if let Err(e) = lobby_player.player.send_message(Response::new(
+                    State::LobbyBroadcast as u32,
+                    Arc::new(ResponseData::LobbyBroadcast(LobbyBroadcast {
+                        event: LobbyEvent::Chat as i32,
+                        lobby,
+                        cards: None,
+                        current_player: None,
+                        next_player: None,
+                        chat_message,
+                    })),
+                )) {
+                    eprintln!("Error sending lobby broadcast: {}", e);
+                }
+            }
+        }
+        Ok(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{game::game::Game, lobby::lobby::Lobby, player::CHAT_RATE_LIMIT};
+
+    use super::*;
+    use std::error::Error;
+
+    #[tokio::test]
+    async fn send_message_with_player_in_lobby_should_add_message_to_lobby_history(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let service = ChatService::new();
+        let player = Arc::new(Player::new(0, String::from("test")));
+        let lobby = Arc::new(Lobby::new(0, 4, player.clone()));
+        player.set_lobby(Some(lobby.clone()));
+        service.send_message(player, " hello ").await?;
+        let history = lobby.get_chat_history();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].content, "hello");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn send_message_with_player_in_game_should_add_message_to_game_history(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let service = ChatService::new();
+        let player = Arc::new(Player::new(0, String::from("test")));
+        let lobby = Arc::new(Lobby::new(0, 4, player.clone()));
+        let game = Arc::new(Game::new(0, vec![player.clone()]));
+        player.set_lobby(Some(lobby.clone()));
+        player.set_game(Some(game.clone()));
+        service.send_message(player, "hello").await?;
+        assert_eq!(game.get_chat_history().len(), 1);
+        assert!(lobby.get_chat_history().is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn send_message_with_player_not_in_lobby_should_return_error() {
+        let service = ChatService::new();
+        let player = Arc::new(Player::new(0, String::from("test")));
+        assert!(service.send_message(player, "hello").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn send_message_with_empty_or_too_long_message_should_return_error() {
+        let service = ChatService::new();
+        let player = Arc::new(Player::new(0, String::from("test")));
+        player.set_lobby(Some(Arc::new(Lobby::new(0, 4, player.clone()))));
+        assert!(service.send_message(player.clone(), "  ").await.is_err());
+        assert!(service
+            .send_message(player, &"a".repeat(MAX_CHAT_MESSAGE_LENGTH + 1))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn send_message_too_fast_should_return_error() -> Result<(), Box<dyn Error + Send + Sync>>
+    {
+        let service = ChatService::new();
+        let player = Arc::new(Player::new(0, String::from("test")));
+        player.set_lobby(Some(Arc::new(Lobby::new(0, 4, player.clone()))));
+        for _ in 0..CHAT_RATE_LIMIT {
+            service.send_message(player.clone(), "hello").await?;
+        }
+        assert!(service.send_message(player, "hello").await.is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,373 @@
+use std::sync::Arc;
+
+use crate::{
+    account::{
+        account::Account,
+        friend::Friend,
+        friendship::{FriendStatus, Friendship},
+    },
+    error::ServiceError,
+    player::{Player, Presence},
+};
+
+use super::{
+    account_service::AccountService, blocking::run_blocking, player_service::PlayerService,
+};
+
+#[cfg(not(test))]
+use crate::frame::{Response, ResponseData};
+#[cfg(not(test))]
+use crate::model::friend::{
+    broadcast::{FriendBroadcast, FriendEvent},
+    invite::Invite,
+};
+#[cfg(not(test))]
+use crate::model::state::State;
+
+#[derive(Debug)]
+pub struct FriendService {
+    player_service: Arc<PlayerService>,
+    account_service: Arc<AccountService>,
+}
+
+impl FriendService {
+    pub fn new(player_service: Arc<PlayerService>, account_service: Arc<AccountService>) -> Self {
+        Self {
+            player_service,
+            account_service,
+        }
+    }
+
+    fn get_account(player: &Player) -> Result<Account, ServiceError> {
+        match player.get_account() {
+            Some(account) => Ok(account),
+            None => Err(ServiceError::NoAccount),
+        }
+    }
+
+    fn get_friend(&self, account: Account, account_id: u32, friendship: &Friendship) -> Friend {
+        let status = friendship.get_status(account_id);
+        let player = match status {
+            FriendStatus::Accepted => self.player_service.get_player_by_account_id(account.id),
+            _ => None,
+        };
+        Friend {
+            account,
+            status,
+            presence: player
+                .as_ref()
+                .map_or(Presence::Offline, |player| player.get_presence()),
+            lobby_id: player
+                .and_then(|player| player.get_lobby())
+                .filter(|lobby| !lobby.is_private())
+                .map(|lobby| lobby.get_id()),
+        }
+    }
+
+    pub async fn get_friends(&self, player: &Player) -> Result<Vec<Friend>, ServiceError> {
+        let account = FriendService::get_account(player)?;
+        let account_service = self.account_service.clone();
+        Ok(
+            run_blocking(move || account_service.get_friendships(account.id))
+                .await?
+                .into_iter()
+                .map(|(other, friendship)| self.get_friend(other, account.id, &friendship))
+                .collect(),
+        )
+    }
+
+    pub async fn add_friend(
+        &self,
+        player: &Player,
+        username: &str,
+    ) -> Result<Friend, ServiceError> {
+        let account = FriendService::get_account(player)?;
+        let account_service = self.account_service.clone();
+        let (account_id, username) = (account.id, username.to_string());
+        let (other, friendship) =
+            run_blocking(move || account_service.request_friend(account_id, &username)).await?;
+        #[cfg(not(test))]
+        self.notify_friend(
+            &account,
+            &other,
+            &friendship,
+            match friendship.accepted {
+                true => FriendEvent::Accept,
+                false => FriendEvent::Request,
+            },
+        )
+        .await;
+        Ok(self.get_friend(other, account.id, &friendship))
+    }
+
+    pub async fn accept_friend(
+        &self,
+        player: &Player,
+        requester_id: u32,
+    ) -> Result<Friend, ServiceError> {
+        let account = FriendService::get_account(player)?;
+        let account_service = self.account_service.clone();
+        let id = account.id;
+        let requester =
+            run_blocking(move || account_service.accept_friend(id, requester_id)).await?;
+        let friendship = Friendship::new(requester_id, account.id, true);
+        #[cfg(not(test))]
+        self.notify_friend(&account, &requester, &friendship, FriendEvent::Accept)
+            .await;
+        Ok(self.get_friend(requester, account.id, &friendship))
+    }
+
+    pub async fn remove_friend(
+        &self,
+        player: &Player,
+        account_id: u32,
+    ) -> Result<(), ServiceError> {
+        let account = FriendService::get_account(player)?;
+        let account_service = self.account_service.clone();
+        let id = account.id;
+        run_blocking(move || account_service.remove_friend(id, account_id)).await?;
+        #[cfg(not(test))]
+        if let Some(other) = self
+            .player_service
+            .get_player_by_account_id(account_id)
+            .and_then(|other| other.get_account())
+        {
+            self.notify_friend(
+                &account,
+                &other,
+                &Friendship::new(account.id, account_id, false),
+                FriendEvent::Remove,
+            )
+            .await;
+        }
+        Ok(())
+    }
+
+    /**
+     * Invite a friend to the player's lobby. An invitation from the leader lets the friend join
+     * with the invite code, skipping the lobby password.
+     */
+    pub async fn invite(
+        &self,
+        player: &Arc<Player>,
+        account_id: u32,
+    ) -> Result<Arc<Player>, ServiceError> {
+        let account = FriendService::get_account(player)?;
+        let account_service = self.account_service.clone();
+        let id = account.id;
+        if !run_blocking(move || account_service.are_friends(id, account_id)).await? {
+            return Err(ServiceError::NotFriends);
+        }
+        let lobby = match player.get_lobby() {
+            Some(lobby) => lobby,
+            None => return Err(ServiceError::NotInLobby),
+        };
+        if lobby.is_started() {
+            return Err(ServiceError::GameAlreadyStarted);
+        }
+        if lobby.is_full() {
+            return Err(ServiceError::LobbyFull);
+        }
+        let friend = match self.player_service.get_player_by_account_id(account_id) {
+            Some(friend) => friend,
+            None => return Err(ServiceError::FriendOffline),
+        };
+        if friend.get_lobby().as_ref() == Some(&lobby) {
+            return Err(ServiceError::FriendAlreadyInLobby);
+        }
+        if *player == lobby.leader {
+            lobby.invite_player(&friend);
+        }
+        #[cfg(not(test))]
+        FriendService::send_friend_broadcast(
+            friend.clone(),
+            FriendBroadcast {
+                event: FriendEvent::Invite as i32,
+                friend: None,
+                invite: Some(Invite {
+                    player: Some(crate::model::player::player::Player::from(player.clone())),
+                    lobby_id: lobby.get_id(),
+                    lobby_name: lobby.get_name(),
+                    invite_code: String::from(lobby.get_invite_code()),
+                }),
+            },
+        )
+        .await;
+        Ok(friend)
+    }
+
+    #[cfg(not(test))]
+    async fn notify_friend(
+        &self,
+        account: &Account,
+        other: &Account,
+        friendship: &Friendship,
+        event: FriendEvent,
+    ) {
+        let other_player = match self.player_service.get_player_by_account_id(other.id) {
+            Some(other_player) => other_player,
+            None => return,
+        };
+        let friend = self.get_friend(account.clone(), other.id, friendship);
+        FriendService::send_friend_broadcast(
+            other_player,
+            FriendBroadcast {
+                event: event as i32,
+                friend: Some(crate::model::friend::friend::Friend::from(&friend)),
+                invite: None,
+            },
+        )
+        .await;
+    }
+
+    #[cfg(not(test))]
+    async fn send_friend_broadcast(player: Arc<Player>, broadcast: FriendBroadcast) {
+        if let Err(e) = player.send_message(Response::new(
+            State::FriendBroadcast as u32,
+            Arc::new(ResponseData::FriendBroadcast(broadcast)),
+        )) {
+            eprintln!("Error sending friend broadcast: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, error::Error};
+
+    use super::*;
+    use crate::{
+        account::account_repository::AccountRepository,
+        service::{game_service::GameService, lobby_service::LobbyService},
+    };
+
+    struct TestContext {
+        friend_service: FriendService,
+        lobby_service: Arc<LobbyService>,
+        player1: Arc<Player>,
+        player2: Arc<Player>,
+    }
+
+    fn test_context() -> Result<TestContext, Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let player_service = Arc::new(PlayerService::new(
+            lobby_service.clone(),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        let account_service = Arc::new(AccountService::new(AccountRepository::open_in_memory()?));
+        let player1 = player_service.add_player(0, String::from("test1"));
+        player1.set_account(Some(account_service.register("test1", "password")?));
+        let player2 = player_service.add_player(1, String::from("test2"));
+        player2.set_account(Some(account_service.register("test2", "password")?));
+        Ok(TestContext {
+            friend_service: FriendService::new(player_service, account_service),
+            lobby_service,
+            player1,
+            player2,
+        })
+    }
+
+    #[tokio::test]
+    async fn get_friends_with_accepted_friend_in_lobby_should_show_presence(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let context = test_context()?;
+        let friend = context
+            .friend_service
+            .add_friend(&context.player1, "test2")
+            .await?;
+        assert_eq!(friend.status, FriendStatus::Outgoing);
+        assert_eq!(friend.presence, Presence::Offline);
+        let lobby = context
+            .lobby_service
+            .create_lobby(context.player1.clone(), 4)
+            .await?;
+        let friend = context
+            .friend_service
+            .accept_friend(&context.player2, context.player1.get_account_id().unwrap())
+            .await?;
+        assert_eq!(friend.status, FriendStatus::Accepted);
+        assert_eq!(friend.presence, Presence::InLobby);
+        assert_eq!(friend.lobby_id, Some(lobby.get_id()));
+        let friends = context.friend_service.get_friends(&context.player1).await?;
+        assert_eq!(friends.len(), 1);
+        assert_eq!(friends[0].presence, Presence::Online);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn invite_with_friend_should_invite_friend_to_lobby(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let context = test_context()?;
+        let account_id = context.player2.get_account_id().unwrap();
+        assert!(context
+            .friend_service
+            .invite(&context.player1, account_id)
+            .await
+            .is_err());
+        context
+            .friend_service
+            .add_friend(&context.player1, "test2")
+            .await?;
+        context
+            .friend_service
+            .add_friend(&context.player2, "test1")
+            .await?;
+        assert!(context
+            .friend_service
+            .invite(&context.player1, account_id)
+            .await
+            .is_err());
+        let lobby = context
+            .lobby_service
+            .create_lobby(context.player1.clone(), 4)
+            .await?;
+        context
+            .friend_service
+            .invite(&context.player1, account_id)
+            .await?;
+        assert!(lobby.is_invited(&context.player2));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn invite_with_member_should_not_skip_password(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let context = test_context()?;
+        let account_id = context.player2.get_account_id().unwrap();
+        context
+            .friend_service
+            .add_friend(&context.player1, "test2")
+            .await?;
+        context
+            .friend_service
+            .add_friend(&context.player2, "test1")
+            .await?;
+        let lobby = context
+            .lobby_service
+            .create_lobby(Arc::new(Player::new(2, String::from("test3"))), 4)
+            .await?;
+        context
+            .lobby_service
+            .add_player_to_lobby(context.player1.clone(), lobby.clone())
+            .await?;
+        context
+            .friend_service
+            .invite(&context.player1, account_id)
+            .await?;
+        assert!(!lobby.is_invited(&context.player2));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn remove_friend_with_guest_should_return_error(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let context = test_context()?;
+        context.player1.set_account(None);
+        assert!(context
+            .friend_service
+            .remove_friend(&context.player1, 2)
+            .await
+            .is_err());
+        Ok(())
+    }
+}
@@ -12,7 +12,9 @@ use tokio::{
 };
 use tokio::{task, time::sleep};
 
+use crate::protocol::Feature;
 use crate::{
+    error::ServiceError,
     game::{
         board::BOARD_SIZE,
         card::Card,
@@ -20,31 +22,49 @@ use crate::{
         game_player::GamePlayer,
         tile::Tile,
     },
-    lobby::lobby::Lobby,
+    lobby::{
+        lobby::Lobby,
+        lobby_settings::{DEFAULT_AFK_REMOVAL_TURNS, DEFAULT_AFK_TURNS},
+    },
+    model::lobby::list::{LobbyListBroadcast, LobbyListEvent},
     player::Player,
+    stats::match_record::MatchRecord,
+};
+
+use super::{
+    lobby_service::LobbyService, rating_service::RatingService, stats_service::StatsService,
 };
 
 #[cfg(not(test))]
 use crate::frame::{Response, ResponseData};
-#[cfg(not(test))]
+use crate::model::game::board::BoardDelta;
+use crate::model::game::broadcast::GameBroadcast;
 use crate::model::game::broadcast::GameEvent;
 #[cfg(not(test))]
 use crate::model::game::cards::Cards;
 #[cfg(not(test))]
 use crate::model::lobby::broadcast::{LobbyBroadcast, LobbyEvent};
 #[cfg(not(test))]
-use crate::model::{game::broadcast::GameBroadcast, state::State};
+use crate::model::state::State;
+
+pub const TURN_DURATION: Duration = Duration::from_secs(30);
+pub const AFK_TURN_DURATION: Duration = Duration::from_secs(2);
 
 #[derive(Debug)]
 pub struct GameService {
     next_game_id: Mutex<u32>,
     games: Mutex<HashMap<u32, Arc<Game>>>,
     wordlist: HashSet<String>,
+    stats_service: Option<Arc<StatsService>>,
+    lobby_service: Option<Arc<LobbyService>>,
 }
 
 impl GameService {
     #[cfg(not(test))]
-    pub async fn new() -> Result<Self, Box<dyn Error + Send + Sync>> {
+    pub async fn new(
+        stats_service: Arc<StatsService>,
+        lobby_service: Arc<LobbyService>,
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
         let mut wordlist = HashSet::new();
         let file = File::open("assets/wordlist.txt").await?;
         let mut reader = BufReader::new(file).lines();
@@ -55,6 +75,8 @@ impl GameService {
             next_game_id: Mutex::new(0),
             games: Mutex::new(HashMap::new()),
             wordlist,
+            stats_service: Some(stats_service),
+            lobby_service: Some(lobby_service),
         })
     }
 
@@ -64,16 +86,21 @@ impl GameService {
             next_game_id: Mutex::new(0),
             games: Mutex::new(HashMap::new()),
             wordlist,
+            stats_service: None,
+            lobby_service: None,
         }
     }
 
-    pub fn start_game(
+    pub async fn start_game(
         game_service: Arc<GameService>,
         player: Arc<Player>,
         lobby: Arc<Lobby>,
-    ) -> Result<Arc<Game>, Box<dyn Error + Send + Sync>> {
+    ) -> Result<Arc<Game>, ServiceError> {
         if player != lobby.leader {
-            return Err("Only leader can start game".into());
+            return Err(ServiceError::NotLeader);
+        }
+        if lobby.is_rematch_vote_open() {
+            return Err(ServiceError::RematchInProgress);
         }
         let mut check = true;
         for player in lobby.get_players() {
@@ -83,8 +110,9 @@ impl GameService {
             }
         }
         if !check {
-            return Err("Not all players are ready".into());
+            return Err(ServiceError::NotAllReady);
         }
+        lobby.cancel_auto_start_task();
         let game = {
             let mut next_id = game_service.next_game_id.lock().unwrap();
             let game = Arc::new(Game::new(
@@ -103,73 +131,94 @@ impl GameService {
             *next_id += 1;
             game
         };
+        let settings = lobby.get_settings();
+        game.set_spectator_settings(settings.allow_spectators, settings.spectator_delay);
+        game.set_settings(settings);
         for game_player in game.get_players() {
             game_player.player.set_game(Some(game.clone()));
             if game_player.player == player {
                 continue;
             }
             #[cfg(not(test))]
-            {
-                let game = game.clone();
-                tokio::spawn(async move {
-                    if let Err(e) = game_player
-                        .clone()
-                        .player
-                        .send_message(Response::new(
-                            State::LobbyBroadcast as u32,
-                            Arc::new(ResponseData::LobbyBroadcast(LobbyBroadcast {
-                                event: LobbyEvent::Start as i32,
-                                lobby: None,
-                                cards: Some(Cards::from(&game_player.get_cards())),
-                                current_player: Some(crate::model::player::player::Player::from(
-                                    game.get_player_in_this_turn(),
-                                )),
-                                next_player: match game.get_next_turn_player() {
-                                    Some(game_player) => Some(
-                                        crate::model::player::player::Player::from(game_player),
-                                    ),
-                                    None => None,
-                                },
-                            })),
-                        ))
-                        .await
-                    {
-                        eprintln!("Error sending lobby broadcast: {}", e);
-                    }
-                });
-            }
+            GameService::send_start_broadcast(game.clone(), lobby.clone(), game_player).await;
         }
+        game_service.notify_lobby_list(&lobby);
         GameService::start_countdown(game_service, game.clone());
         Ok(game)
     }
 
+    #[cfg(not(test))]
+    pub async fn send_start_broadcast(
+        game: Arc<Game>,
+        lobby: Arc<Lobby>,
+        game_player: Arc<GamePlayer>,
+    ) {
+        if let Err(e) = game_player.clone().player.send_message(Response::new(
+            State::LobbyBroadcast as u32,
+            Arc::new(ResponseData::LobbyBroadcast(LobbyBroadcast {
+                event: LobbyEvent::Start as i32,
+                lobby: Some(crate::model::lobby::lobby::Lobby::from(lobby)),
+                cards: Some(Cards::from(&game_player.get_cards())),
+                current_player: Some(crate::model::player::player::Player::from(
+                    game.get_player_in_this_turn(),
+                )),
+                next_player: match game.get_next_turn_player() {
+                    Some(game_player) => {
+                        Some(crate::model::player::player::Player::from(game_player))
+                    }
+                    None => None,
+                },
+                chat_message: None,
+            })),
+        )) {
+            eprintln!("Error sending lobby broadcast: {}", e);
+        }
+    }
+
     pub fn get_game(&self, id: u32) -> Option<Arc<Game>> {
         self.games.lock().unwrap().get(&id).cloned()
     }
 
     /**
-     * Finish turn. Return true if the game is ended.
+     * Finish turn. Return true if the game is ended, the caller ends it with `end_game` once the
+     * turn is broadcast.
      */
-    fn finish_turn(
+    async fn finish_turn(
         game_service: Arc<GameService>,
         game: Arc<Game>,
-    ) -> Result<bool, Box<dyn Error + Send + Sync>> {
+    ) -> Result<bool, ServiceError> {
         let player_in_this_turn = game.get_player_in_this_turn();
         player_in_this_turn.get_new_card();
         game.cancel_timeout_task();
         game.next_turn();
         game.backup_board();
         if game.get_turns() > END_GAME_TURN {
-            game_service.clone().remove_game(game.clone())?;
-            #[cfg(not(test))]
-            GameService::boardcast_game_end(game);
+            if let Some(stats_service) = game_service.stats_service.clone() {
+                // the result is taken now, the database write must not hold up the game
+                let record = MatchRecord::from_game(&game);
+                let ratings = RatingService::rate_game(&game);
+                tokio::spawn(async move {
+                    if let Err(e) =
+                        StatsService::record_result(stats_service, record, ratings).await
+                    {
+                        eprintln!("failed to record game; err = {:?}", e);
+                    }
+                });
+            }
             return Ok(true);
         }
         Ok(false)
     }
 
+    async fn end_game(&self, game: Arc<Game>) -> Result<(), ServiceError> {
+        self.remove_game(game.clone())?;
+        #[cfg(not(test))]
+        GameService::boardcast_game_end(game).await;
+        Ok(())
+    }
+
     #[cfg(not(test))]
-    fn send_finish_turn_broadcast(
+    async fn send_finish_turn_broadcast(
         game: Arc<Game>,
         words: &Vec<String>,
         origin_player: Arc<GamePlayer>,
@@ -179,61 +228,186 @@ impl GameService {
             if game_player == origin_player && !send_to_origin_player {
                 continue;
             }
-            let game = game.clone();
             let board = Some(crate::model::game::board::Board::from({
                 &game.get_board().lock().unwrap().clone()
             }));
             let words = Some(crate::model::game::words::Words::from(words));
-            let origin_player = origin_player.clone();
-            tokio::spawn(async move {
-                if let Err(e) = game_player
-                    .player
-                    .send_message(Response::new(
-                        State::GameBroadcast as u32,
-                        Arc::new(ResponseData::GameBroadcast(GameBroadcast {
-                            event: GameEvent::FinishTurn as i32,
-                            board,
-                            players: None,
-                            current_player: Some(crate::model::player::player::Player::from(
-                                game.get_player_in_this_turn(),
-                            )),
-                            next_player: match game.get_next_turn_player() {
-                                Some(game_player) => {
-                                    Some(crate::model::player::player::Player::from(game_player))
-                                }
-                                None => None,
-                            },
-                            words,
-                            cards: match game_player == origin_player {
-                                true => Some(crate::model::game::cards::Cards::from(
-                                    &origin_player.get_cards(),
-                                )),
-                                false => None,
-                            },
-                        })),
-                    ))
-                    .await
-                {
-                    eprintln!("Error sending game broadcast: {}", e);
-                }
-            });
+            if let Err(e) = game_player.player.send_message(Response::new(
+                State::GameBroadcast as u32,
+                Arc::new(ResponseData::GameBroadcast(GameBroadcast {
+                    event: GameEvent::FinishTurn as i32,
+                    board,
+                    players: None,
+                    current_player: Some(crate::model::player::player::Player::from(
+                        game.get_player_in_this_turn(),
+                    )),
+                    next_player: match game.get_next_turn_player() {
+                        Some(game_player) => {
+                            Some(crate::model::player::player::Player::from(game_player))
+                        }
+                        None => None,
+                    },
+                    words,
+                    cards: match game_player == origin_player {
+                        true => Some(crate::model::game::cards::Cards::from(
+                            &origin_player.get_cards(),
+                        )),
+                        false => None,
+                    },
+                    chat_message: None,
+                    spectators: None,
+                    afk_player: None,
+                    kick_votes: 0,
+                    board_delta: None,
+                    board_sequence: game.get_board_sequence(),
+                })),
+            )) {
+                eprintln!("Error sending game broadcast: {}", e);
+            }
+        }
+        GameService::send_spectator_broadcast(
+            &game,
+            GameBroadcast {
+                event: GameEvent::FinishTurn as i32,
+                board: Some(crate::model::game::board::Board::from(
+                    &game.get_board().lock().unwrap().clone(),
+                )),
+                players: None,
+                current_player: Some(crate::model::player::player::Player::from(
+                    game.get_player_in_this_turn(),
+                )),
+                next_player: game
+                    .get_next_turn_player()
+                    .map(crate::model::player::player::Player::from),
+                words: Some(crate::model::game::words::Words::from(words)),
+                cards: None,
+                chat_message: None,
+                spectators: None,
+                afk_player: None,
+                kick_votes: 0,
+                board_delta: None,
+                board_sequence: game.get_board_sequence(),
+            },
+        );
+    }
+
+    #[cfg(not(test))]
+    fn send_spectator_broadcast(game: &Game, broadcast: GameBroadcast) {
+        for spectator in game.get_spectators() {
+            let broadcast = GameBroadcast {
+                cards: None,
+                ..broadcast.clone()
+            };
+            game.send_to_spectator(
+                spectator,
+                Response::new(
+                    State::GameBroadcast as u32,
+                    Arc::new(ResponseData::GameBroadcast(broadcast)),
+                ),
+            );
+        }
+    }
+
+    #[cfg(not(test))]
+    async fn send_spectators_update(game: Arc<Game>) {
+        let players = game
+            .get_players()
+            .into_iter()
+            .map(|game_player| game_player.player.clone())
+            .chain(game.get_spectators());
+        for player in players {
+            if let Err(e) = player.send_message(Response::new(
+                State::GameBroadcast as u32,
+                Arc::new(ResponseData::GameBroadcast(GameBroadcast {
+                    event: GameEvent::Spectate as i32,
+                    board: None,
+                    players: Some(crate::model::player::players::Players::from(
+                        &game.get_players(),
+                    )),
+                    current_player: None,
+                    next_player: None,
+                    words: None,
+                    cards: None,
+                    chat_message: None,
+                    spectators: Some(crate::model::player::players::Players::from(
+                        &game.get_spectators(),
+                    )),
+                    afk_player: None,
+                    kick_votes: 0,
+                    board_delta: None,
+                    board_sequence: 0,
+                })),
+            )) {
+                eprintln!("Error sending game broadcast: {}", e);
+            }
         }
     }
 
+    pub async fn spectate(
+        &self,
+        player: Arc<Player>,
+        lobby: Arc<Lobby>,
+    ) -> Result<Arc<Game>, ServiceError> {
+        if player.get_lobby().is_some() || player.get_game().is_some() {
+            return Err(ServiceError::AlreadyInLobby);
+        }
+        if player.get_spectating().is_some() {
+            return Err(ServiceError::AlreadySpectating);
+        }
+        let game = match lobby.get_game() {
+            Some(game) => game,
+            None => return Err(ServiceError::GameNotStarted),
+        };
+        if !game.allows_spectators() {
+            return Err(ServiceError::SpectatorsNotAllowed);
+        }
+        game.add_spectator(player.clone());
+        player.set_spectating(Some(game.clone()));
+        #[cfg(not(test))]
+        GameService::send_spectators_update(game.clone()).await;
+        Ok(game)
+    }
+
+    pub async fn stop_spectating(&self, player: Arc<Player>) -> Result<Arc<Game>, ServiceError> {
+        let game = match player.get_spectating() {
+            Some(game) => game,
+            None => return Err(ServiceError::NotSpectating),
+        };
+        game.remove_spectator(&player);
+        player.set_spectating(None);
+        #[cfg(not(test))]
+        GameService::send_spectators_update(game.clone()).await;
+        Ok(game)
+    }
+
     fn start_countdown(game_service: Arc<GameService>, game: Arc<Game>) {
         let game_bak = game.clone();
+        // turns of AFK players are passed almost immediately
+        let duration = match game.get_player_in_this_turn().is_afk() {
+            true => AFK_TURN_DURATION,
+            false => TURN_DURATION,
+        };
         let task = Arc::new(task::spawn(async move {
-            sleep(Duration::from_secs(30)).await;
+            sleep(duration).await;
+            // wait for the request being handled, it may finish the turn and abort this task
+            let _guard = game.lock_requests().await;
+            game.clear_timeout_task();
             let _origin_player = game.get_player_in_this_turn();
-            match GameService::timeout_finish_turn(game_service, game.clone()) {
-                Ok(_words) => {
+            match GameService::timeout_finish_turn(game_service.clone(), game.clone()).await {
+                Ok((_words, ended)) => {
                     #[cfg(not(test))]
                     GameService::send_finish_turn_broadcast(
                         game.clone(),
                         &_words,
                         _origin_player,
                         true,
-                    );
+                    )
+                    .await;
+                    if ended {
+                        if let Err(e) = game_service.end_game(game.clone()).await {
+                            eprintln!("encounter error when end game: {}", e);
+                        }
+                    }
                 }
                 Err(e) => eprintln!("encounter error when finish turn: {}", e),
             }
@@ -241,10 +415,10 @@ impl GameService {
         game_bak.set_timeout_task(task);
     }
 
-    pub fn timeout_finish_turn(
+    pub async fn timeout_finish_turn(
         game_service: Arc<GameService>,
         game: Arc<Game>,
-    ) -> Result<Vec<String>, Box<dyn Error + Send + Sync>> {
+    ) -> Result<(Vec<String>, bool), ServiceError> {
         let words = {
             game.clone()
                 .get_board()
@@ -255,59 +429,192 @@ impl GameService {
         let words = match words {
             Some(words) => words,
             None => {
-                game.restore_board();
+                GameService::revert_turn(&game).await;
                 Vec::new()
             }
         };
-        let _origin_player = game.get_player_in_this_turn();
-        game.get_player_in_this_turn().set_has_shuffled(false);
-        if !GameService::finish_turn(game_service.clone(), game.clone())? {
-            GameService::start_countdown(game_service, game.clone());
+        let origin_player = game.get_player_in_this_turn();
+        origin_player.record_words(&words);
+        origin_player.set_has_shuffled(false);
+        // a turn that still formed words is not counted as missed
+        let missed_turns = match words.is_empty() {
+            true => origin_player.record_missed_turn(),
+            false => {
+                origin_player.record_active_turn();
+                0
+            }
+        };
+        let (afk_turns, afk_removal_turns) = GameService::get_afk_turns(&game);
+        if afk_turns != 0 && missed_turns >= afk_turns && !origin_player.is_afk() {
+            origin_player.set_afk(true);
+            #[cfg(not(test))]
+            GameService::send_afk_broadcast(game.clone(), GameEvent::Afk, origin_player.clone(), 0)
+                .await;
         }
-        Ok(words)
+        if GameService::finish_turn(game_service.clone(), game.clone()).await? {
+            return Ok((words, true));
+        }
+        if afk_removal_turns != 0 && missed_turns >= afk_removal_turns {
+            game_service
+                .remove_player_from_game(origin_player.player.clone())
+                .await?;
+            if game.get_players().is_empty() {
+                return Ok((words, false));
+            }
+        }
+        GameService::start_countdown(game_service, game.clone());
+        Ok((words, false))
+    }
+
+    fn get_afk_turns(game: &Game) -> (u32, u32) {
+        match game.get_settings() {
+            Some(settings) => (settings.afk_turns, settings.afk_removal_turns),
+            None => (DEFAULT_AFK_TURNS, DEFAULT_AFK_REMOVAL_TURNS),
+        }
+    }
+
+    pub async fn vote_kick(
+        game_service: Arc<GameService>,
+        player: Arc<Player>,
+        target_id: u32,
+    ) -> Result<(u32, bool), ServiceError> {
+        let game = match player.get_game() {
+            Some(game) => game,
+            None => return Err(ServiceError::NotInGame),
+        };
+        if target_id == player.id {
+            return Err(ServiceError::CannotVoteKickSelf);
+        }
+        let target = match game.get_player(target_id) {
+            Some(target) => target,
+            None => return Err(ServiceError::TargetNotInGame),
+        };
+        if !target.is_afk() {
+            return Err(ServiceError::TargetNotAfk);
+        }
+        let votes = target.add_kick_vote(player.id);
+        let voters = game
+            .get_players()
+            .iter()
+            .filter(|game_player| **game_player != target && !game_player.is_afk())
+            .count() as u32;
+        if votes < voters / 2 + 1 {
+            #[cfg(not(test))]
+            GameService::send_afk_broadcast(game.clone(), GameEvent::KickVote, target, votes).await;
+            return Ok((votes, false));
+        }
+        let is_current_turn = game.get_player_in_this_turn() == target;
+        game_service
+            .remove_player_from_game(target.player.clone())
+            .await?;
+        if is_current_turn && !game.get_players().is_empty() {
+            game.cancel_timeout_task();
+            GameService::revert_turn(&game).await;
+            // like a timed out turn, the turn passes with the full board for clients without deltas
+            #[cfg(not(test))]
+            GameService::send_finish_turn_broadcast(game.clone(), &Vec::new(), target, false).await;
+            GameService::start_countdown(game_service, game);
+        }
+        Ok((votes, true))
     }
 
-    pub fn remove_player_from_game(
+    #[cfg(not(test))]
+    async fn send_afk_broadcast(
+        game: Arc<Game>,
+        event: GameEvent,
+        game_player: Arc<GamePlayer>,
+        kick_votes: u32,
+    ) {
+        let broadcast = GameBroadcast {
+            event: event as i32,
+            board: None,
+            players: Some(crate::model::player::players::Players::from(
+                &game.get_players(),
+            )),
+            current_player: None,
+            next_player: None,
+            words: None,
+            cards: None,
+            chat_message: None,
+            spectators: None,
+            afk_player: Some(crate::model::player::player::Player::from(game_player)),
+            kick_votes,
+            board_delta: None,
+            board_sequence: 0,
+        };
+        for game_player in game.get_players() {
+            let broadcast = broadcast.clone();
+            if let Err(e) = game_player.player.send_message(Response::new(
+                State::GameBroadcast as u32,
+                Arc::new(ResponseData::GameBroadcast(broadcast)),
+            )) {
+                eprintln!("Error sending game broadcast: {}", e);
+            }
+        }
+        GameService::send_spectator_broadcast(&game, broadcast);
+    }
+
+    pub async fn remove_player_from_game(
         &self,
         player: Arc<Player>,
-    ) -> Result<Arc<GamePlayer>, Box<dyn Error + Send + Sync>> {
+    ) -> Result<Arc<GamePlayer>, ServiceError> {
         let game = match player.clone().get_game() {
             Some(game) => game,
-            None => return Err("Player is not in a game".into()),
+            None => return Err(ServiceError::NotInGame),
         };
         let game_player = match game.remove_player(player.clone()) {
             Some(game) => game,
-            None => return Err("Player is not in the game".into()),
+            None => return Err(ServiceError::NotInGame),
         };
         let is_game_destroy = game.get_players().len() == 0;
         #[cfg(not(test))]
         {
             for game_player in game.get_players() {
-                let game = game.clone();
-                tokio::spawn(async move {
-                    if let Err(e) = game_player
-                        .player
-                        .send_message(Response::new(
-                            State::GameBroadcast as u32,
-                            Arc::new(ResponseData::GameBroadcast(GameBroadcast {
-                                event: GameEvent::Leave as i32,
-                                board: None,
-                                players: Some(crate::model::player::players::Players::from(
-                                    &game.get_players(),
-                                )),
-                                current_player: None,
-                                next_player: None,
-                                words: None,
-                                cards: None,
-                            })),
-                        ))
-                        .await
-                    {
-                        eprintln!("Error sending game broadcast: {}", e);
-                    }
-                });
+                if let Err(e) = game_player.player.send_message(Response::new(
+                    State::GameBroadcast as u32,
+                    Arc::new(ResponseData::GameBroadcast(GameBroadcast {
+                        event: GameEvent::Leave as i32,
+                        board: None,
+                        players: Some(crate::model::player::players::Players::from(
+                            &game.get_players(),
+                        )),
+                        current_player: None,
+                        next_player: None,
+                        words: None,
+                        cards: None,
+                        chat_message: None,
+                        spectators: None,
+                        afk_player: None,
+                        kick_votes: 0,
+                        board_delta: None,
+                        board_sequence: 0,
+                    })),
+                )) {
+                    eprintln!("Error sending game broadcast: {}", e);
+                }
             }
         }
+        #[cfg(not(test))]
+        GameService::send_spectator_broadcast(
+            &game,
+            GameBroadcast {
+                event: GameEvent::Leave as i32,
+                board: None,
+                players: Some(crate::model::player::players::Players::from(
+                    &game.get_players(),
+                )),
+                current_player: None,
+                next_player: None,
+                words: None,
+                cards: None,
+                chat_message: None,
+                spectators: None,
+                afk_player: None,
+                kick_votes: 0,
+                board_delta: None,
+                board_sequence: 0,
+            },
+        );
         player.set_game(None);
         if is_game_destroy {
             self.remove_game(game)?;
@@ -315,15 +622,58 @@ impl GameService {
         Ok(game_player)
     }
 
-    pub fn remove_game(&self, game: Arc<Game>) -> Result<Arc<Game>, Box<dyn Error + Send + Sync>> {
+    pub fn remove_game(&self, game: Arc<Game>) -> Result<Arc<Game>, ServiceError> {
         match self.games.lock().unwrap().remove(&game.id) {
             Some(game) => {
+                #[cfg(not(test))]
+                GameService::send_spectator_broadcast(
+                    &game,
+                    GameBroadcast {
+                        event: GameEvent::Destroy as i32,
+                        board: None,
+                        players: None,
+                        current_player: None,
+                        next_player: None,
+                        words: None,
+                        cards: None,
+                        chat_message: None,
+                        spectators: None,
+                        afk_player: None,
+                        kick_votes: 0,
+                        board_delta: None,
+                        board_sequence: 0,
+                    },
+                );
+                for spectator in game.get_spectators() {
+                    game.remove_spectator(&spectator);
+                    spectator.set_spectating(None);
+                }
+                let mut lobby = None;
                 for game_player in game.get_players() {
                     game_player.player.set_game(None);
+                    if let Some(player_lobby) = game_player.player.get_lobby() {
+                        if !player_lobby.is_rematch_vote_open() {
+                            player_lobby.open_rematch_vote();
+                        }
+                        lobby = Some(player_lobby);
+                    }
+                }
+                if let Some(lobby) = lobby {
+                    self.notify_lobby_list(&lobby);
                 }
                 Ok(game)
             }
-            None => Err("Game not found".into()),
+            None => Err(ServiceError::GameNotFound),
+        }
+    }
+
+    fn notify_lobby_list(&self, lobby: &Lobby) -> Vec<(Arc<Player>, LobbyListBroadcast)> {
+        match &self.lobby_service {
+            // the lobby may be gone already, it must not come back on the list
+            Some(lobby_service) if lobby_service.get_lobby(lobby.get_id()).is_some() => {
+                lobby_service.notify_lobby_list(lobby, LobbyListEvent::Updated)
+            }
+            _ => Vec::new(),
         }
     }
 
@@ -331,86 +681,149 @@ impl GameService {
         self.games.lock().unwrap().values().cloned().collect()
     }
 
-    pub fn place_tile_on_board(&self, game: Arc<Game>, tile: Tile, x: usize, y: usize) {
-        let t = game.get_board();
-        let mut board = t.lock().unwrap();
-        println!("x: {x}, y: {y}");
-        board.tiles[BOARD_SIZE - y - 1][x] = Some(tile);
-        for i in 0..26 {
-            for j in 0..26 {
-                if board.tiles[i][j].is_some() {
-                    println!(
-                        "row: {i}, col: {j}, {:?}",
-                        board.tiles[i][j].clone().unwrap().char
-                    );
+    pub async fn place_tile_on_board(&self, game: Arc<Game>, tile: Tile, x: usize, y: usize) {
+        {
+            let t = game.get_board();
+            let mut board = t.lock().unwrap();
+            println!("x: {x}, y: {y}");
+            board.set_tile(x, y, Some(tile));
+            for i in 0..26 {
+                for j in 0..26 {
+                    if board.tiles[i][j].is_some() {
+                        println!(
+                            "row: {i}, col: {j}, {:?}",
+                            board.tiles[i][j].clone().unwrap().char
+                        );
+                    }
                 }
             }
         }
         #[cfg(not(test))]
-        {
-            for game_player in game.get_players() {
-                if game_player == game.get_player_in_this_turn() {
-                    continue;
-                }
+        GameService::send_board_update(&game, GameEvent::PlaceTile, &[(x, y)]).await;
+    }
 
-                let board = game.get_board().clone();
-                tokio::spawn(async move {
-                    let t = Some(crate::model::game::board::Board::from(
-                        &*board.lock().unwrap(),
-                    ));
-                    if let Err(e) = game_player
-                        .player
-                        .send_message(Response::new(
-                            State::GameBroadcast as u32,
-                            Arc::new(ResponseData::GameBroadcast(GameBroadcast {
-                                event: GameEvent::PlaceTile as i32,
-                                board: t,
-                                players: None,
-                                current_player: None,
-                                next_player: None,
-                                words: None,
-                                cards: None,
-                            })),
-                        ))
-                        .await
-                    {
-                        eprintln!("Error sending game broadcast: {}", e);
-                    }
-                });
+    #[cfg(not(test))]
+    async fn send_board_update(game: &Arc<Game>, event: GameEvent, cells: &[(usize, usize)]) {
+        for (player, spectating, broadcast) in
+            GameService::get_board_update_broadcasts(game, event, cells)
+        {
+            let response = Response::new(
+                State::GameBroadcast as u32,
+                Arc::new(ResponseData::GameBroadcast(broadcast)),
+            );
+            if spectating {
+                game.send_to_spectator(player, response);
+                continue;
+            }
+            if let Err(e) = player.send_message(response) {
+                eprintln!("Error sending game broadcast: {}", e);
             }
         }
     }
 
+    /**
+     * Build the board update of every player and spectator of the game, flagged with whether the
+     * recipient is spectating. Every recipient gets each sequence number, so clients can tell a
+     * missed update from a gap.
+     */
+    fn get_board_update_broadcasts(
+        game: &Game,
+        event: GameEvent,
+        cells: &[(usize, usize)],
+    ) -> Vec<(Arc<Player>, bool, GameBroadcast)> {
+        let (delta, snapshot) = {
+            let board = game.get_board();
+            let board = board.lock().unwrap();
+            (
+                BoardDelta::new(game.next_board_sequence(), &board, cells),
+                crate::model::game::board::Board::from(&*board),
+            )
+        };
+        let recipients = game
+            .get_players()
+            .into_iter()
+            .map(|game_player| (game_player.player.clone(), false))
+            .chain(
+                game.get_spectators()
+                    .into_iter()
+                    .map(|spectator| (spectator, true)),
+            );
+        let mut broadcasts = Vec::new();
+        for (player, spectating) in recipients {
+            let broadcast = match player.has_feature(Feature::BoardDelta) {
+                true => GameBroadcast {
+                    event: event as i32,
+                    board: None,
+                    players: None,
+                    current_player: None,
+                    next_player: None,
+                    words: None,
+                    cards: None,
+                    chat_message: None,
+                    spectators: None,
+                    afk_player: None,
+                    kick_votes: 0,
+                    board_delta: Some(delta.clone()),
+                    board_sequence: delta.sequence,
+                },
+                false if event == GameEvent::RevertTurn => continue,
+                false => GameBroadcast {
+                    event: GameEvent::PlaceTile as i32,
+                    board: Some(snapshot.clone()),
+                    players: None,
+                    current_player: None,
+                    next_player: None,
+                    words: None,
+                    cards: None,
+                    chat_message: None,
+                    spectators: None,
+                    afk_player: None,
+                    kick_votes: 0,
+                    board_delta: None,
+                    board_sequence: delta.sequence,
+                },
+            };
+            broadcasts.push((player, spectating, broadcast));
+        }
+        broadcasts
+    }
+
+    async fn revert_turn(game: &Arc<Game>) {
+        let _cells = game.restore_board();
+        #[cfg(not(test))]
+        GameService::send_board_update(game, GameEvent::RevertTurn, &_cells).await;
+    }
+
     #[cfg(not(test))]
-    fn boardcast_game_end(game: Arc<Game>) {
+    async fn boardcast_game_end(game: Arc<Game>) {
         for game_player in game.get_players() {
-            tokio::spawn(async move {
-                if let Err(e) = game_player
-                    .player
-                    .send_message(Response::new(
-                        State::GameBroadcast as u32,
-                        Arc::new(ResponseData::GameBroadcast(GameBroadcast {
-                            event: GameEvent::Destroy as i32,
-                            board: None,
-                            players: None,
-                            current_player: None,
-                            next_player: None,
-                            words: None,
-                            cards: None,
-                        })),
-                    ))
-                    .await
-                {
-                    eprintln!("Error sending game broadcast: {}", e);
-                }
-            });
+            if let Err(e) = game_player.player.send_message(Response::new(
+                State::GameBroadcast as u32,
+                Arc::new(ResponseData::GameBroadcast(GameBroadcast {
+                    event: GameEvent::Destroy as i32,
+                    board: None,
+                    players: None,
+                    current_player: None,
+                    next_player: None,
+                    words: None,
+                    cards: None,
+                    chat_message: None,
+                    spectators: None,
+                    afk_player: None,
+                    kick_votes: 0,
+                    board_delta: None,
+                    board_sequence: 0,
+                })),
+            )) {
+                eprintln!("Error sending game broadcast: {}", e);
+            }
         }
     }
 
-    pub fn validate_board_and_finish_turn(
+    pub async fn validate_board_and_finish_turn(
         game_service: Arc<GameService>,
         game: Arc<Game>,
-    ) -> Result<Vec<String>, Box<dyn Error + Send + Sync>> {
+    ) -> Result<Vec<String>, ServiceError> {
         let words = match game
             .get_board()
             .lock()
@@ -418,67 +831,55 @@ impl GameService {
             .validate(&game_service.wordlist, game.clone())
         {
             Some(words) => words,
-            None => return Err("invalid word".into()),
+            None => return Err(ServiceError::InvalidWord),
         };
-        let _origin_player = game.get_player_in_this_turn();
-        if !GameService::finish_turn(game_service.clone(), game.clone())? {
-            GameService::start_countdown(game_service, game.clone());
+        let origin_player = game.get_player_in_this_turn();
+        origin_player.record_words(&words);
+        let _was_afk = origin_player.record_active_turn();
+        #[cfg(not(test))]
+        if _was_afk {
+            GameService::send_afk_broadcast(
+                game.clone(),
+                GameEvent::Active,
+                origin_player.clone(),
+                0,
+            )
+            .await;
+        }
+        let ended = GameService::finish_turn(game_service.clone(), game.clone()).await?;
+        if !ended {
+            GameService::start_countdown(game_service.clone(), game.clone());
         }
         #[cfg(not(test))]
-        GameService::send_finish_turn_broadcast(game.clone(), &words, _origin_player, false);
+        GameService::send_finish_turn_broadcast(game.clone(), &words, origin_player, false).await;
+        if ended {
+            game_service.end_game(game).await?;
+        }
         Ok(words)
     }
 
-    pub fn remove_selected_tile(&self, x: u32, y: u32, game: Arc<Game>) {
+    pub async fn remove_selected_tile(&self, x: u32, y: u32, game: Arc<Game>) {
         {
-            game.get_board().lock().unwrap().tiles[(BOARD_SIZE - y as usize - 1) as usize]
-                [x as usize] = None;
+            game.get_board()
+                .lock()
+                .unwrap()
+                .set_tile(x as usize, y as usize, None);
             println!("x: {x}, y: {y}");
             let row = BOARD_SIZE - y as usize - 1;
             println!("row: {row}, col: {x}");
         }
         #[cfg(not(test))]
-        {
-            let board = game.get_board().clone();
-            for game_player in game.get_players() {
-                if game_player == game.get_player_in_this_turn() {
-                    continue;
-                }
-                let _game = game.clone();
-                let t = Some(crate::model::game::board::Board::from(
-                    &*board.lock().unwrap(),
-                ));
-                tokio::spawn(async move {
-                    if let Err(e) = game_player
-                        .player
-                        .send_message(Response::new(
-                            State::GameBroadcast as u32,
-                            Arc::new(ResponseData::GameBroadcast(GameBroadcast {
-                                event: GameEvent::PlaceTile as i32,
-                                board: t,
-                                players: None,
-                                current_player: None,
-                                next_player: None,
-                                words: None,
-                                cards: None,
-                            })),
-                        ))
-                        .await
-                    {
-                        eprintln!("Error sending game broadcast: {}", e);
-                    }
-                });
-            }
-        }
+        GameService::send_board_update(&game, GameEvent::RemoveTile, &[(x as usize, y as usize)])
+            .await;
     }
 
-    pub fn shuffle(
+    pub async fn shuffle(
         &self,
         #[cfg(not(test))] game: Arc<Game>,
         game_player: Arc<GamePlayer>,
-    ) -> Result<Vec<Card>, Box<dyn Error + Send + Sync>> {
+    ) -> Result<Vec<Card>, ServiceError> {
         if game_player.get_has_shuffled() {
-            return Err("Player has shuffled in this turn".into());
+            return Err(ServiceError::AlreadyShuffled);
         }
         let cards = game_player.get_new_card();
         #[cfg(not(test))]
@@ -488,26 +889,26 @@ impl GameService {
                     continue;
                 }
 
-                tokio::spawn(async move {
-                    if let Err(e) = game_player
-                        .player
-                        .send_message(Response::new(
-                            State::GameBroadcast as u32,
-                            Arc::new(ResponseData::GameBroadcast(GameBroadcast {
-                                event: GameEvent::Shuffle as i32,
-                                board: None,
-                                players: None,
-                                current_player: None,
-                                next_player: None,
-                                words: None,
-                                cards: None,
-                            })),
-                        ))
-                        .await
-                    {
-                        eprintln!("Error sending game broadcast: {}", e);
-                    }
-                });
+                if let Err(e) = game_player.player.send_message(Response::new(
+                    State::GameBroadcast as u32,
+                    Arc::new(ResponseData::GameBroadcast(GameBroadcast {
+                        event: GameEvent::Shuffle as i32,
+                        board: None,
+                        players: None,
+                        current_player: None,
+                        next_player: None,
+                        words: None,
+                        cards: None,
+                        chat_message: None,
+                        spectators: None,
+                        afk_player: None,
+                        kick_votes: 0,
+                        board_delta: None,
+                        board_sequence: 0,
+                    })),
+                )) {
+                    eprintln!("Error sending game broadcast: {}", e);
+                }
             }
         }
         Ok(cards)
@@ -517,6 +918,8 @@ impl GameService {
 #[cfg(test)]
 mod tests {
 
+    use crate::lobby::lobby_filter::LobbyFilter;
+
     use super::*;
 
     #[tokio::test]
@@ -526,7 +929,9 @@ mod tests {
         let leader = Arc::new(Player::new(0, "test".to_string()));
         let lobby = Arc::new(Lobby::new(0, 4, leader.clone()));
         lobby.get_player(leader.id).unwrap().set_ready(true);
-        assert!(GameService::start_game(game_service, leader.clone(), lobby).is_ok());
+        assert!(GameService::start_game(game_service, leader.clone(), lobby)
+            .await
+            .is_ok());
         Ok(())
     }
 
@@ -538,6 +943,7 @@ mod tests {
         let player = Arc::new(Player::new(1, "test2".to_string()));
         assert!(
             GameService::start_game(game_service, player, Arc::new(Lobby::new(0, 4, leader)))
+                .await
                 .is_err()
         );
         Ok(())
@@ -551,7 +957,9 @@ mod tests {
         let lobby = Arc::new(Lobby::new(0, 4, leader.clone()));
         let player = Arc::new(Player::new(1, "test2".to_string()));
         lobby.add_player(player.clone())?;
-        assert!(GameService::start_game(game_service, player, lobby).is_err());
+        assert!(GameService::start_game(game_service, player, lobby)
+            .await
+            .is_err());
         Ok(())
     }
 
@@ -561,7 +969,9 @@ mod tests {
         let game_service = Arc::new(GameService::new(HashSet::new()));
         let leader = Arc::new(Player::new(0, "test".to_string()));
         let lobby = Arc::new(Lobby::new(0, 4, leader.clone()));
-        assert!(GameService::start_game(game_service, leader, lobby).is_err());
+        assert!(GameService::start_game(game_service, leader, lobby)
+            .await
+            .is_err());
         Ok(())
     }
 
@@ -575,11 +985,15 @@ mod tests {
         lobby.add_player(player1)?;
         lobby.get_player(0).unwrap().set_ready(true);
         lobby.get_player(1).unwrap().set_ready(true);
-        let game = GameService::start_game(game_service.clone(), player.clone(), lobby)?;
+        let game = GameService::start_game(game_service.clone(), player.clone(), lobby).await?;
         {
             game.get_board().lock().unwrap().tiles[0][0] = Some(Tile::new('a', player.clone(), 1));
         }
-        assert!(GameService::validate_board_and_finish_turn(game_service, game).is_err());
+        assert!(
+            GameService::validate_board_and_finish_turn(game_service, game)
+                .await
+                .is_err()
+        );
         Ok(())
     }
 
@@ -593,9 +1007,9 @@ mod tests {
         lobby.add_player(player1)?;
         lobby.get_player(0).unwrap().set_ready(true);
         lobby.get_player(1).unwrap().set_ready(true);
-        let game = GameService::start_game(game_service.clone(), player.clone(), lobby)?;
+        let game = GameService::start_game(game_service.clone(), player.clone(), lobby).await?;
         game.set_turn(END_GAME_TURN + 1);
-        GameService::validate_board_and_finish_turn(game_service.clone(), game)?;
+        GameService::validate_board_and_finish_turn(game_service.clone(), game).await?;
         assert_eq!(game_service.get_gamees().len(), 0);
         Ok(())
     }
@@ -610,8 +1024,8 @@ mod tests {
         lobby.add_player(player1)?;
         lobby.get_player(0).unwrap().set_ready(true);
         lobby.get_player(1).unwrap().set_ready(true);
-        let game = GameService::start_game(game_service.clone(), player.clone(), lobby)?;
-        GameService::validate_board_and_finish_turn(game_service.clone(), game)?;
+        let game = GameService::start_game(game_service.clone(), player.clone(), lobby).await?;
+        GameService::validate_board_and_finish_turn(game_service.clone(), game).await?;
         assert_eq!(game_service.get_gamees().len(), 1);
         Ok(())
     }
@@ -626,8 +1040,12 @@ mod tests {
         lobby.add_player(player1)?;
         lobby.get_player(0).unwrap().set_ready(true);
         lobby.get_player(1).unwrap().set_ready(true);
-        let game = GameService::start_game(game_service.clone(), player.clone(), lobby)?;
-        assert!(GameService::validate_board_and_finish_turn(game_service, game).is_ok());
+        let game = GameService::start_game(game_service.clone(), player.clone(), lobby).await?;
+        assert!(
+            GameService::validate_board_and_finish_turn(game_service, game)
+                .await
+                .is_ok()
+        );
         Ok(())
     }
 
@@ -641,13 +1059,56 @@ mod tests {
         lobby.add_player(player1)?;
         lobby.get_player(0).unwrap().set_ready(true);
         lobby.get_player(1).unwrap().set_ready(true);
-        let game = GameService::start_game(game_service.clone(), player, lobby)?;
+        let game = GameService::start_game(game_service.clone(), player, lobby).await?;
         let turn_before = game.clone().get_turns();
-        GameService::finish_turn(game_service, game.clone())?;
+        GameService::finish_turn(game_service, game.clone()).await?;
         assert!(turn_before + 1 == game.get_turns());
         Ok(())
     }
 
+    #[tokio::test]
+    async fn timeout_finish_turn_when_player_misses_afk_turns_should_mark_player_afk(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let game_service = Arc::new(GameService::new(HashSet::new()));
+        let player = Arc::new(Player::new(0, String::from("test1")));
+        let player1 = Arc::new(Player::new(1, String::from("test2")));
+        let lobby = Arc::new(Lobby::new(0, 4, player.clone()));
+        lobby.add_player(player1)?;
+        lobby.get_player(0).unwrap().set_ready(true);
+        lobby.get_player(1).unwrap().set_ready(true);
+        let game = GameService::start_game(game_service.clone(), player, lobby).await?;
+        let first_player = game.get_player_in_this_turn();
+        for _ in 0..DEFAULT_AFK_TURNS * 2 - 1 {
+            GameService::timeout_finish_turn(game_service.clone(), game.clone()).await?;
+        }
+        assert!(first_player.is_afk());
+        assert!(!game.get_player_in_this_turn().is_afk());
+        assert!(first_player.record_active_turn());
+        assert!(!first_player.is_afk());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn timeout_finish_turn_when_player_misses_removal_turns_should_remove_player(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let game_service = Arc::new(GameService::new(HashSet::new()));
+        let player = Arc::new(Player::new(0, String::from("test1")));
+        let player1 = Arc::new(Player::new(1, String::from("test2")));
+        let lobby = Arc::new(Lobby::new(0, 4, player.clone()));
+        lobby.add_player(player1)?;
+        lobby.get_player(0).unwrap().set_ready(true);
+        lobby.get_player(1).unwrap().set_ready(true);
+        let game = GameService::start_game(game_service.clone(), player, lobby).await?;
+        let first_player = game.get_player_in_this_turn();
+        for _ in 0..DEFAULT_AFK_REMOVAL_TURNS * 2 - 1 {
+            GameService::timeout_finish_turn(game_service.clone(), game.clone()).await?;
+        }
+        assert!(game.get_player(first_player.player.id).is_none());
+        assert!(first_player.player.get_game().is_none());
+        assert_ne!(game.get_player_in_this_turn(), first_player);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn get_game_with_game_id_should_return_game() -> Result<(), Box<dyn Error + Send + Sync>>
     {
@@ -684,6 +1145,48 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn remove_game_with_test_player_in_lobby_should_open_rematch_vote(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let game_service = GameService::new(HashSet::new());
+        let player = Arc::new(Player::new(0, "test".to_string()));
+        let lobby = Arc::new(Lobby::new(0, 4, player.clone()));
+        player.set_lobby(Some(lobby.clone()));
+        let game = Arc::new(Game::new(0, vec![player.clone()]));
+        game_service.games.lock().unwrap().insert(0, game.clone());
+        game_service.remove_game(game)?;
+        assert!(lobby.is_rematch_vote_open());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn notify_lobby_list_with_started_and_removed_game_should_update_lobby(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let game_service = GameService {
+            lobby_service: Some(lobby_service.clone()),
+            ..GameService::new(HashSet::new())
+        };
+        lobby_service.subscribe_lobby_list(
+            Arc::new(Player::new(1, "test2".to_string())),
+            LobbyFilter::default(),
+        );
+        let player = Arc::new(Player::new(0, "test".to_string()));
+        let lobby = lobby_service.create_lobby(player.clone(), 4).await?;
+        let game = Arc::new(Game::new(0, vec![player.clone()]));
+        player.set_game(Some(game.clone()));
+        let broadcasts = game_service.notify_lobby_list(&lobby);
+        assert!(broadcasts[0].1.lobby_info.as_ref().unwrap().started);
+        game_service.games.lock().unwrap().insert(0, game.clone());
+        game_service.remove_game(game)?;
+        let broadcasts = game_service.notify_lobby_list(&lobby);
+        assert_eq!(broadcasts[0].1.event, LobbyListEvent::Updated as i32);
+        assert!(!broadcasts[0].1.lobby_info.as_ref().unwrap().started);
+        lobby_service.remove_lobby(lobby.clone()).await?;
+        assert!(game_service.notify_lobby_list(&lobby).is_empty());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn get_games_with_test_games_should_return_test_games(
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
@@ -713,7 +1216,7 @@ mod tests {
         ));
         player.set_game(Some(game.clone()));
         game_service.games.lock().unwrap().insert(0, game.clone());
-        game_service.remove_player_from_game(player.clone())?;
+        game_service.remove_player_from_game(player.clone()).await?;
         assert!(game.get_player(0).is_none());
         Ok(())
     }
@@ -726,7 +1229,7 @@ mod tests {
         let game = Arc::new(Game::new(0, vec![player.clone()]));
         player.set_game(Some(game.clone()));
         game_service.games.lock().unwrap().insert(0, game.clone());
-        game_service.remove_player_from_game(player.clone())?;
+        game_service.remove_player_from_game(player.clone()).await?;
         assert!(game_service.games.lock().unwrap().len() == 0);
         Ok(())
     }
@@ -739,7 +1242,7 @@ mod tests {
         let game = Arc::new(Game::new(0, vec![player.clone()]));
         player.set_game(Some(game.clone()));
         game_service.games.lock().unwrap().insert(0, game.clone());
-        let game_player = game_service.remove_player_from_game(player.clone())?;
+        let game_player = game_service.remove_player_from_game(player.clone()).await?;
         assert_eq!(game_player.player, player);
         Ok(())
     }
@@ -753,8 +1256,84 @@ mod tests {
         player.set_game(Some(game.clone()));
         game_service.games.lock().unwrap().insert(0, game.clone());
         game.get_board().lock().unwrap().tiles[0][0] = Some(Tile::new('a', player, 1));
-        GameService::finish_turn(game_service, game.clone())?;
+        GameService::finish_turn(game_service, game.clone()).await?;
         assert!(game.get_board_backup().tiles[0][0].is_some());
         Ok(())
     }
+
+    #[tokio::test]
+    async fn vote_kick_with_target_in_turn_should_revert_board_and_pass_turn(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let game_service = Arc::new(GameService::new(HashSet::new()));
+        let player = Arc::new(Player::new(0, String::from("test1")));
+        let player1 = Arc::new(Player::new(1, String::from("test2")));
+        let lobby = Arc::new(Lobby::new(0, 4, player.clone()));
+        lobby.add_player(player1)?;
+        lobby.get_player(0).unwrap().set_ready(true);
+        lobby.get_player(1).unwrap().set_ready(true);
+        let game = GameService::start_game(game_service.clone(), player, lobby).await?;
+        let target = game.get_player_in_this_turn();
+        let voter = game
+            .get_players()
+            .into_iter()
+            .find(|game_player| *game_player != target)
+            .unwrap();
+        target.set_afk(true);
+        game.get_board().lock().unwrap().tiles[0][0] =
+            Some(Tile::new('a', target.player.clone(), 1));
+        let (_, kicked) =
+            GameService::vote_kick(game_service, voter.player.clone(), target.player.id).await?;
+        assert!(kicked);
+        assert!(game.get_board().lock().unwrap().tiles[0][0].is_none());
+        assert_eq!(game.get_player_in_this_turn(), voter);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn finish_turn_on_last_turn_should_leave_ending_game_to_caller(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let game_service = Arc::new(GameService::new(HashSet::new()));
+        let player = Arc::new(Player::new(0, String::from("test1")));
+        let game = Arc::new(Game::new(0, vec![player.clone()]));
+        player.set_game(Some(game.clone()));
+        game_service.games.lock().unwrap().insert(0, game.clone());
+        while !GameService::finish_turn(game_service.clone(), game.clone()).await? {}
+        assert!(game_service.get_game(0).is_some());
+        assert!(player.get_game().is_some());
+        game_service.end_game(game).await?;
+        assert!(game_service.get_game(0).is_none());
+        assert!(player.get_game().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn get_board_update_broadcasts_with_player_in_turn_should_send_consecutive_sequences() {
+        let player1 = Arc::new(Player::new(0, String::from("test1")));
+        let player2 = Arc::new(Player::new(1, String::from("test2")));
+        let spectator = Arc::new(Player::new(2, String::from("test3")));
+        for player in [&player1, &player2, &spectator] {
+            player.set_features(Feature::BoardDelta as u32);
+        }
+        let game = Game::new(0, vec![player1.clone(), player2.clone()]);
+        game.add_spectator(spectator.clone());
+        let mut sequences: HashMap<u32, Vec<u32>> = HashMap::new();
+        for event in [
+            GameEvent::PlaceTile,
+            GameEvent::RemoveTile,
+            GameEvent::RevertTurn,
+        ] {
+            for (player, _, broadcast) in
+                GameService::get_board_update_broadcasts(&game, event, &[(0, 0)])
+            {
+                sequences
+                    .entry(player.id)
+                    .or_default()
+                    .push(broadcast.board_sequence);
+            }
+        }
+        assert_eq!(sequences.len(), 3);
+        for sequence in sequences.values() {
+            assert_eq!(sequence, &vec![1, 2, 3]);
+        }
+    }
 }
@@ -1,14 +1,26 @@
 use std::{
     collections::HashMap,
-    error::Error,
     sync::{Arc, Mutex},
+    time::Instant,
 };
 
+use tokio::time::sleep;
+
 use crate::{
-    lobby::{lobby::Lobby, lobby_player::LobbyPlayer},
+    error::ServiceError,
+    game::game::Game,
+    lobby::{
+        lobby::Lobby,
+        lobby_filter::LobbyFilter,
+        lobby_player::LobbyPlayer,
+        lobby_settings::{LobbySettings, AUTO_START_COUNTDOWN},
+    },
+    model::lobby::list::{LobbyInfo, LobbyListBroadcast, LobbyListEvent},
     player::Player,
 };
 
+use super::game_service::GameService;
+
 #[cfg(not(test))]
 use crate::frame::{Response, ResponseData};
 #[cfg(not(test))]
@@ -16,10 +28,14 @@ use crate::model::lobby::broadcast::{LobbyBroadcast, LobbyEvent};
 #[cfg(not(test))]
 use crate::model::state::State;
 
+pub const DEFAULT_LOBBY_PAGE_SIZE: u32 = 20;
+pub const MAX_LOBBY_PAGE_SIZE: u32 = 50;
+
 #[derive(Debug)]
 pub struct LobbyService {
     next_lobby_id: Mutex<u32>,
     lobbies: Mutex<HashMap<u32, Arc<Lobby>>>,
+    lobby_list_subscribers: Mutex<HashMap<u32, (Arc<Player>, LobbyFilter)>>,
 }
 
 impl LobbyService {
@@ -27,37 +43,65 @@ impl LobbyService {
         Self {
             next_lobby_id: Mutex::new(0),
             lobbies: Mutex::new(HashMap::new()),
+            lobby_list_subscribers: Mutex::new(HashMap::new()),
         }
     }
 
-    pub fn create_lobby(
+    pub async fn create_lobby(
         &self,
         leader: Arc<Player>,
         max_players: u32,
-    ) -> Result<Arc<Lobby>, Box<dyn Error + Send + Sync + Send + Sync>> {
+    ) -> Result<Arc<Lobby>, ServiceError> {
+        let settings = LobbySettings::new(Lobby::default_name(&leader), false, None);
+        self.create_lobby_with_settings(leader, max_players, settings)
+            .await
+    }
+
+    pub async fn create_lobby_with_settings(
+        &self,
+        leader: Arc<Player>,
+        max_players: u32,
+        settings: LobbySettings,
+    ) -> Result<Arc<Lobby>, ServiceError> {
         if max_players < 4 || max_players > 8 {
-            return Err("Invalid max players".into());
+            return Err(ServiceError::InvalidMaxPlayers);
         }
-        let mut next_lobby_id = self.next_lobby_id.lock().unwrap();
-        let lobby = Arc::new(Lobby::new(*next_lobby_id, max_players, leader.clone()));
-        self.lobbies
-            .lock()
-            .unwrap()
-            .insert(*next_lobby_id, lobby.clone());
-        *next_lobby_id += 1;
+        settings.validate()?;
+        let lobby = {
+            let mut next_lobby_id = self.next_lobby_id.lock().unwrap();
+            let lobby = Arc::new(Lobby::new(*next_lobby_id, max_players, leader.clone()));
+            lobby.set_settings(settings);
+            self.lobbies
+                .lock()
+                .unwrap()
+                .insert(*next_lobby_id, lobby.clone());
+            *next_lobby_id += 1;
+            lobby
+        };
         leader.set_lobby(Some(lobby.clone()));
+        self.notify_lobby_list(&lobby, LobbyListEvent::Created);
         Ok(lobby)
     }
 
-    pub fn add_player_to_lobby(
+    pub async fn add_player_to_lobby(
         &self,
         player: Arc<Player>,
         lobby: Arc<Lobby>,
-    ) -> Result<Arc<LobbyPlayer>, Box<dyn Error + Send + Sync>> {
+    ) -> Result<Arc<LobbyPlayer>, ServiceError> {
         if player.get_lobby().is_some() {
-            return Err("player already in a lobby".into());
+            return Err(ServiceError::AlreadyInLobby);
+        }
+        if lobby.is_banned(&player) {
+            return Err(ServiceError::BannedFromLobby);
+        }
+        if lobby.is_started() {
+            return Err(ServiceError::GameAlreadyStarted);
+        }
+        if lobby.is_rematch_vote_open() {
+            return Err(ServiceError::RematchInProgress);
         }
         let lobby_player = lobby.add_player(player.clone())?;
+        self.cancel_auto_start(&lobby).await;
         #[cfg(not(test))]
         {
             for lobby_player in lobby.get_players() {
@@ -65,27 +109,23 @@ impl LobbyService {
                     continue;
                 }
                 let lobby = lobby.clone();
-                tokio::spawn(async move {
-                    if let Err(e) = lobby_player
-                        .player
-                        .send_message(Response::new(
-                            State::LobbyBroadcast as u32,
-                            Arc::new(ResponseData::LobbyBroadcast(LobbyBroadcast {
-                                event: LobbyEvent::Join as i32,
-                                lobby: Some(crate::model::lobby::lobby::Lobby::from(lobby.clone())),
-                                cards: None,
-                                current_player: None,
-                                next_player: None,
-                            })),
-                        ))
-                        .await
-                    {
-                        eprintln!("Error sending lobby broadcast: {}", e);
-                    }
-                });
+                if let Err(e) = lobby_player.player.send_message(Response::new(
+                    State::LobbyBroadcast as u32,
+                    Arc::new(ResponseData::LobbyBroadcast(LobbyBroadcast {
+                        event: LobbyEvent::Join as i32,
+                        lobby: Some(crate::model::lobby::lobby::Lobby::from(lobby.clone())),
+                        cards: None,
+                        current_player: None,
+                        next_player: None,
+                        chat_message: None,
+                    })),
+                )) {
+                    eprintln!("Error sending lobby broadcast: {}", e);
+                }
             }
         }
-        player.set_lobby(Some(lobby));
+        player.set_lobby(Some(lobby.clone()));
+        self.notify_lobby_list(&lobby, LobbyListEvent::Updated);
         Ok(lobby_player)
     }
 
@@ -97,71 +137,406 @@ impl LobbyService {
         Some(self.lobbies.lock().unwrap().get(&id)?.clone())
     }
 
-    pub fn remove_player_from_lobby(
+    pub fn get_public_lobbies(&self) -> Vec<Arc<Lobby>> {
+        self.lobbies
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|lobby| !lobby.is_private())
+            .cloned()
+            .collect()
+    }
+
+    pub fn get_public_lobbies_page(
+        &self,
+        filter: &LobbyFilter,
+        page: u32,
+        page_size: u32,
+    ) -> (Vec<Arc<Lobby>>, u32) {
+        let mut lobbies = self
+            .get_public_lobbies()
+            .into_iter()
+            .filter(|lobby| filter.matches(lobby))
+            .collect::<Vec<_>>();
+        lobbies.sort_by_key(|lobby| lobby.get_id());
+        let total = lobbies.len() as u32;
+        let lobbies = lobbies
+            .into_iter()
+            .skip(page as usize * page_size as usize)
+            .take(page_size as usize)
+            .collect();
+        (lobbies, total)
+    }
+
+    pub fn subscribe_lobby_list(&self, player: Arc<Player>, filter: LobbyFilter) {
+        self.lobby_list_subscribers
+            .lock()
+            .unwrap()
+            .insert(player.id, (player, filter));
+    }
+
+    pub fn unsubscribe_lobby_list(&self, player: &Player) {
+        self.lobby_list_subscribers
+            .lock()
+            .unwrap()
+            .remove(&player.id);
+    }
+
+    pub fn is_subscribed_lobby_list(&self, player: &Player) -> bool {
+        self.lobby_list_subscribers
+            .lock()
+            .unwrap()
+            .contains_key(&player.id)
+    }
+
+    /**
+     * Tell the lobby list subscribers about a change of the lobby. A lobby that is private or no
+     * longer matches a subscriber's filter is reported to them as removed, so clients should treat
+     * `UPDATED` as an upsert. Returns the broadcasts sent.
+     */
+    pub fn notify_lobby_list(
+        &self,
+        lobby: &Lobby,
+        event: LobbyListEvent,
+    ) -> Vec<(Arc<Player>, LobbyListBroadcast)> {
+        let lobby_info = LobbyInfo::from(lobby);
+        let broadcasts = self
+            .lobby_list_subscribers
+            .lock()
+            .unwrap()
+            .values()
+            .filter_map(|(subscriber, filter)| {
+                let visible = !lobby.is_private() && filter.matches(lobby);
+                let event = match (visible, event) {
+                    (false, LobbyListEvent::Created) => return None,
+                    (false, _) => LobbyListEvent::Removed,
+                    (true, event) => event,
+                };
+                Some((
+                    subscriber.clone(),
+                    LobbyListBroadcast {
+                        event: event as i32,
+                        lobby_info: Some(lobby_info.clone()),
+                    },
+                ))
+            })
+            .collect::<Vec<_>>();
+        #[cfg(not(test))]
+        for (subscriber, broadcast) in &broadcasts {
+            if let Err(e) = subscriber.send_message(Response::new(
+                State::LobbyListBroadcast as u32,
+                Arc::new(ResponseData::LobbyListBroadcast(broadcast.clone())),
+            )) {
+                eprintln!("Error sending lobby list broadcast: {}", e);
+            }
+        }
+        broadcasts
+    }
+
+    pub fn get_lobby_by_invite_code(&self, invite_code: &str) -> Option<Arc<Lobby>> {
+        self.lobbies
+            .lock()
+            .unwrap()
+            .values()
+            .find(|lobby| lobby.get_invite_code().eq_ignore_ascii_case(invite_code))
+            .cloned()
+    }
+
+    pub async fn update_lobby_settings(
+        &self,
+        player: Arc<Player>,
+        settings: LobbySettings,
+    ) -> Result<Arc<Lobby>, ServiceError> {
+        let lobby = match player.get_lobby() {
+            Some(lobby) => lobby,
+            None => return Err(ServiceError::NotInLobby),
+        };
+        if player != lobby.leader {
+            return Err(ServiceError::NotLeader);
+        }
+        if player.get_game().is_some() {
+            return Err(ServiceError::GameAlreadyStarted);
+        }
+        settings.validate()?;
+        lobby.set_settings(settings);
+        if !lobby.can_auto_start() {
+            self.cancel_auto_start(&lobby).await;
+        }
+        self.notify_lobby_list(&lobby, LobbyListEvent::Updated);
+        #[cfg(not(test))]
+        LobbyService::send_lobby_broadcast(lobby.clone(), LobbyEvent::Update, Some(player)).await;
+        Ok(lobby)
+    }
+
+    pub async fn toggle_ready(&self, player: Arc<Player>) -> Result<Arc<Lobby>, ServiceError> {
+        let lobby = match player.get_lobby() {
+            Some(lobby) => lobby,
+            None => return Err(ServiceError::NotInLobby),
+        };
+        if player.get_game().is_some() {
+            return Err(ServiceError::GameAlreadyStarted);
+        }
+        if lobby.is_rematch_vote_open() {
+            return Err(ServiceError::RematchInProgress);
+        }
+        let lobby_player = match lobby.get_player(player.id) {
+            Some(lobby_player) => lobby_player,
+            None => return Err(ServiceError::NotInLobby),
+        };
+        lobby_player.set_ready(!lobby_player.get_ready());
+        if !lobby_player.get_ready() {
+            self.cancel_auto_start(&lobby).await;
+        }
+        #[cfg(not(test))]
+        LobbyService::send_lobby_broadcast(lobby.clone(), LobbyEvent::Ready, Some(player)).await;
+        Ok(lobby)
+    }
+
+    #[cfg(not(test))]
+    pub async fn send_lobby_broadcast(
+        lobby: Arc<Lobby>,
+        event: LobbyEvent,
+        origin_player: Option<Arc<Player>>,
+    ) {
+        for lobby_player in lobby.get_players() {
+            if Some(&lobby_player.player) == origin_player.as_ref() {
+                continue;
+            }
+            let lobby = lobby.clone();
+            if let Err(e) = lobby_player.player.send_message(Response::new(
+                State::LobbyBroadcast as u32,
+                Arc::new(ResponseData::LobbyBroadcast(LobbyBroadcast {
+                    event: event as i32,
+                    lobby: Some(crate::model::lobby::lobby::Lobby::from(lobby)),
+                    cards: None,
+                    current_player: None,
+                    next_player: None,
+                    chat_message: None,
+                })),
+            )) {
+                eprintln!("Error sending lobby broadcast: {}", e);
+            }
+        }
+    }
+
+    pub async fn schedule_auto_start(
+        lobby_service: Arc<LobbyService>,
+        game_service: Arc<GameService>,
+        lobby: Arc<Lobby>,
+    ) -> bool {
+        if !lobby.can_auto_start()
+            || lobby.has_auto_start_task()
+            || lobby_service.get_lobby(lobby.get_id()).as_ref() != Some(&lobby)
+        {
+            return false;
+        }
+        let deadline = Instant::now() + AUTO_START_COUNTDOWN;
+        let lobby_bak = lobby.clone();
+        let task = Arc::new(tokio::spawn(async move {
+            sleep(AUTO_START_COUNTDOWN).await;
+            LobbyService::finish_auto_start(lobby_service, game_service, lobby).await;
+        }));
+        lobby_bak.set_auto_start_task(task, deadline);
+        #[cfg(not(test))]
+        LobbyService::send_lobby_broadcast(lobby_bak, LobbyEvent::AutoStart, None).await;
+        true
+    }
+
+    /**
+     * Start the game when the countdown ran out. The lobby is checked again, since the leader
+     * may have started the game or a player left while the countdown waited for the start lock.
+     * Return the started game.
+     */
+    async fn finish_auto_start(
+        lobby_service: Arc<LobbyService>,
+        game_service: Arc<GameService>,
+        lobby: Arc<Lobby>,
+    ) -> Option<Arc<Game>> {
+        let _guard = lobby.lock_start().await;
+        lobby.clear_auto_start_task();
+        if !lobby.can_auto_start()
+            || lobby.get_game().is_some()
+            || lobby_service.get_lobby(lobby.get_id()).as_ref() != Some(&lobby)
+        {
+            #[cfg(not(test))]
+            LobbyService::send_lobby_broadcast(lobby.clone(), LobbyEvent::AutoStartCancel, None)
+                .await;
+            return None;
+        }
+        match GameService::start_game(game_service, lobby.leader.clone(), lobby.clone()).await {
+            Ok(game) => {
+                #[cfg(not(test))]
+                if let Some(game_player) = game.get_player(lobby.leader.id) {
+                    GameService::send_start_broadcast(game.clone(), lobby.clone(), game_player)
+                        .await;
+                }
+                Some(game)
+            }
+            Err(e) => {
+                eprintln!("encounter error when auto starting game: {}", e);
+                None
+            }
+        }
+    }
+
+    async fn cancel_auto_start(&self, lobby: &Arc<Lobby>) {
+        if lobby.cancel_auto_start_task() {
+            #[cfg(not(test))]
+            LobbyService::send_lobby_broadcast(lobby.clone(), LobbyEvent::AutoStartCancel, None)
+                .await;
+        }
+    }
+
+    pub async fn remove_player_from_lobby(
         &self,
         player: Arc<Player>,
-    ) -> Result<Arc<LobbyPlayer>, Box<dyn Error + Send + Sync>> {
+    ) -> Result<Arc<LobbyPlayer>, ServiceError> {
         let lobby = match player.clone().get_lobby() {
             Some(lobby) => lobby,
-            None => return Err("Player is not in a lobby".into()),
+            None => return Err(ServiceError::NotInLobby),
         };
         let lobby_player = lobby.remove_player(player.clone())?;
         let is_lobby_destroy = player == lobby.leader;
+        self.cancel_auto_start(&lobby).await;
         #[cfg(not(test))]
         {
             for lobby_player in lobby.get_players() {
                 let lobby = lobby.clone();
-                tokio::spawn(async move {
-                    if let Err(e) = lobby_player
-                        .player
-                        .send_message(Response::new(
-                            State::LobbyBroadcast as u32,
-                            Arc::new(ResponseData::LobbyBroadcast(LobbyBroadcast {
-                                event: match is_lobby_destroy {
-                                    true => LobbyEvent::Destroy as i32,
-                                    false => LobbyEvent::Leave as i32,
-                                },
-                                lobby: Some(crate::model::lobby::lobby::Lobby::from(lobby)),
-                                cards: None,
-                                current_player: None,
-                                next_player: None,
-                            })),
-                        ))
-                        .await
-                    {
-                        eprintln!("Error sending lobby broadcast: {}", e);
-                    }
-                });
+                if let Err(e) = lobby_player.player.send_message(Response::new(
+                    State::LobbyBroadcast as u32,
+                    Arc::new(ResponseData::LobbyBroadcast(LobbyBroadcast {
+                        event: match is_lobby_destroy {
+                            true => LobbyEvent::Destroy as i32,
+                            false => LobbyEvent::Leave as i32,
+                        },
+                        lobby: Some(crate::model::lobby::lobby::Lobby::from(lobby)),
+                        cards: None,
+                        current_player: None,
+                        next_player: None,
+                        chat_message: None,
+                    })),
+                )) {
+                    eprintln!("Error sending lobby broadcast: {}", e);
+                }
             }
         }
         player.set_lobby(None);
         if is_lobby_destroy {
-            self.remove_lobby(lobby)?;
+            self.remove_lobby(lobby).await?;
+        } else if !self.try_finish_rematch_vote(&lobby).await {
+            self.notify_lobby_list(&lobby, LobbyListEvent::Updated);
         }
         Ok(lobby_player)
     }
 
-    pub fn remove_lobby(
+    pub async fn vote_rematch(
         &self,
-        lobby: Arc<Lobby>,
-    ) -> Result<Arc<Lobby>, Box<dyn Error + Send + Sync>> {
-        let mut lobbies = self.lobbies.lock().unwrap();
-        if !lobbies.contains_key(&lobby.get_id()) {
-            return Err("Lobby does not exist".into());
+        player: Arc<Player>,
+        accept: bool,
+    ) -> Result<bool, ServiceError> {
+        let lobby = match player.get_lobby() {
+            Some(lobby) => lobby,
+            None => return Err(ServiceError::NotInLobby),
+        };
+        if !lobby.is_rematch_vote_open() {
+            return Err(ServiceError::NoRematchInProgress);
+        }
+        let lobby_player = match lobby.get_player(player.id) {
+            Some(lobby_player) => lobby_player,
+            None => return Err(ServiceError::NotInLobby),
+        };
+        if !accept {
+            self.remove_player_from_lobby(player).await?;
+            return Ok(false);
+        }
+        lobby_player.set_rematch_vote(Some(true));
+        if self.try_finish_rematch_vote(&lobby).await {
+            return Ok(true);
         }
-        Ok(lobbies.remove(&lobby.get_id()).unwrap())
+        #[cfg(not(test))]
+        LobbyService::send_lobby_broadcast(lobby, LobbyEvent::RematchVote, Some(player)).await;
+        Ok(false)
+    }
+
+    async fn try_finish_rematch_vote(&self, lobby: &Arc<Lobby>) -> bool {
+        if !lobby.is_rematch_vote_open() || !lobby.is_rematch_accepted() {
+            return false;
+        }
+        lobby.close_rematch_vote();
+        self.notify_lobby_list(lobby, LobbyListEvent::Updated);
+        #[cfg(not(test))]
+        LobbyService::send_lobby_broadcast(lobby.clone(), LobbyEvent::Rematch, None).await;
+        true
+    }
+
+    pub async fn kick_player(
+        &self,
+        player: Arc<Player>,
+        target_id: u32,
+        ban: bool,
+    ) -> Result<Arc<LobbyPlayer>, ServiceError> {
+        let lobby = match player.get_lobby() {
+            Some(lobby) => lobby,
+            None => return Err(ServiceError::NotInLobby),
+        };
+        if player != lobby.leader {
+            return Err(ServiceError::NotLeader);
+        }
+        if player.id == target_id {
+            return Err(ServiceError::LeaderCannotBeKicked);
+        }
+        if player.get_game().is_some() {
+            return Err(ServiceError::GameAlreadyStarted);
+        }
+        let target = match lobby.get_player(target_id) {
+            Some(lobby_player) => lobby_player.player.clone(),
+            None => return Err(ServiceError::NotInLobby),
+        };
+        if ban {
+            lobby.ban_player(&target);
+        }
+        let lobby_player = self.remove_player_from_lobby(target).await?;
+        #[cfg(not(test))]
+        {
+            if let Err(e) = lobby_player.player.send_message(Response::new(
+                State::LobbyBroadcast as u32,
+                Arc::new(ResponseData::LobbyBroadcast(LobbyBroadcast {
+                    event: LobbyEvent::Kick as i32,
+                    lobby: Some(crate::model::lobby::lobby::Lobby::from(lobby)),
+                    cards: None,
+                    current_player: None,
+                    next_player: None,
+                    chat_message: None,
+                })),
+            )) {
+                eprintln!("Error sending lobby broadcast: {}", e);
+            }
+        }
+        Ok(lobby_player)
+    }
+
+    pub async fn remove_lobby(&self, lobby: Arc<Lobby>) -> Result<Arc<Lobby>, ServiceError> {
+        let lobby = match self.lobbies.lock().unwrap().remove(&lobby.get_id()) {
+            Some(lobby) => lobby,
+            None => return Err(ServiceError::LobbyNotFound),
+        };
+        self.notify_lobby_list(&lobby, LobbyListEvent::Removed);
+        Ok(lobby)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::{collections::HashSet, error::Error};
 
     #[tokio::test]
     async fn create_lobby_with_test_user_should_create_lobby(
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let service = LobbyService::new();
-        service.create_lobby(Arc::new(Player::new(0, String::from("test"))), 4)?;
+        service
+            .create_lobby(Arc::new(Player::new(0, String::from("test"))), 4)
+            .await?;
         assert!(service.lobbies.lock().unwrap().get(&0).is_some());
         Ok(())
     }
@@ -171,8 +546,8 @@ mod tests {
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let service = LobbyService::new();
         let leader = Arc::new(Player::new(0, String::from("test")));
-        assert!(service.create_lobby(leader.clone(), 3).is_err());
-        assert!(service.create_lobby(leader, 9).is_err());
+        assert!(service.create_lobby(leader.clone(), 3).await.is_err());
+        assert!(service.create_lobby(leader, 9).await.is_err());
         Ok(())
     }
 
@@ -181,7 +556,7 @@ mod tests {
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let service = LobbyService::new();
         let leader = Arc::new(Player::new(0, String::from("test")));
-        service.create_lobby(leader, 4)?;
+        service.create_lobby(leader, 4).await?;
         assert!(service
             .lobbies
             .lock()
@@ -200,7 +575,9 @@ mod tests {
         let leader = Arc::new(Player::new(0, String::from("test")));
         let lobby = Arc::new(Lobby::new(0, 4, leader.clone()));
         service.lobbies.lock().unwrap().insert(0, lobby.clone());
-        service.add_player_to_lobby(Arc::new(Player::new(1, String::from("test2"))), lobby)?;
+        service
+            .add_player_to_lobby(Arc::new(Player::new(1, String::from("test2"))), lobby)
+            .await?;
         assert!(service
             .lobbies
             .lock()
@@ -220,20 +597,71 @@ mod tests {
         let lobby = Arc::new(Lobby::new(0, 4, leader.clone()));
         service.lobbies.lock().unwrap().insert(0, lobby.clone());
         let player = Arc::new(Player::new(1, String::from("test2")));
-        service.add_player_to_lobby(player.clone(), lobby.clone())?;
+        service
+            .add_player_to_lobby(player.clone(), lobby.clone())
+            .await?;
         assert_eq!(player.get_lobby().unwrap(), lobby);
         Ok(())
     }
 
+    #[tokio::test]
+    async fn add_player_to_lobby_with_full_lobby_should_return_error(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let service = LobbyService::new();
+        let leader = Arc::new(Player::new(0, String::from("test")));
+        let lobby = service.create_lobby(leader, 4).await?;
+        for id in 1..4 {
+            service
+                .add_player_to_lobby(
+                    Arc::new(Player::new(id, format!("test{}", id))),
+                    lobby.clone(),
+                )
+                .await?;
+        }
+        let player = Arc::new(Player::new(4, String::from("test4")));
+        assert_eq!(
+            service
+                .add_player_to_lobby(player.clone(), lobby.clone())
+                .await
+                .err(),
+            Some(ServiceError::LobbyFull)
+        );
+        assert!(lobby.get_player(4).is_none());
+        assert!(player.get_lobby().is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn add_player_to_lobby_with_started_game_should_return_error(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let service = LobbyService::new();
+        let game_service = Arc::new(GameService::new(HashSet::new()));
+        let lobby = ready_auto_start_lobby(&service).await?;
+        GameService::start_game(game_service, lobby.leader.clone(), lobby.clone()).await?;
+        let player = Arc::new(Player::new(2, String::from("test3")));
+        assert_eq!(
+            service
+                .add_player_to_lobby(player.clone(), lobby.clone())
+                .await
+                .err(),
+            Some(ServiceError::GameAlreadyStarted)
+        );
+        assert!(lobby.get_player(2).is_none());
+        assert!(player.get_lobby().is_none());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn remove_player_from_lobby_with_test_user_in_test_lobby_should_quit_lobby(
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let service = LobbyService::new();
         let leader = Arc::new(Player::new(0, String::from("test")));
-        let lobby = service.create_lobby(leader.clone(), 4)?;
+        let lobby = service.create_lobby(leader.clone(), 4).await?;
         let other_player = Arc::new(Player::new(1, String::from("test1")));
-        service.add_player_to_lobby(other_player.clone(), lobby)?;
-        service.remove_player_from_lobby(other_player)?;
+        service
+            .add_player_to_lobby(other_player.clone(), lobby)
+            .await?;
+        service.remove_player_from_lobby(other_player).await?;
         assert!(service
             .lobbies
             .lock()
@@ -250,8 +678,8 @@ mod tests {
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let service = LobbyService::new();
         let leader = Arc::new(Player::new(0, String::from("test")));
-        service.create_lobby(leader.clone(), 4)?;
-        service.remove_player_from_lobby(leader)?;
+        service.create_lobby(leader.clone(), 4).await?;
+        service.remove_player_from_lobby(leader).await?;
         assert!(service.lobbies.lock().unwrap().get(&0).is_none());
         Ok(())
     }
@@ -261,8 +689,8 @@ mod tests {
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let service = LobbyService::new();
         let leader = Arc::new(Player::new(0, String::from("test")));
-        service.create_lobby(leader.clone(), 4)?;
-        service.remove_player_from_lobby(leader)?;
+        service.create_lobby(leader.clone(), 4).await?;
+        service.remove_player_from_lobby(leader).await?;
         assert!(service.lobbies.lock().unwrap().is_empty());
         Ok(())
     }
@@ -294,8 +722,339 @@ mod tests {
             Arc::new(Player::new(0, String::from("test"))),
         ));
         service.lobbies.lock().unwrap().insert(0, lobby.clone());
-        service.remove_lobby(lobby)?;
+        service.remove_lobby(lobby).await?;
         assert_eq!(service.lobbies.lock().unwrap().len(), 0);
         Ok(())
     }
+
+    #[tokio::test]
+    async fn kick_player_with_leader_should_remove_target_from_lobby(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let service = LobbyService::new();
+        let leader = Arc::new(Player::new(0, String::from("test1")));
+        let lobby = service.create_lobby(leader.clone(), 4).await?;
+        let player = Arc::new(Player::new(1, String::from("test2")));
+        service
+            .add_player_to_lobby(player.clone(), lobby.clone())
+            .await?;
+        service.kick_player(leader, player.id, false).await?;
+        assert!(lobby.get_player(player.id).is_none());
+        assert!(player.get_lobby().is_none());
+        service.add_player_to_lobby(player.clone(), lobby).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn kick_player_with_ban_should_refuse_target_to_join_again(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let service = LobbyService::new();
+        let leader = Arc::new(Player::new(0, String::from("test1")));
+        let lobby = service.create_lobby(leader.clone(), 4).await?;
+        let player = Arc::new(Player::new(1, String::from("test2")));
+        service
+            .add_player_to_lobby(player.clone(), lobby.clone())
+            .await?;
+        service.kick_player(leader, player.id, true).await?;
+        assert!(service
+            .add_player_to_lobby(player.clone(), lobby)
+            .await
+            .is_err());
+        assert!(player.get_lobby().is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn kick_player_with_not_leader_should_return_error(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let service = LobbyService::new();
+        let leader = Arc::new(Player::new(0, String::from("test1")));
+        let lobby = service.create_lobby(leader.clone(), 4).await?;
+        let player = Arc::new(Player::new(1, String::from("test2")));
+        service
+            .add_player_to_lobby(player.clone(), lobby.clone())
+            .await?;
+        assert!(service.kick_player(player, leader.id, false).await.is_err());
+        assert!(lobby.get_player(leader.id).is_some());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn kick_player_with_leader_self_should_return_error(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let service = LobbyService::new();
+        let leader = Arc::new(Player::new(0, String::from("test1")));
+        service.create_lobby(leader.clone(), 4).await?;
+        assert!(service
+            .kick_player(leader.clone(), leader.id, false)
+            .await
+            .is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_public_lobbies_with_private_lobby_should_not_return_private_lobby(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let service = LobbyService::new();
+        service
+            .create_lobby(Arc::new(Player::new(0, String::from("test1"))), 4)
+            .await?;
+        let private_lobby = service
+            .create_lobby(Arc::new(Player::new(1, String::from("test2"))), 4)
+            .await?;
+        private_lobby.set_settings(LobbySettings::new(String::from("test"), true, None));
+        let lobbies = service.get_public_lobbies();
+        assert_eq!(lobbies.len(), 1);
+        assert_eq!(lobbies[0].get_id(), 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_lobby_by_invite_code_with_test_lobby_invite_code_should_return_test_lobby(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let service = LobbyService::new();
+        let lobby = service
+            .create_lobby(Arc::new(Player::new(0, String::from("test"))), 4)
+            .await?;
+        let invite_code = lobby.get_invite_code().to_lowercase();
+        assert_eq!(
+            service.get_lobby_by_invite_code(&invite_code).unwrap(),
+            lobby
+        );
+        assert!(service.get_lobby_by_invite_code("").is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_lobby_settings_with_leader_should_update_settings(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let service = LobbyService::new();
+        let leader = Arc::new(Player::new(0, String::from("test")));
+        let lobby = service.create_lobby(leader.clone(), 4).await?;
+        let settings = LobbySettings::new(String::from("test"), true, Some(String::from("1234")));
+        service
+            .update_lobby_settings(leader, settings.clone())
+            .await?;
+        assert_eq!(lobby.get_settings(), settings);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_lobby_settings_with_not_leader_should_return_error(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let service = LobbyService::new();
+        let lobby = service
+            .create_lobby(Arc::new(Player::new(0, String::from("test1"))), 4)
+            .await?;
+        let player = Arc::new(Player::new(1, String::from("test2")));
+        service.add_player_to_lobby(player.clone(), lobby).await?;
+        assert!(service
+            .update_lobby_settings(player, LobbySettings::new(String::from("test"), true, None))
+            .await
+            .is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_lobby_settings_with_invalid_settings_should_return_error(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let service = LobbyService::new();
+        let leader = Arc::new(Player::new(0, String::from("test")));
+        let lobby = service.create_lobby(leader.clone(), 4).await?;
+        let origin_settings = lobby.get_settings();
+        assert!(service
+            .update_lobby_settings(leader, LobbySettings::new(String::new(), true, None))
+            .await
+            .is_err());
+        assert_eq!(lobby.get_settings(), origin_settings);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn toggle_ready_with_player_in_lobby_should_toggle_ready(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let service = LobbyService::new();
+        let player = Arc::new(Player::new(0, String::from("test")));
+        let lobby = service.create_lobby(player.clone(), 4).await?;
+        service.toggle_ready(player.clone()).await?;
+        assert!(lobby.get_player(0).unwrap().get_ready());
+        service.toggle_ready(player).await?;
+        assert!(!lobby.get_player(0).unwrap().get_ready());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn toggle_ready_with_player_not_in_lobby_should_return_error() {
+        let service = LobbyService::new();
+        let player = Arc::new(Player::new(0, String::from("test")));
+        assert!(service.toggle_ready(player).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn get_public_lobbies_page_with_private_lobby_should_skip_private_lobby(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let service = LobbyService::new();
+        service
+            .create_lobby(Arc::new(Player::new(0, String::from("test1"))), 4)
+            .await?;
+        service
+            .create_lobby_with_settings(
+                Arc::new(Player::new(1, String::from("test2"))),
+                4,
+                LobbySettings::new(String::from("test"), true, None),
+            )
+            .await?;
+        let (lobbies, total) = service.get_public_lobbies_page(&LobbyFilter::default(), 0, 10);
+        assert_eq!(lobbies.len(), 1);
+        assert_eq!(total, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn notify_lobby_list_with_private_lobby_should_report_removed(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let service = LobbyService::new();
+        service.subscribe_lobby_list(
+            Arc::new(Player::new(1, String::from("test2"))),
+            LobbyFilter::default(),
+        );
+        let lobby = service
+            .create_lobby(Arc::new(Player::new(0, String::from("test"))), 4)
+            .await?;
+        lobby.set_settings(LobbySettings::new(String::from("test"), true, None));
+        assert!(service
+            .notify_lobby_list(&lobby, LobbyListEvent::Created)
+            .is_empty());
+        let broadcasts = service.notify_lobby_list(&lobby, LobbyListEvent::Updated);
+        assert_eq!(broadcasts[0].1.event, LobbyListEvent::Removed as i32);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn notify_lobby_list_with_subscriber_filter_should_follow_filter(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let service = LobbyService::new();
+        let subscriber = Arc::new(Player::new(1, String::from("test2")));
+        service.subscribe_lobby_list(
+            subscriber.clone(),
+            LobbyFilter {
+                language: Some(String::from("zh")),
+                ..Default::default()
+            },
+        );
+        let lobby = service
+            .create_lobby(Arc::new(Player::new(0, String::from("test"))), 4)
+            .await?;
+        assert!(service
+            .notify_lobby_list(&lobby, LobbyListEvent::Created)
+            .is_empty());
+        lobby.set_settings(LobbySettings {
+            language: String::from("zh"),
+            ..LobbySettings::new(String::from("test"), false, None)
+        });
+        let broadcasts = service.notify_lobby_list(&lobby, LobbyListEvent::Updated);
+        assert_eq!(broadcasts[0].0, subscriber);
+        assert_eq!(broadcasts[0].1.event, LobbyListEvent::Updated as i32);
+        lobby.set_settings(LobbySettings::new(String::from("test"), false, None));
+        let broadcasts = service.notify_lobby_list(&lobby, LobbyListEvent::Updated);
+        assert_eq!(broadcasts[0].1.event, LobbyListEvent::Removed as i32);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn vote_rematch_with_every_player_accepted_should_reset_lobby(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let service = LobbyService::new();
+        let leader = Arc::new(Player::new(0, String::from("test1")));
+        let player = Arc::new(Player::new(1, String::from("test2")));
+        let lobby = service.create_lobby(leader.clone(), 4).await?;
+        service
+            .add_player_to_lobby(player.clone(), lobby.clone())
+            .await?;
+        lobby.get_player(0).unwrap().set_ready(true);
+        lobby.get_player(1).unwrap().set_ready(true);
+        lobby.open_rematch_vote();
+        assert!(!service.vote_rematch(leader, true).await?);
+        assert!(service.vote_rematch(player, true).await?);
+        assert!(!lobby.is_rematch_vote_open());
+        assert!(!lobby.get_player(0).unwrap().get_ready());
+        assert!(!lobby.get_player(1).unwrap().get_ready());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn vote_rematch_with_last_holdout_declined_should_pass_vote(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let service = LobbyService::new();
+        let leader = Arc::new(Player::new(0, String::from("test1")));
+        let player = Arc::new(Player::new(1, String::from("test2")));
+        let lobby = service.create_lobby(leader.clone(), 4).await?;
+        service
+            .add_player_to_lobby(player.clone(), lobby.clone())
+            .await?;
+        lobby.open_rematch_vote();
+        service.vote_rematch(leader, true).await?;
+        assert!(!service.vote_rematch(player.clone(), false).await?);
+        assert!(player.get_lobby().is_none());
+        assert!(!lobby.is_rematch_vote_open());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn vote_rematch_without_vote_in_progress_should_return_error(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let service = LobbyService::new();
+        let leader = Arc::new(Player::new(0, String::from("test")));
+        service.create_lobby(leader.clone(), 4).await?;
+        assert!(service.vote_rematch(leader, true).await.is_err());
+        Ok(())
+    }
+
+    async fn ready_auto_start_lobby(
+        service: &LobbyService,
+    ) -> Result<Arc<Lobby>, Box<dyn Error + Send + Sync>> {
+        let leader = Arc::new(Player::new(0, String::from("test1")));
+        let player = Arc::new(Player::new(1, String::from("test2")));
+        let lobby = service.create_lobby(leader, 4).await?;
+        service.add_player_to_lobby(player, lobby.clone()).await?;
+        lobby.set_settings(LobbySettings {
+            auto_start: true,
+            ..lobby.get_settings()
+        });
+        for lobby_player in lobby.get_players() {
+            lobby_player.set_ready(true);
+        }
+        Ok(lobby)
+    }
+
+    #[tokio::test]
+    async fn finish_auto_start_with_ready_lobby_should_start_game(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let service = Arc::new(LobbyService::new());
+        let game_service = Arc::new(GameService::new(HashSet::new()));
+        let lobby = ready_auto_start_lobby(&service).await?;
+        assert!(
+            LobbyService::finish_auto_start(service, game_service.clone(), lobby.clone())
+                .await
+                .is_some()
+        );
+        assert!(lobby.is_started());
+        assert_eq!(game_service.get_gamees().len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn finish_auto_start_after_manual_start_should_not_start_another_game(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let service = Arc::new(LobbyService::new());
+        let game_service = Arc::new(GameService::new(HashSet::new()));
+        let lobby = ready_auto_start_lobby(&service).await?;
+        GameService::start_game(game_service.clone(), lobby.leader.clone(), lobby.clone()).await?;
+        assert!(
+            LobbyService::finish_auto_start(service, game_service.clone(), lobby)
+                .await
+                .is_none()
+        );
+        assert_eq!(game_service.get_gamees().len(), 1);
+        Ok(())
+    }
 }
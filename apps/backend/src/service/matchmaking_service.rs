@@ -0,0 +1,338 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use crate::{
+    error::ServiceError,
+    lobby::{lobby::Lobby, lobby_settings::LobbySettings},
+    matchmaking::{
+        matchmaking_key::MatchmakingKey, matchmaking_result::MatchmakingResult,
+        wait_estimator::WaitEstimator,
+    },
+    player::Player,
+};
+
+#[cfg(not(test))]
+use crate::model::lobby::broadcast::LobbyEvent;
+
+use super::{lobby_service::LobbyService, player_service::PlayerService};
+
+pub const QUICK_PLAY_LOBBY_NAME: &str = "Quick play";
+
+#[derive(Debug)]
+struct QueueEntry {
+    player: Arc<Player>,
+    joined_at: Instant,
+}
+
+#[derive(Debug)]
+pub struct MatchmakingService {
+    player_service: Arc<PlayerService>,
+    lobby_service: Arc<LobbyService>,
+    queues: Mutex<HashMap<MatchmakingKey, VecDeque<QueueEntry>>>,
+    wait_estimators: Mutex<HashMap<MatchmakingKey, WaitEstimator>>,
+}
+
+impl MatchmakingService {
+    pub fn new(player_service: Arc<PlayerService>, lobby_service: Arc<LobbyService>) -> Self {
+        Self {
+            player_service,
+            lobby_service,
+            queues: Mutex::new(HashMap::new()),
+            wait_estimators: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub async fn join_queue(
+        &self,
+        player: Arc<Player>,
+        max_players: u32,
+        language: String,
+    ) -> Result<MatchmakingResult, ServiceError> {
+        if !(4..=8).contains(&max_players) {
+            return Err(ServiceError::InvalidMaxPlayers);
+        }
+        if player.get_lobby().is_some() || player.get_game().is_some() {
+            return Err(ServiceError::AlreadyInLobby);
+        }
+        if self.is_queued(&player) {
+            return Err(ServiceError::AlreadyInQueue);
+        }
+        let key = MatchmakingKey::new(max_players, language, player.get_rating());
+        let caller = player.clone();
+        let group = {
+            let mut queues = self.queues.lock().unwrap();
+            let queue = queues.entry(key.clone()).or_default();
+            queue.retain(|entry| self.is_available(&entry.player));
+            queue.push_back(QueueEntry {
+                player,
+                joined_at: Instant::now(),
+            });
+            if queue.len() < max_players as usize {
+                return Ok(MatchmakingResult::Queued {
+                    estimated_wait: self.get_estimated_wait(&key),
+                    queue_size: queue.len() as u32,
+                });
+            }
+            queue.drain(..max_players as usize).collect::<Vec<_>>()
+        };
+        let waits = group
+            .iter()
+            .map(|entry| entry.joined_at.elapsed())
+            .collect::<Vec<_>>();
+        match self.create_lobby(&key, group).await {
+            Ok(lobby) => {
+                let mut wait_estimators = self.wait_estimators.lock().unwrap();
+                let wait_estimator = wait_estimators.entry(key.clone()).or_default();
+                for wait in waits {
+                    wait_estimator.record(wait);
+                }
+                Ok(MatchmakingResult::Matched(lobby))
+            }
+            // the group fell apart, but the player is still waiting for the next one
+            Err(e) if self.is_queued(&caller) => {
+                eprintln!("failed to create quick play lobby; err = {:?}", e);
+                Ok(MatchmakingResult::Queued {
+                    estimated_wait: self.get_estimated_wait(&key),
+                    queue_size: self.get_queue_size(&key),
+                })
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn leave_queue(&self, player: &Player) -> Result<(), ServiceError> {
+        let mut queues = self.queues.lock().unwrap();
+        for queue in queues.values_mut() {
+            if let Some(index) = queue.iter().position(|entry| *entry.player == *player) {
+                queue.remove(index);
+                return Ok(());
+            }
+        }
+        Err(ServiceError::NotInQueue)
+    }
+
+    pub fn is_queued(&self, player: &Player) -> bool {
+        self.queues
+            .lock()
+            .unwrap()
+            .values()
+            .any(|queue| queue.iter().any(|entry| *entry.player == *player))
+    }
+
+    fn get_queue_size(&self, key: &MatchmakingKey) -> u32 {
+        match self.queues.lock().unwrap().get(key) {
+            Some(queue) => queue.len() as u32,
+            None => 0,
+        }
+    }
+
+    pub fn get_estimated_wait(&self, key: &MatchmakingKey) -> std::time::Duration {
+        match self.wait_estimators.lock().unwrap().get(key) {
+            Some(wait_estimator) => wait_estimator.estimate(),
+            None => WaitEstimator::default().estimate(),
+        }
+    }
+
+    fn is_available(&self, player: &Arc<Player>) -> bool {
+        self.player_service.is_online(player)
+            && player.get_lobby().is_none()
+            && player.get_game().is_none()
+    }
+
+    async fn create_lobby(
+        &self,
+        key: &MatchmakingKey,
+        group: Vec<QueueEntry>,
+    ) -> Result<Arc<Lobby>, ServiceError> {
+        let leader = match group.first() {
+            Some(entry) => entry.player.clone(),
+            None => return Err(ServiceError::EmptyMatchmakingGroup),
+        };
+        let settings = LobbySettings {
+            language: key.language.clone(),
+            ..LobbySettings::new(String::from(QUICK_PLAY_LOBBY_NAME), true, None)
+        };
+        let lobby = match self
+            .lobby_service
+            .create_lobby_with_settings(leader, key.max_players, settings)
+            .await
+        {
+            Ok(lobby) => lobby,
+            Err(e) => {
+                self.requeue(key, group);
+                return Err(e);
+            }
+        };
+        for entry in group.iter().skip(1) {
+            if let Err(e) = self
+                .lobby_service
+                .add_player_to_lobby(entry.player.clone(), lobby.clone())
+                .await
+            {
+                // the leader leaves last, which removes the lobby
+                for entry in group.iter().rev() {
+                    if entry.player.get_lobby().as_ref() == Some(&lobby) {
+                        self.lobby_service
+                            .remove_player_from_lobby(entry.player.clone())
+                            .await?;
+                    }
+                }
+                self.requeue(key, group);
+                return Err(e);
+            }
+        }
+        #[cfg(not(test))]
+        LobbyService::send_lobby_broadcast(lobby.clone(), LobbyEvent::Matched, None).await;
+        Ok(lobby)
+    }
+
+    fn requeue(&self, key: &MatchmakingKey, group: Vec<QueueEntry>) {
+        let mut queues = self.queues.lock().unwrap();
+        let queue = queues.entry(key.clone()).or_default();
+        for entry in group.into_iter().rev() {
+            if self.is_available(&entry.player) {
+                queue.push_front(entry);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, error::Error};
+
+    use crate::{player::DEFAULT_RATING, service::game_service::GameService};
+
+    use super::*;
+
+    fn setup() -> (Arc<PlayerService>, MatchmakingService) {
+        let lobby_service = Arc::new(LobbyService::new());
+        let player_service = Arc::new(PlayerService::new(
+            lobby_service.clone(),
+            Arc::new(GameService::new(HashSet::new())),
+        ));
+        let matchmaking_service = MatchmakingService::new(player_service.clone(), lobby_service);
+        (player_service, matchmaking_service)
+    }
+
+    #[tokio::test]
+    async fn join_queue_with_not_enough_players_should_queue_player(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let (player_service, service) = setup();
+        let player = player_service.add_player(0, String::from("test"));
+        match service
+            .join_queue(player.clone(), 4, String::from("en"))
+            .await?
+        {
+            MatchmakingResult::Queued { queue_size, .. } => assert_eq!(queue_size, 1),
+            MatchmakingResult::Matched(_) => panic!("should not match"),
+        }
+        assert!(service.is_queued(&player));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn join_queue_with_enough_players_should_create_lobby(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let (player_service, service) = setup();
+        for id in 0..3 {
+            let player = player_service.add_player(id, String::from("test"));
+            service.join_queue(player, 4, String::from("en")).await?;
+        }
+        let player = player_service.add_player(3, String::from("test"));
+        let lobby = match service
+            .join_queue(player.clone(), 4, String::from("en"))
+            .await?
+        {
+            MatchmakingResult::Matched(lobby) => lobby,
+            MatchmakingResult::Queued { .. } => panic!("should match"),
+        };
+        assert_eq!(lobby.get_players().len(), 4);
+        assert_eq!(lobby.leader.id, 0);
+        assert!(!service.is_queued(&player));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn create_lobby_with_unavailable_player_should_remove_lobby_and_requeue_others(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let (player_service, service) = setup();
+        let key = MatchmakingKey::new(4, String::from("en"), DEFAULT_RATING);
+        let players = (0..4)
+            .map(|id| player_service.add_player(id, format!("test{}", id + 1)))
+            .collect::<Vec<_>>();
+        // one of the matched players joined a lobby of their own in the meantime
+        let own_lobby = service
+            .lobby_service
+            .create_lobby(players[2].clone(), 4)
+            .await?;
+        let group = players
+            .iter()
+            .map(|player| QueueEntry {
+                player: player.clone(),
+                joined_at: Instant::now(),
+            })
+            .collect();
+        assert!(service.create_lobby(&key, group).await.is_err());
+        assert!(players[0].get_lobby().is_none());
+        assert!(players[1].get_lobby().is_none());
+        assert_eq!(players[2].get_lobby(), Some(own_lobby));
+        assert_eq!(service.get_queue_size(&key), 3);
+        assert!(!service.is_queued(&players[2]));
+        assert_eq!(
+            service.queues.lock().unwrap()[&key][0].player.id,
+            players[0].id
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn join_queue_with_different_preferences_should_not_group_players(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let (player_service, service) = setup();
+        for id in 0..3 {
+            let player = player_service.add_player(id, String::from("test"));
+            service.join_queue(player, 4, String::from("en")).await?;
+        }
+        let player = player_service.add_player(3, String::from("test"));
+        assert!(matches!(
+            service.join_queue(player, 4, String::from("zh")).await?,
+            MatchmakingResult::Queued { .. }
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn join_queue_with_disconnected_player_should_drop_player(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let (player_service, service) = setup();
+        let player = player_service.add_player(0, String::from("test"));
+        service
+            .join_queue(player.clone(), 4, String::from("en"))
+            .await?;
+        player_service.remove_player(player).await?;
+        let player = player_service.add_player(1, String::from("test"));
+        match service.join_queue(player, 4, String::from("en")).await? {
+            MatchmakingResult::Queued { queue_size, .. } => assert_eq!(queue_size, 1),
+            MatchmakingResult::Matched(_) => panic!("should not match"),
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn leave_queue_with_queued_player_should_remove_player(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let (player_service, service) = setup();
+        let player = player_service.add_player(0, String::from("test"));
+        service
+            .join_queue(player.clone(), 4, String::from("en"))
+            .await?;
+        service.leave_queue(&player)?;
+        assert!(!service.is_queued(&player));
+        assert!(service.leave_queue(&player).is_err());
+        Ok(())
+    }
+}
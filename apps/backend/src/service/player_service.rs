@@ -1,22 +1,25 @@
 #[cfg(not(test))]
-use crate::frame::Frame;
-use crate::player::Player;
+use crate::connection::ClientSender;
+use crate::{error::ServiceError, naming::name_rejection::NameRejection, player::Player};
 
 use std::{
     collections::HashMap,
-    error::Error,
     sync::{Arc, Mutex},
+    time::{Duration, Instant},
 };
-#[cfg(not(test))]
-use tokio::sync::mpsc::Sender;
+use tokio::time::sleep;
 
 use super::{game_service::GameService, lobby_service::LobbyService};
 
+pub const RECONNECT_GRACE_PERIOD: Duration = Duration::from_secs(60);
+
 type ClientMap = Arc<Mutex<HashMap<u32, Arc<Player>>>>;
+type SessionMap = Arc<Mutex<HashMap<String, Arc<Player>>>>;
 
 #[derive(Debug, Clone)]
 pub struct PlayerService {
     online_player_map: ClientMap,
+    disconnected_player_map: SessionMap,
     lobby_service: Arc<LobbyService>,
     game_service: Arc<GameService>,
 }
@@ -25,6 +28,7 @@ impl PlayerService {
     pub fn new(lobby_service: Arc<LobbyService>, game_service: Arc<GameService>) -> Self {
         Self {
             online_player_map: Arc::new(Mutex::new(HashMap::new())),
+            disconnected_player_map: Arc::new(Mutex::new(HashMap::new())),
             lobby_service,
             game_service,
         }
@@ -41,7 +45,7 @@ impl PlayerService {
         &self,
         client_id: u32,
         name: String,
-        #[cfg(not(test))] sender: Sender<Frame>,
+        #[cfg(not(test))] sender: ClientSender,
     ) -> Arc<Player> {
         let player = Arc::new(Player::new(
             client_id,
@@ -56,6 +60,55 @@ impl PlayerService {
         player
     }
 
+    pub fn add_player_with_unique_name(
+        &self,
+        client_id: u32,
+        name: String,
+        #[cfg(not(test))] sender: ClientSender,
+    ) -> Result<Arc<Player>, NameRejection> {
+        let mut online_player_map = self.online_player_map.lock().unwrap();
+        if PlayerService::is_name_used(
+            online_player_map
+                .values()
+                .chain(self.disconnected_player_map.lock().unwrap().values()),
+            &name,
+        ) {
+            return Err(NameRejection::Taken);
+        }
+        let player = Arc::new(Player::new(
+            client_id,
+            name,
+            #[cfg(not(test))]
+            sender,
+        ));
+        online_player_map.insert(client_id, player.clone());
+        Ok(player)
+    }
+
+    pub fn is_name_taken(&self, name: &str) -> bool {
+        let online_player_map = self.online_player_map.lock().unwrap();
+        PlayerService::is_name_used(
+            online_player_map
+                .values()
+                .chain(self.disconnected_player_map.lock().unwrap().values()),
+            name,
+        )
+    }
+
+    fn is_name_used<'a>(mut players: impl Iterator<Item = &'a Arc<Player>>, name: &str) -> bool {
+        let name = name.to_lowercase();
+        players.any(|player| player.name.to_lowercase() == name)
+    }
+
+    pub fn get_player_by_account_id(&self, account_id: u32) -> Option<Arc<Player>> {
+        self.online_player_map
+            .lock()
+            .unwrap()
+            .values()
+            .find(|player| player.get_account_id() == Some(account_id))
+            .cloned()
+    }
+
     pub fn get_players(&self) -> Vec<Arc<Player>> {
         self.online_player_map
             .lock()
@@ -65,25 +118,132 @@ impl PlayerService {
             .collect()
     }
 
-    pub fn remove_player(
+    pub fn is_online(&self, player: &Arc<Player>) -> bool {
+        self.online_player_map
+            .lock()
+            .unwrap()
+            .values()
+            .any(|online_player| Arc::ptr_eq(online_player, player))
+    }
+
+    pub async fn remove_player(&self, player: Arc<Player>) -> Result<Arc<Player>, ServiceError> {
+        let player = {
+            let mut online_player_map = self.online_player_map.lock().unwrap();
+            let client_id = match online_player_map
+                .iter()
+                .find(|(_, online_player)| **online_player == player)
+            {
+                Some((client_id, _)) => *client_id,
+                None => return Err(ServiceError::PlayerNotFound),
+            };
+            online_player_map.remove(&client_id).unwrap()
+        };
+        self.clean_up_player(player).await
+    }
+
+    async fn clean_up_player(&self, player: Arc<Player>) -> Result<Arc<Player>, ServiceError> {
+        self.lobby_service.unsubscribe_lobby_list(&player);
+        if let Some(lobby) = player.get_lobby() {
+            println!("cleaning: remove player from lobby");
+            self.lobby_service
+                .remove_player_from_lobby(player.clone())
+                .await?;
+            // the player may have been the last one not ready
+            LobbyService::schedule_auto_start(
+                self.lobby_service.clone(),
+                self.game_service.clone(),
+                lobby,
+            )
+            .await;
+        };
+        if player.clone().get_game().is_some() {
+            println!("cleaning: remove player from game");
+            self.game_service
+                .remove_player_from_game(player.clone())
+                .await?;
+        };
+        if player.clone().get_spectating().is_some() {
+            println!("cleaning: stop spectating");
+            self.game_service.stop_spectating(player.clone()).await?;
+        };
+        Ok(player)
+    }
+
+    pub fn disconnect_player(&self, client_id: u32) -> Result<Arc<Player>, ServiceError> {
+        let player = match self.online_player_map.lock().unwrap().remove(&client_id) {
+            Some(player) => player,
+            None => return Err(ServiceError::PlayerNotFound),
+        };
+        let disconnected_at = Instant::now();
+        player.set_disconnected_at(Some(disconnected_at));
+        let session_token = player.get_session_token();
+        self.disconnected_player_map
+            .lock()
+            .unwrap()
+            .insert(session_token.clone(), player.clone());
+        let player_service = self.clone();
+        tokio::spawn(async move {
+            sleep(RECONNECT_GRACE_PERIOD).await;
+            if let Err(e) = player_service
+                .expire_session(&session_token, disconnected_at)
+                .await
+            {
+                eprintln!("failed to clean up player's resource, err: {e}");
+            }
+        });
+        Ok(player)
+    }
+
+    async fn expire_session(
         &self,
-        player: Arc<Player>,
-    ) -> Result<Arc<Player>, Box<dyn Error + Send + Sync>> {
-        match self.online_player_map.lock().unwrap().remove(&player.id) {
-            Some(player) => {
-                if player.clone().get_lobby().is_some() {
-                    println!("cleaning: remove player from lobby");
-                    self.lobby_service
-                        .remove_player_from_lobby(player.clone())?;
-                };
-                if player.clone().get_game().is_some() {
-                    println!("cleaning: remove player from game");
-                    self.game_service.remove_player_from_game(player.clone())?;
-                };
-                Ok(player)
+        session_token: &str,
+        disconnected_at: Instant,
+    ) -> Result<Option<Arc<Player>>, ServiceError> {
+        let player = {
+            let mut disconnected_player_map = self.disconnected_player_map.lock().unwrap();
+            match disconnected_player_map.get(session_token) {
+                Some(player) if player.get_disconnected_at() == Some(disconnected_at) => {
+                    disconnected_player_map.remove(session_token).unwrap()
+                }
+                _ => return Ok(None),
             }
-            None => Err("Player not found".into()),
+        };
+        // the game may be handling a request of the player
+        let game = player.get_game().or_else(|| player.get_spectating());
+        let _guard = match &game {
+            Some(game) => Some(game.lock_requests().await),
+            None => None,
+        };
+        Ok(Some(self.clean_up_player(player).await?))
+    }
+
+    pub fn reconnect_player(
+        &self,
+        client_id: u32,
+        session_token: &str,
+        #[cfg(not(test))] sender: ClientSender,
+    ) -> Result<Arc<Player>, ServiceError> {
+        if self.get_player(client_id).is_some() {
+            return Err(ServiceError::AlreadyConnected);
         }
+        let player = match self
+            .disconnected_player_map
+            .lock()
+            .unwrap()
+            .remove(session_token)
+        {
+            Some(player) => player,
+            None => return Err(ServiceError::InvalidSessionToken),
+        };
+        player.renew_session_token();
+        #[cfg(not(test))]
+        player.set_sender(sender);
+        player.set_disconnected_at(None);
+        self.online_player_map
+            .lock()
+            .unwrap()
+            .insert(client_id, player.clone());
+        Ok(player)
     }
 }
 
@@ -130,8 +290,8 @@ mod tests {
             Arc::new(GameService::new(HashSet::new())),
         );
         let player = service.add_player(0, String::from("test"));
-        let lobby = lobby_service.create_lobby(player.clone(), 4)?;
-        service.remove_player(player.clone())?;
+        let lobby = lobby_service.create_lobby(player.clone(), 4).await?;
+        service.remove_player(player.clone()).await?;
         assert_eq!(lobby.get_players().len(), 0);
         assert!(player.get_lobby().is_none());
         Ok(())
@@ -144,10 +304,10 @@ mod tests {
         let lobby_service = Arc::new(LobbyService::new());
         let service = PlayerService::new(lobby_service.clone(), game_service.clone());
         let player = service.add_player(0, String::from("test"));
-        let lobby = lobby_service.create_lobby(player.clone(), 4)?;
+        let lobby = lobby_service.create_lobby(player.clone(), 4).await?;
         lobby.get_player(player.id).unwrap().set_ready(true);
-        let game = GameService::start_game(game_service, player.clone(), lobby)?;
-        service.remove_player(player.clone())?;
+        let game = GameService::start_game(game_service, player.clone(), lobby).await?;
+        service.remove_player(player.clone()).await?;
         assert_eq!(game.get_players().len(), 0);
         assert!(player.get_game().is_none());
         Ok(())
@@ -162,7 +322,94 @@ mod tests {
         );
         assert!(service
             .remove_player(Arc::new(Player::new(0, String::from("test"))))
+            .await
             .is_err());
         Ok(())
     }
+
+    #[tokio::test]
+    async fn disconnect_player_with_a_player_in_lobby_should_keep_player_in_lobby(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let service = PlayerService::new(
+            lobby_service.clone(),
+            Arc::new(GameService::new(HashSet::new())),
+        );
+        let player = service.add_player(0, String::from("test"));
+        let lobby = lobby_service.create_lobby(player.clone(), 4).await?;
+        service.disconnect_player(0)?;
+        assert!(service.get_player(0).is_none());
+        assert!(!service.is_online(&player));
+        assert!(player.get_disconnected_at().is_some());
+        assert_eq!(lobby.get_players().len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn expire_session_with_a_disconnected_player_should_remove_player_from_lobby(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let service = PlayerService::new(
+            lobby_service.clone(),
+            Arc::new(GameService::new(HashSet::new())),
+        );
+        let player = service.add_player(0, String::from("test"));
+        let lobby = lobby_service.create_lobby(player.clone(), 4).await?;
+        service.disconnect_player(0)?;
+        let expired = service
+            .expire_session(
+                &player.get_session_token(),
+                player.get_disconnected_at().unwrap(),
+            )
+            .await?;
+        assert!(expired.is_some());
+        assert_eq!(lobby.get_players().len(), 0);
+        assert!(player.get_lobby().is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn expire_session_with_a_reconnected_player_should_do_nothing(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let lobby_service = Arc::new(LobbyService::new());
+        let service = PlayerService::new(
+            lobby_service.clone(),
+            Arc::new(GameService::new(HashSet::new())),
+        );
+        let player = service.add_player(0, String::from("test"));
+        let lobby = lobby_service.create_lobby(player.clone(), 4).await?;
+        service.disconnect_player(0)?;
+        let disconnected_at = player.get_disconnected_at().unwrap();
+        let session_token = player.get_session_token();
+        service.reconnect_player(1, &session_token)?;
+        assert!(service
+            .expire_session(&session_token, disconnected_at)
+            .await?
+            .is_none());
+        assert_eq!(lobby.get_players().len(), 1);
+        assert!(service.is_online(&player));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn add_player_with_unique_name_with_taken_name_should_return_taken(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let service = PlayerService::new(
+            Arc::new(LobbyService::new()),
+            Arc::new(GameService::new(HashSet::new())),
+        );
+        service.add_player(0, String::from("test"));
+        assert_eq!(
+            service
+                .add_player_with_unique_name(1, String::from("Test"))
+                .err(),
+            Some(NameRejection::Taken)
+        );
+        service.disconnect_player(0)?;
+        assert!(service.is_name_taken("TEST"));
+        assert!(service
+            .add_player_with_unique_name(1, String::from("test2"))
+            .is_ok());
+        Ok(())
+    }
 }
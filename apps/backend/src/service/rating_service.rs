@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use crate::{
+    error::ServiceError,
+    game::game::Game,
+    player::{Player, DEFAULT_RATING},
+    stats::{
+        rating::calculate_ratings,
+        rating_repository::{LeaderboardEntry, RatingRepository, RatingUpdate},
+    },
+};
+
+pub const DEFAULT_LEADERBOARD_PAGE_SIZE: u32 = 20;
+pub const MAX_LEADERBOARD_PAGE_SIZE: u32 = 50;
+
+#[derive(Debug)]
+pub struct RatingService {
+    repository: RatingRepository,
+}
+
+impl RatingService {
+    pub fn new(repository: RatingRepository) -> Self {
+        Self { repository }
+    }
+
+    pub fn get_rating(&self, account_id: u32) -> Result<u32, ServiceError> {
+        Ok(self
+            .repository
+            .get_rating(account_id)?
+            .unwrap_or(DEFAULT_RATING))
+    }
+
+    pub fn rate_game(game: &Game) -> Vec<(Arc<Player>, RatingUpdate)> {
+        let game_players = game.get_players();
+        let ratings = calculate_ratings(
+            &game_players
+                .iter()
+                .map(|game_player| (game_player.player.get_rating(), game_player.get_score()))
+                .collect::<Vec<_>>(),
+        );
+        game_players
+            .iter()
+            .zip(ratings)
+            .filter_map(|(game_player, rating)| {
+                let account = game_player.player.get_account()?;
+                Some((
+                    game_player.player.clone(),
+                    RatingUpdate {
+                        account_id: account.id,
+                        username: account.username,
+                        rating,
+                    },
+                ))
+            })
+            .collect()
+    }
+
+    pub fn get_leaderboard_page(
+        &self,
+        page: u32,
+        page_size: u32,
+    ) -> Result<(Vec<LeaderboardEntry>, u32), ServiceError> {
+        Ok((
+            self.repository
+                .get_leaderboard(page.saturating_mul(page_size), page_size)?,
+            self.repository.count()?,
+        ))
+    }
+
+    pub fn get_leaderboard_entry(
+        &self,
+        account_id: u32,
+    ) -> Result<Option<LeaderboardEntry>, ServiceError> {
+        Ok(self.repository.get_entry(account_id)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use super::*;
+    use crate::{account::account::Account, player::Player};
+
+    #[test]
+    fn rate_game_with_registered_winner_should_rate_registered_players_only() {
+        let player1 = Arc::new(Player::new(0, String::from("test1")));
+        player1.set_account(Some(Account::new(1, String::from("test1"))));
+        let player2 = Arc::new(Player::new(1, String::from("test2")));
+        let game = Game::new(0, vec![player1.clone(), player2]);
+        game.get_player(0)
+            .unwrap()
+            .record_words(&[String::from("apple")]);
+        let ratings = RatingService::rate_game(&game);
+        assert_eq!(ratings.len(), 1);
+        assert_eq!(ratings[0].0, player1);
+        assert_eq!(
+            ratings[0].1,
+            RatingUpdate {
+                account_id: 1,
+                username: String::from("test1"),
+                rating: 1516,
+            }
+        );
+        assert_eq!(player1.get_rating(), DEFAULT_RATING);
+    }
+
+    #[test]
+    fn get_rating_without_rated_game_should_return_default_rating(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let rating_service = RatingService::new(RatingRepository::open_in_memory()?);
+        assert_eq!(rating_service.get_rating(1)?, DEFAULT_RATING);
+        assert!(rating_service.get_leaderboard_entry(1)?.is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+use std::sync::Arc;
+
+use crate::{
+    error::ServiceError,
+    player::Player,
+    stats::{
+        match_record::MatchRecord, match_repository::MatchRepository, player_stats::PlayerStats,
+        rating_repository::RatingUpdate,
+    },
+};
+
+use super::blocking::run_blocking;
+
+pub const DEFAULT_MATCH_HISTORY_LIMIT: u32 = 10;
+pub const MAX_MATCH_HISTORY_LIMIT: u32 = 50;
+
+#[derive(Debug)]
+pub struct StatsService {
+    repository: MatchRepository,
+}
+
+impl StatsService {
+    pub fn new(repository: MatchRepository) -> Self {
+        Self { repository }
+    }
+
+    pub async fn record_result(
+        stats_service: Arc<StatsService>,
+        record: MatchRecord,
+        ratings: Vec<(Arc<Player>, RatingUpdate)>,
+    ) -> Result<u32, ServiceError> {
+        let updates = ratings
+            .iter()
+            .map(|(_, update)| update.clone())
+            .collect::<Vec<_>>();
+        let id = run_blocking(move || stats_service.record_match(&record, &updates)).await?;
+        for (player, update) in ratings {
+            player.set_rating(update.rating);
+        }
+        Ok(id)
+    }
+
+    fn record_match(
+        &self,
+        record: &MatchRecord,
+        ratings: &[RatingUpdate],
+    ) -> Result<u32, ServiceError> {
+        Ok(self.repository.insert_with_ratings(record, ratings)?)
+    }
+
+    pub fn get_player_stats(&self, account_id: u32) -> Result<PlayerStats, ServiceError> {
+        Ok(PlayerStats::from_results(
+            &self.repository.get_results(account_id)?,
+        ))
+    }
+
+    pub fn get_match_history(
+        &self,
+        account_id: u32,
+        limit: u32,
+    ) -> Result<Vec<MatchRecord>, ServiceError> {
+        let limit = match limit {
+            0 => DEFAULT_MATCH_HISTORY_LIMIT,
+            limit => limit.min(MAX_MATCH_HISTORY_LIMIT),
+        };
+        Ok(self.repository.get_recent_matches(account_id, limit)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use super::*;
+    use crate::{
+        account::account::Account, game::game::Game, service::rating_service::RatingService,
+        stats::rating_repository::RatingRepository,
+    };
+
+    fn stats_service() -> Result<Arc<StatsService>, Box<dyn Error + Send + Sync>> {
+        Ok(Arc::new(StatsService::new(
+            MatchRepository::open_in_memory()?,
+        )))
+    }
+
+    fn test_game() -> Game {
+        let player1 = Arc::new(Player::new(0, String::from("test1")));
+        player1.set_account(Some(Account::new(1, String::from("test1"))));
+        let player2 = Arc::new(Player::new(1, String::from("test2")));
+        let game = Game::new(0, vec![player1, player2]);
+        game.get_player(0)
+            .unwrap()
+            .record_words(&[String::from("apple")]);
+        game
+    }
+
+    async fn record_game(
+        stats_service: &Arc<StatsService>,
+        game: &Game,
+    ) -> Result<u32, ServiceError> {
+        StatsService::record_result(
+            stats_service.clone(),
+            MatchRecord::from_game(game),
+            RatingService::rate_game(game),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn record_result_with_test_game_should_update_player_stats(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let stats_service = stats_service()?;
+        record_game(&stats_service, &test_game()).await?;
+        record_game(&stats_service, &test_game()).await?;
+        let stats = stats_service.get_player_stats(1)?;
+        assert_eq!(stats.games_played, 2);
+        assert_eq!(stats.wins, 2);
+        assert_eq!(stats.average_score, 5.0);
+        assert_eq!(stats.longest_word, Some(String::from("apple")));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn record_result_with_registered_player_should_store_and_apply_rating(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        // both repositories use the same database, like the server does
+        let database = "file:record_result?mode=memory&cache=shared";
+        let stats_service = Arc::new(StatsService::new(MatchRepository::open(database)?));
+        let rating_service = RatingService::new(RatingRepository::open(database)?);
+        let game = test_game();
+        record_game(&stats_service, &game).await?;
+        assert_eq!(game.get_player(0).unwrap().player.get_rating(), 1516);
+        assert_eq!(rating_service.get_rating(1)?, 1516);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_match_history_with_zero_limit_should_use_default_limit(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let stats_service = stats_service()?;
+        for _ in 0..DEFAULT_MATCH_HISTORY_LIMIT + 1 {
+            record_game(&stats_service, &test_game()).await?;
+        }
+        assert_eq!(
+            stats_service.get_match_history(1, 0)?.len() as u32,
+            DEFAULT_MATCH_HISTORY_LIMIT
+        );
+        assert_eq!(stats_service.get_match_history(1, 3)?.len(), 3);
+        assert!(stats_service.get_match_history(2, 0)?.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+pub mod match_record;
+pub mod match_repository;
+pub mod player_stats;
+pub mod rating;
+pub mod rating_repository;
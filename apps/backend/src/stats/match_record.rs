@@ -0,0 +1,92 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::game::game::Game;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchPlayerRecord {
+    pub account_id: Option<u32>,
+    pub name: String,
+    pub score: u32,
+    pub words: Vec<String>,
+    pub winner: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchRecord {
+    pub id: u32,
+    pub started_at: u64,
+    pub duration: Duration,
+    pub language: String,
+    pub dictionary: String,
+    pub players: Vec<MatchPlayerRecord>,
+}
+
+impl MatchRecord {
+    pub fn from_game(game: &Game) -> Self {
+        let game_players = game.get_players();
+        let top_score = game_players
+            .iter()
+            .map(|game_player| game_player.get_score())
+            .max()
+            .unwrap_or(0);
+        let settings = game.get_settings();
+        Self {
+            id: 0,
+            started_at: game
+                .get_started_at()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            duration: SystemTime::now()
+                .duration_since(game.get_started_at())
+                .unwrap_or_default(),
+            language: settings
+                .as_ref()
+                .map(|settings| settings.language.clone())
+                .unwrap_or_default(),
+            dictionary: settings
+                .as_ref()
+                .map(|settings| settings.dictionary.clone())
+                .unwrap_or_default(),
+            players: game_players
+                .iter()
+                .map(|game_player| MatchPlayerRecord {
+                    account_id: game_player.player.get_account_id(),
+                    name: game_player.player.name.clone(),
+                    score: game_player.get_score(),
+                    words: game_player.get_words(),
+                    winner: game_player.get_score() == top_score,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::player::Player;
+
+    #[test]
+    fn from_game_with_scored_players_should_mark_top_score_as_winner() {
+        let player1 = Arc::new(Player::new(0, String::from("test1")));
+        let player2 = Arc::new(Player::new(1, String::from("test2")));
+        let game = Game::new(0, vec![player1, player2]);
+        game.get_player(0)
+            .unwrap()
+            .record_words(&[String::from("apple")]);
+        game.get_player(1)
+            .unwrap()
+            .record_words(&[String::from("ox")]);
+        let record = MatchRecord::from_game(&game);
+        let winner = record.players.iter().find(|player| player.winner).unwrap();
+        assert_eq!(winner.name, "test1");
+        assert_eq!(winner.score, 5);
+        assert_eq!(
+            record.players.iter().filter(|player| player.winner).count(),
+            1
+        );
+    }
+}
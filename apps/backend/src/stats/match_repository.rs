@@ -0,0 +1,248 @@
+use std::{error::Error, sync::Mutex, time::Duration};
+
+use rusqlite::{params, Connection};
+
+use super::{
+    match_record::{MatchPlayerRecord, MatchRecord},
+    rating_repository::{RatingRepository, RatingUpdate},
+};
+
+#[derive(Debug)]
+pub struct MatchRepository {
+    connection: Mutex<Connection>,
+}
+
+impl MatchRepository {
+    pub fn open(path: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        MatchRepository::init(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self, Box<dyn Error + Send + Sync>> {
+        MatchRepository::init(Connection::open_in_memory()?)
+    }
+
+    fn init(connection: Connection) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS match_record (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                started_at INTEGER NOT NULL,
+                duration INTEGER NOT NULL,
+                language TEXT NOT NULL,
+                dictionary TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS match_player (
+                match_id INTEGER NOT NULL REFERENCES match_record(id),
+                account_id INTEGER,
+                name TEXT NOT NULL,
+                score INTEGER NOT NULL,
+                words TEXT NOT NULL,
+                winner INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS match_player_account_id ON match_player(account_id);",
+        )?;
+        RatingRepository::create_table(&connection)?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    /**
+     * Store the record together with the new ratings of its players. Either both are stored or
+     * neither is. Return the id of the record.
+     */
+    pub fn insert_with_ratings(
+        &self,
+        record: &MatchRecord,
+        ratings: &[RatingUpdate],
+    ) -> Result<u32, Box<dyn Error + Send + Sync>> {
+        let mut connection = self.connection.lock().unwrap();
+        let transaction = connection.transaction()?;
+        transaction.execute(
+            "INSERT INTO match_record (started_at, duration, language, dictionary)
+            VALUES (?1, ?2, ?3, ?4)",
+            params![
+                record.started_at,
+                record.duration.as_secs(),
+                record.language,
+                record.dictionary
+            ],
+        )?;
+        let id = transaction.last_insert_rowid() as u32;
+        for player in &record.players {
+            transaction.execute(
+                "INSERT INTO match_player (match_id, account_id, name, score, words, winner)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    id,
+                    player.account_id,
+                    player.name,
+                    player.score,
+                    player.words.join(" "),
+                    player.winner
+                ],
+            )?;
+        }
+        for rating in ratings {
+            RatingRepository::write_rating(&transaction, rating)?;
+        }
+        transaction.commit()?;
+        Ok(id)
+    }
+
+    pub fn get_results(
+        &self,
+        account_id: u32,
+    ) -> Result<Vec<MatchPlayerRecord>, Box<dyn Error + Send + Sync>> {
+        let connection = self.connection.lock().unwrap();
+        let mut statement = connection.prepare(
+            "SELECT account_id, name, score, words, winner FROM match_player WHERE account_id = ?1",
+        )?;
+        let results = statement
+            .query_map(params![account_id], MatchRepository::read_player)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(results)
+    }
+
+    pub fn get_recent_matches(
+        &self,
+        account_id: u32,
+        limit: u32,
+    ) -> Result<Vec<MatchRecord>, Box<dyn Error + Send + Sync>> {
+        let connection = self.connection.lock().unwrap();
+        let mut statement = connection.prepare(
+            "SELECT id, started_at, duration, language, dictionary FROM match_record
+            WHERE id IN (SELECT match_id FROM match_player WHERE account_id = ?1)
+            ORDER BY started_at DESC, id DESC LIMIT ?2",
+        )?;
+        let mut matches = statement
+            .query_map(params![account_id, limit], |row| {
+                Ok(MatchRecord {
+                    id: row.get(0)?,
+                    started_at: row.get(1)?,
+                    duration: Duration::from_secs(row.get(2)?),
+                    language: row.get(3)?,
+                    dictionary: row.get(4)?,
+                    players: Vec::new(),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut statement = connection.prepare(
+            "SELECT account_id, name, score, words, winner FROM match_player WHERE match_id = ?1",
+        )?;
+        for record in matches.iter_mut() {
+            record.players = statement
+                .query_map(params![record.id], MatchRepository::read_player)?
+                .collect::<Result<Vec<_>, _>>()?;
+        }
+        Ok(matches)
+    }
+
+    fn read_player(row: &rusqlite::Row) -> rusqlite::Result<MatchPlayerRecord> {
+        let words: String = row.get(3)?;
+        Ok(MatchPlayerRecord {
+            account_id: row.get(0)?,
+            name: row.get(1)?,
+            score: row.get(2)?,
+            words: words.split_whitespace().map(String::from).collect(),
+            winner: row.get(4)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(started_at: u64, account_id: Option<u32>) -> MatchRecord {
+        MatchRecord {
+            id: 0,
+            started_at,
+            duration: Duration::from_secs(300),
+            language: String::from("en"),
+            dictionary: String::from("default"),
+            players: vec![
+                MatchPlayerRecord {
+                    account_id,
+                    name: String::from("test1"),
+                    score: 8,
+                    words: vec![String::from("apple"), String::from("pen")],
+                    winner: true,
+                },
+                MatchPlayerRecord {
+                    account_id: None,
+                    name: String::from("test2"),
+                    score: 2,
+                    words: vec![String::from("ox")],
+                    winner: false,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn insert_with_ratings_with_record_should_be_found_in_results(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let repository = MatchRepository::open_in_memory()?;
+        repository.insert_with_ratings(&record(0, Some(1)), &[])?;
+        let results = repository.get_results(1)?;
+        assert_eq!(results, vec![record(0, Some(1)).players[0].clone()]);
+        assert!(repository.get_results(2)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn get_recent_matches_with_records_should_return_newest_first(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let repository = MatchRepository::open_in_memory()?;
+        let old_id = repository.insert_with_ratings(&record(100, Some(1)), &[])?;
+        let new_id = repository.insert_with_ratings(&record(200, Some(1)), &[])?;
+        repository.insert_with_ratings(&record(300, Some(2)), &[])?;
+        let matches = repository.get_recent_matches(1, 10)?;
+        assert_eq!(
+            matches.iter().map(|record| record.id).collect::<Vec<_>>(),
+            vec![new_id, old_id]
+        );
+        assert_eq!(matches[0].players.len(), 2);
+        assert_eq!(repository.get_recent_matches(1, 1)?.len(), 1);
+        Ok(())
+    }
+
+    fn rating(account_id: u32) -> RatingUpdate {
+        RatingUpdate {
+            account_id,
+            username: String::from("test1"),
+            rating: 1516,
+        }
+    }
+
+    #[test]
+    fn insert_with_ratings_should_store_record_and_ratings(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let repository = MatchRepository::open_in_memory()?;
+        repository.insert_with_ratings(&record(0, Some(1)), &[rating(1)])?;
+        assert_eq!(repository.get_results(1)?.len(), 1);
+        let stored: u32 = repository.connection.lock().unwrap().query_row(
+            "SELECT rating FROM rating WHERE account_id = 1",
+            (),
+            |row| row.get(0),
+        )?;
+        assert_eq!(stored, 1516);
+        Ok(())
+    }
+
+    #[test]
+    fn insert_with_ratings_when_rating_fails_should_not_store_record(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let repository = MatchRepository::open_in_memory()?;
+        repository
+            .connection
+            .lock()
+            .unwrap()
+            .execute_batch("DROP TABLE rating")?;
+        assert!(repository
+            .insert_with_ratings(&record(0, Some(1)), &[rating(1)])
+            .is_err());
+        assert!(repository.get_results(1)?.is_empty());
+        Ok(())
+    }
+}
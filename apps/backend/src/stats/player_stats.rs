@@ -0,0 +1,60 @@
+use super::match_record::MatchPlayerRecord;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlayerStats {
+    pub games_played: u32,
+    pub wins: u32,
+    pub average_score: f32,
+    pub longest_word: Option<String>,
+}
+
+impl PlayerStats {
+    pub fn from_results(results: &[MatchPlayerRecord]) -> Self {
+        if results.is_empty() {
+            return PlayerStats::default();
+        }
+        let total_score: u32 = results.iter().map(|result| result.score).sum();
+        Self {
+            games_played: results.len() as u32,
+            wins: results.iter().filter(|result| result.winner).count() as u32,
+            average_score: total_score as f32 / results.len() as f32,
+            longest_word: results
+                .iter()
+                .flat_map(|result| result.words.iter())
+                .max_by_key(|word| word.chars().count())
+                .cloned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(score: u32, words: &[&str], winner: bool) -> MatchPlayerRecord {
+        MatchPlayerRecord {
+            account_id: Some(1),
+            name: String::from("test"),
+            score,
+            words: words.iter().map(|word| String::from(*word)).collect(),
+            winner,
+        }
+    }
+
+    #[test]
+    fn from_results_without_results_should_return_empty_stats() {
+        assert_eq!(PlayerStats::from_results(&[]), PlayerStats::default());
+    }
+
+    #[test]
+    fn from_results_with_results_should_aggregate() {
+        let stats = PlayerStats::from_results(&[
+            result(10, &["apple", "pen"], true),
+            result(5, &["banana"], false),
+        ]);
+        assert_eq!(stats.games_played, 2);
+        assert_eq!(stats.wins, 1);
+        assert_eq!(stats.average_score, 7.5);
+        assert_eq!(stats.longest_word, Some(String::from("banana")));
+    }
+}
@@ -0,0 +1,60 @@
+pub const RATING_K_FACTOR: f64 = 32.0;
+
+/**
+ * Multiplayer Elo. Every pair of players is treated as one match decided by score, and each
+ * player's change is scaled down by the number of opponents so that larger games do not swing
+ * ratings harder. Takes `(rating, score)` of every player and returns their new ratings.
+ */
+pub fn calculate_ratings(players: &[(u32, u32)]) -> Vec<u32> {
+    if players.len() < 2 {
+        return players.iter().map(|(rating, _)| *rating).collect();
+    }
+    let k = RATING_K_FACTOR / (players.len() - 1) as f64;
+    players
+        .iter()
+        .enumerate()
+        .map(|(i, (rating, score))| {
+            let mut change = 0.0;
+            for (j, (opponent_rating, opponent_score)) in players.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+                let expected =
+                    1.0 / (1.0 + 10f64.powf((*opponent_rating as f64 - *rating as f64) / 400.0));
+                let actual = match score.cmp(opponent_score) {
+                    std::cmp::Ordering::Greater => 1.0,
+                    std::cmp::Ordering::Equal => 0.5,
+                    std::cmp::Ordering::Less => 0.0,
+                };
+                change += k * (actual - expected);
+            }
+            (*rating as f64 + change).round().max(0.0) as u32
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calculate_ratings_with_equal_ratings_should_move_winner_up() {
+        assert_eq!(
+            calculate_ratings(&[(1500, 10), (1500, 5)]),
+            vec![1516, 1484]
+        );
+    }
+
+    #[test]
+    fn calculate_ratings_with_draw_should_keep_equal_ratings() {
+        assert_eq!(
+            calculate_ratings(&[(1500, 5), (1500, 5), (1500, 5)]),
+            vec![1500, 1500, 1500]
+        );
+    }
+
+    #[test]
+    fn calculate_ratings_with_single_player_should_keep_rating() {
+        assert_eq!(calculate_ratings(&[(1500, 10)]), vec![1500]);
+    }
+}
@@ -0,0 +1,194 @@
+use std::{error::Error, sync::Mutex};
+
+use rusqlite::{params, Connection, OptionalExtension};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeaderboardEntry {
+    pub rank: u32,
+    pub account_id: u32,
+    pub username: String,
+    pub rating: u32,
+    pub games_played: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RatingUpdate {
+    pub account_id: u32,
+    pub username: String,
+    pub rating: u32,
+}
+
+#[derive(Debug)]
+pub struct RatingRepository {
+    connection: Mutex<Connection>,
+}
+
+impl RatingRepository {
+    pub fn open(path: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        RatingRepository::init(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self, Box<dyn Error + Send + Sync>> {
+        RatingRepository::init(Connection::open_in_memory()?)
+    }
+
+    fn init(connection: Connection) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        RatingRepository::create_table(&connection)?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    /**
+     * The match repository stores ratings along with the game in the same database, so it
+     * creates the table too.
+     */
+    pub fn create_table(connection: &Connection) -> Result<(), Box<dyn Error + Send + Sync>> {
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS rating (
+                account_id INTEGER PRIMARY KEY,
+                username TEXT NOT NULL,
+                rating INTEGER NOT NULL,
+                games_played INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS rating_rating ON rating(rating);",
+        )?;
+        Ok(())
+    }
+
+    pub fn get_rating(&self, account_id: u32) -> Result<Option<u32>, Box<dyn Error + Send + Sync>> {
+        Ok(self
+            .connection
+            .lock()
+            .unwrap()
+            .query_row(
+                "SELECT rating FROM rating WHERE account_id = ?1",
+                params![account_id],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    pub fn write_rating(
+        connection: &Connection,
+        update: &RatingUpdate,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        connection.execute(
+            "INSERT INTO rating (account_id, username, rating, games_played) VALUES (?1, ?2, ?3, 1)
+            ON CONFLICT(account_id) DO UPDATE SET
+                username = excluded.username,
+                rating = excluded.rating,
+                games_played = games_played + 1",
+            params![update.account_id, update.username, update.rating],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_leaderboard(
+        &self,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Vec<LeaderboardEntry>, Box<dyn Error + Send + Sync>> {
+        let connection = self.connection.lock().unwrap();
+        let mut statement = connection.prepare(
+            "SELECT
+                (SELECT COUNT(*) FROM rating AS other WHERE other.rating > rating.rating) + 1,
+                account_id, username, rating, games_played
+            FROM rating ORDER BY rating DESC, account_id LIMIT ?1 OFFSET ?2",
+        )?;
+        let entries = statement
+            .query_map(params![limit, offset], RatingRepository::read_entry)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(entries)
+    }
+
+    pub fn get_entry(
+        &self,
+        account_id: u32,
+    ) -> Result<Option<LeaderboardEntry>, Box<dyn Error + Send + Sync>> {
+        Ok(self
+            .connection
+            .lock()
+            .unwrap()
+            .query_row(
+                "SELECT
+                    (SELECT COUNT(*) FROM rating AS other WHERE other.rating > rating.rating) + 1,
+                    account_id, username, rating, games_played
+                FROM rating WHERE account_id = ?1",
+                params![account_id],
+                RatingRepository::read_entry,
+            )
+            .optional()?)
+    }
+
+    pub fn count(&self) -> Result<u32, Box<dyn Error + Send + Sync>> {
+        Ok(self
+            .connection
+            .lock()
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM rating", (), |row| row.get(0))?)
+    }
+
+    fn read_entry(row: &rusqlite::Row) -> rusqlite::Result<LeaderboardEntry> {
+        Ok(LeaderboardEntry {
+            rank: row.get(0)?,
+            account_id: row.get(1)?,
+            username: row.get(2)?,
+            rating: row.get(3)?,
+            games_played: row.get(4)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_rating(
+        repository: &RatingRepository,
+        account_id: u32,
+        username: &str,
+        rating: u32,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        RatingRepository::write_rating(
+            &repository.connection.lock().unwrap(),
+            &RatingUpdate {
+                account_id,
+                username: String::from(username),
+                rating,
+            },
+        )
+    }
+
+    #[test]
+    fn write_rating_with_new_account_should_be_found() -> Result<(), Box<dyn Error + Send + Sync>> {
+        let repository = RatingRepository::open_in_memory()?;
+        assert!(repository.get_rating(1)?.is_none());
+        write_rating(&repository, 1, "test", 1516)?;
+        write_rating(&repository, 1, "test", 1530)?;
+        assert_eq!(repository.get_rating(1)?, Some(1530));
+        assert_eq!(repository.get_entry(1)?.unwrap().games_played, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn get_leaderboard_with_ratings_should_rank_highest_first(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let repository = RatingRepository::open_in_memory()?;
+        write_rating(&repository, 1, "test1", 1400)?;
+        write_rating(&repository, 2, "test2", 1600)?;
+        write_rating(&repository, 3, "test3", 1600)?;
+        let entries = repository.get_leaderboard(0, 10)?;
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| (entry.rank, entry.account_id))
+                .collect::<Vec<_>>(),
+            vec![(1, 2), (1, 3), (3, 1)]
+        );
+        assert_eq!(repository.get_leaderboard(2, 10)?.len(), 1);
+        assert_eq!(repository.get_entry(1)?.unwrap().rank, 3);
+        assert_eq!(repository.count()?, 3);
+        Ok(())
+    }
+}
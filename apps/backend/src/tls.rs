@@ -0,0 +1,163 @@
+use std::error::Error;
+use std::fmt::{self, Debug, Formatter};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use tokio_rustls::{
+    rustls::{Certificate, PrivateKey, ServerConfig},
+    server::TlsStream,
+    TlsAcceptor,
+};
+
+use crate::transport::Stream;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsSettings {
+    pub cert_path: PathBuf,
+    pub key_path: PathBuf,
+}
+
+impl TlsSettings {
+    pub fn from_env() -> Result<Option<Self>, Box<dyn Error + Send + Sync>> {
+        match (
+            std::env::var("TLS_CERT_PATH"),
+            std::env::var("TLS_KEY_PATH"),
+        ) {
+            (Ok(cert_path), Ok(key_path)) => Ok(Some(Self {
+                cert_path: PathBuf::from(cert_path),
+                key_path: PathBuf::from(key_path),
+            })),
+            (Err(_), Err(_)) => Ok(None),
+            _ => Err("TLS_CERT_PATH and TLS_KEY_PATH must be set together".into()),
+        }
+    }
+
+    pub fn load(&self) -> Result<Tls, Box<dyn Error + Send + Sync>> {
+        let config = ServerConfig::builder()
+            .with_safe_defaults()
+            .with_no_client_auth()
+            .with_single_cert(
+                TlsSettings::read_certs(&self.cert_path)?,
+                TlsSettings::read_key(&self.key_path)?,
+            )?;
+        Ok(Tls {
+            acceptor: TlsAcceptor::from(Arc::new(config)),
+        })
+    }
+
+    fn read_certs(path: &Path) -> Result<Vec<Certificate>, Box<dyn Error + Send + Sync>> {
+        let certs = rustls_pemfile::certs(&mut BufReader::new(File::open(path)?))?;
+        if certs.is_empty() {
+            return Err(format!("No certificate found in {}", path.display()).into());
+        }
+        Ok(certs.into_iter().map(Certificate).collect())
+    }
+
+    fn read_key(path: &Path) -> Result<PrivateKey, Box<dyn Error + Send + Sync>> {
+        let mut reader = BufReader::new(File::open(path)?);
+        while let Some(item) = rustls_pemfile::read_one(&mut reader)? {
+            match item {
+                rustls_pemfile::Item::RSAKey(key)
+                | rustls_pemfile::Item::PKCS8Key(key)
+                | rustls_pemfile::Item::ECKey(key) => return Ok(PrivateKey(key)),
+                _ => continue,
+            }
+        }
+        Err(format!("No private key found in {}", path.display()).into())
+    }
+}
+
+#[derive(Clone)]
+pub struct Tls {
+    acceptor: TlsAcceptor,
+}
+
+impl Debug for Tls {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tls").finish_non_exhaustive()
+    }
+}
+
+impl Tls {
+    pub async fn accept<S: Stream>(
+        &self,
+        stream: S,
+    ) -> Result<TlsStream<S>, Box<dyn Error + Send + Sync>> {
+        Ok(self.acceptor.accept(stream).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{duplex, AsyncReadExt, AsyncWriteExt};
+    use tokio_rustls::{
+        rustls::{ClientConfig, RootCertStore, ServerName},
+        TlsConnector,
+    };
+
+    fn write_self_signed(
+        name: &str,
+    ) -> Result<(TlsSettings, Certificate), Box<dyn Error + Send + Sync>> {
+        let cert = rcgen::generate_simple_self_signed(vec![String::from("localhost")])?;
+        let dir = std::env::temp_dir();
+        let settings = TlsSettings {
+            cert_path: dir.join(format!("letterlegend-{name}-{}.crt", std::process::id())),
+            key_path: dir.join(format!("letterlegend-{name}-{}.key", std::process::id())),
+        };
+        std::fs::write(&settings.cert_path, cert.serialize_pem()?)?;
+        std::fs::write(&settings.key_path, cert.serialize_private_key_pem())?;
+        Ok((settings, Certificate(cert.serialize_der()?)))
+    }
+
+    #[test]
+    fn load_with_missing_files_should_return_error() {
+        let settings = TlsSettings {
+            cert_path: PathBuf::from("not-exist.crt"),
+            key_path: PathBuf::from("not-exist.key"),
+        };
+        assert!(settings.load().is_err());
+    }
+
+    #[test]
+    fn load_with_key_as_certificate_should_return_error() -> Result<(), Box<dyn Error + Send + Sync>>
+    {
+        let (settings, _) = write_self_signed("swapped")?;
+        let swapped = TlsSettings {
+            cert_path: settings.key_path.clone(),
+            key_path: settings.key_path,
+        };
+        assert!(swapped.load().is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn accept_with_self_signed_certificate_should_carry_data(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let (settings, cert) = write_self_signed("handshake")?;
+        let tls = settings.load()?;
+        let mut roots = RootCertStore::empty();
+        roots.add(&cert)?;
+        let connector = TlsConnector::from(Arc::new(
+            ClientConfig::builder()
+                .with_safe_defaults()
+                .with_root_certificates(roots)
+                .with_no_client_auth(),
+        ));
+
+        let (client, server) = duplex(4096);
+        let accept = tokio::spawn(async move { tls.accept(server).await });
+        let mut client = connector
+            .connect(ServerName::try_from("localhost")?, client)
+            .await?;
+        let mut server = accept.await??;
+        client.write_all(&[1, 2, 3]).await?;
+        client.flush().await?;
+        let mut buf = [0; 3];
+        server.read_exact(&mut buf).await?;
+        assert_eq!(buf, [1, 2, 3]);
+        Ok(())
+    }
+}
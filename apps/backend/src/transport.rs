@@ -0,0 +1,153 @@
+use std::error::Error;
+
+use bytes::BytesMut;
+use futures_util::{
+    stream::{SplitSink, SplitStream},
+    SinkExt, StreamExt,
+};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadHalf, WriteHalf};
+use tokio_tungstenite::{
+    tungstenite::{protocol::WebSocketConfig, Message},
+    WebSocketStream,
+};
+
+use crate::connection::MAX_BUFFER_LEN;
+
+pub trait Stream: AsyncRead + AsyncWrite + Send + Unpin {}
+
+impl<T: AsyncRead + AsyncWrite + Send + Unpin> Stream for T {}
+
+pub type BoxedStream = Box<dyn Stream>;
+
+pub enum TransportReader {
+    Raw(ReadHalf<BoxedStream>),
+    WebSocket(SplitStream<WebSocketStream<BoxedStream>>),
+}
+
+pub enum TransportWriter {
+    Raw(WriteHalf<BoxedStream>),
+    WebSocket(SplitSink<WebSocketStream<BoxedStream>, Message>),
+}
+
+pub fn raw(stream: BoxedStream) -> (TransportReader, TransportWriter) {
+    let (reader, writer) = tokio::io::split(stream);
+    (TransportReader::Raw(reader), TransportWriter::Raw(writer))
+}
+
+pub async fn websocket(
+    stream: BoxedStream,
+) -> Result<(TransportReader, TransportWriter), Box<dyn Error + Send + Sync>> {
+    // a single message must fit in the connection's read buffer
+    let config = WebSocketConfig {
+        max_message_size: Some(MAX_BUFFER_LEN),
+        max_frame_size: Some(MAX_BUFFER_LEN),
+        ..Default::default()
+    };
+    let (writer, reader) = tokio_tungstenite::accept_async_with_config(stream, Some(config))
+        .await?
+        .split();
+    Ok((
+        TransportReader::WebSocket(reader),
+        TransportWriter::WebSocket(writer),
+    ))
+}
+
+impl TransportReader {
+    pub async fn read_buf(
+        &mut self,
+        buf: &mut BytesMut,
+    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        match self {
+            TransportReader::Raw(reader) => Ok(reader.read_buf(buf).await?),
+            TransportReader::WebSocket(reader) => loop {
+                match reader.next().await {
+                    Some(Ok(Message::Binary(data))) => {
+                        // an empty message must not be mistaken for the end of stream
+                        if data.is_empty() {
+                            continue;
+                        }
+                        buf.extend_from_slice(&data);
+                        return Ok(data.len());
+                    }
+                    // control frames are answered by tungstenite itself
+                    Some(Ok(Message::Ping(_) | Message::Pong(_) | Message::Frame(_))) => continue,
+                    Some(Ok(Message::Text(_))) => return Err("unexpected text message".into()),
+                    Some(Ok(Message::Close(_))) | None => return Ok(0),
+                    Some(Err(e)) => return Err(e.into()),
+                }
+            },
+        }
+    }
+}
+
+impl TransportWriter {
+    pub async fn write_frame(&mut self, data: Vec<u8>) -> Result<(), Box<dyn Error>> {
+        match self {
+            TransportWriter::Raw(writer) => {
+                writer.write_all(&data).await?;
+                Ok(())
+            }
+            TransportWriter::WebSocket(writer) => {
+                writer.send(Message::Binary(data)).await?;
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::duplex;
+
+    #[tokio::test]
+    async fn read_buf_with_raw_stream_should_return_written_bytes(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let (client, server) = duplex(64);
+        let (mut reader, _) = raw(Box::new(server));
+        let (_, mut writer) = raw(Box::new(client));
+        writer.write_frame(vec![1, 2, 3]).await.unwrap();
+        let mut buf = BytesMut::new();
+        assert_eq!(reader.read_buf(&mut buf).await?, 3);
+        assert_eq!(&buf[..], &[1, 2, 3]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_buf_with_websocket_binary_message_should_return_message_bytes(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let (client, server) = duplex(1024);
+        let accept = tokio::spawn(websocket(Box::new(server)));
+        let (mut client, _) = tokio_tungstenite::client_async("ws://localhost/", client).await?;
+        let (mut reader, _) = accept.await??;
+        client.send(Message::Binary(vec![1, 2, 3])).await?;
+        let mut buf = BytesMut::new();
+        assert_eq!(reader.read_buf(&mut buf).await?, 3);
+        assert_eq!(&buf[..], &[1, 2, 3]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_buf_with_websocket_text_message_should_return_error(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let (client, server) = duplex(1024);
+        let accept = tokio::spawn(websocket(Box::new(server)));
+        let (mut client, _) = tokio_tungstenite::client_async("ws://localhost/", client).await?;
+        let (mut reader, _) = accept.await??;
+        client.send(Message::Text(String::from("hello"))).await?;
+        assert!(reader.read_buf(&mut BytesMut::new()).await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_buf_with_closed_websocket_should_return_zero(
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let (client, server) = duplex(1024);
+        let accept = tokio::spawn(websocket(Box::new(server)));
+        let (mut client, _) = tokio_tungstenite::client_async("ws://localhost/", client).await?;
+        let (mut reader, _) = accept.await??;
+        client.close(None).await?;
+        assert_eq!(reader.read_buf(&mut BytesMut::new()).await?, 0);
+        Ok(())
+    }
+}
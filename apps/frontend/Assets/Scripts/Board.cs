@@ -73,6 +73,9 @@ public void Update()
                 //Debug.Log(res.Players);
                 break;
             case GameEvent.Shuffle:
+            case GameEvent.Afk:
+            case GameEvent.Active:
+            case GameEvent.KickVote:
                 break;
             case GameEvent.PlaceTile:
                 SetBoard(res.Board);
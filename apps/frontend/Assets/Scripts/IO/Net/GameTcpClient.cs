@@ -17,6 +17,8 @@ namespace IO.Net
 {
     public class GameTcpClient
     {
+        // must match PROTOCOL_VERSION in the backend
+        private const byte ProtocolVersion = 1;
         private readonly string _host;
         private readonly int _port;
         private readonly TcpClient _client;
@@ -30,7 +32,8 @@ public class GameTcpClient
         enum Broadcast
         {
             Lobby = 0,
-            Game = 1
+            Game = 1,
+            Ping = 4
         }
 
         public GameTcpClient(string host, int port)
@@ -95,6 +98,11 @@ private void Loop()
                             var gameRes = GameBroadcast.Parser.ParseFrom(buf);
                             Board.BroadcastEnqueue(gameRes);
                         }
+                        else if (state == (uint)(Broadcast.Ping))
+                        {
+                            var ping = PingBroadcast.Parser.ParseFrom(buf);
+                            _ = Pong(ping);
+                        }
                         else if (_taskMap.ContainsKey(state))
                         {
                             _taskMap[state].SetResult(buf);
@@ -115,7 +123,8 @@ public async Task<Player> ConnectAsync(string name)
             Loop();
             var req = new ConnectRequest()
             {
-                Name = name
+                Name = name,
+                ProtocolVersion = ProtocolVersion
             };
             var stream = new MemoryStream();
             req.WriteTo(stream);
@@ -259,6 +268,20 @@ public async Task HeartBeat()
             }
         }
 
+        private async Task Pong(PingBroadcast ping)
+        {
+            var req = new PongRequest()
+            {
+                Sequence = ping.Sequence,
+                Timestamp = ping.Timestamp,
+            };
+
+            var stream = new MemoryStream();
+            req.WriteTo(stream);
+
+            await Rpc(Operation.Pong, stream.ToArray(), false);
+        }
+
         public async Task<List<HandCard>> Cancel(uint x, uint y)
         {
             var req = new CancelRequest()
@@ -331,7 +354,7 @@ private async Task RpcCall(Operation operation, byte[] data, uint state)
             if (BitConverter.IsLittleEndian)
                 Array.Reverse(t);
             var outputStream = new MemoryStream();
-            await outputStream.WriteAsync(new byte[] { (byte)operation, 0, 0, 0 });
+            await outputStream.WriteAsync(new byte[] { (byte)operation, ProtocolVersion, 0, 0 });
             await outputStream.WriteAsync(t);
             await outputStream.WriteAsync(BitConverter.GetBytes(data.Length));
             await outputStream.WriteAsync(data);
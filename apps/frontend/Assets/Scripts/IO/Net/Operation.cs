@@ -15,6 +15,26 @@ public enum Operation
         FinishTurn,
         GetNewCard,
         Cancel,
-        Exit
+        Exit,
+        UpdateLobby,
+        Kick,
+        Chat,
+        QuickPlay,
+        CancelQuickPlay,
+        Rematch,
+        Spectate,
+        StopSpectating,
+        Reconnect,
+        GetProfile,
+        GetMatchHistory,
+        GetLeaderboard,
+        AddFriend,
+        AcceptFriend,
+        RemoveFriend,
+        ListFriends,
+        Invite,
+        VoteKick,
+        Pong,
+        GetBoard
     }
 }
\ No newline at end of file
@@ -1,7 +1,6 @@
 using System.Collections;
 using System.Collections.Generic;
 using Protos.Lobby;
-using Protos.Player;
 using TMPro;
 using UnityEngine;
 
@@ -10,7 +9,7 @@ public class PlayerItem : MonoBehaviour
     public TMP_Text playerName;
     public TMP_Text id;
 
-    public void SetText(Lobby lobby, Player p)
+    public void SetText(Lobby lobby, LobbyPlayer p)
     {
         //TODO Make lead object
         playerName.SetText(p.Name);
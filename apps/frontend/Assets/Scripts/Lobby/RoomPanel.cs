@@ -52,6 +52,14 @@ public void Update()
                 ClearList();
                 UpdateRoom();
                 break;
+            case LobbyEvent.Ready:
+            case LobbyEvent.RematchVote:
+            case LobbyEvent.Rematch:
+            case LobbyEvent.AutoStart:
+            case LobbyEvent.AutoStartCancel:
+                Lobby = res.Lobby;
+                UpdateRoom();
+                break;
             case LobbyEvent.Destroy:
                 lobbyPanel.SetActive(true);
                 gameObject.SetActive(false);
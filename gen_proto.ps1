@@ -1,7 +1,7 @@
 $outBasePath = "apps/frontend/Assets/Scripts/Protos/"
 $protoBasePath = "apps/backend/src/proto/"
 
-$protoFolderMappingList = @(@("Control", "control/*.proto"), @("Error", "error/*.proto"), @("Game", "game/*.proto"), @("Lobby", "lobby/*.proto"), @("Player", "player/*.proto"))
+$protoFolderMappingList = @(@("Chat", "chat/*.proto"), @("Control", "control/*.proto"), @("Error", "error/*.proto"), @("Friend", "friend/*.proto"), @("Game", "game/*.proto"), @("Lobby", "lobby/*.proto"), @("Matchmaking", "matchmaking/*.proto"), @("Player", "player/*.proto"), @("Stats", "stats/*.proto"))
 
 for ($i = 0; $i -lt $protoFolderMappingList.Length; $i++) {
     $outPath = $outBasePath + $protoFolderMappingList[$i][0]